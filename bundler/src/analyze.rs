@@ -0,0 +1,213 @@
+use indexmap::IndexMap;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Visit, VisitWith};
+use swc_ecma_ast::*;
+use swc_ecma_utils::{find_ids, id, Id};
+
+/// Name of a binding imported from another module.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum Imported {
+    /// `import { foo } from 'src'`, or `default` for `import foo from 'src'`
+    Named(JsWord),
+    /// `import * as ns from 'src'`
+    Namespace,
+}
+
+/// Import / export statements of a module.
+#[derive(Debug, Default)]
+pub(crate) struct ModuleInfo {
+    /// Sources of imports and re-exports, in the order they appear.
+    pub sources: Vec<JsWord>,
+
+    /// Sources of `import()` called with a string literal.
+    pub dynamic_sources: Vec<JsWord>,
+
+    /// Bindings created by import declarations.
+    pub imports: IndexMap<Id, (JsWord, Imported)>,
+
+    /// Exported local bindings, keyed by exported name.
+    ///
+    /// `export default expr` and anonymous default exports are stored as a
+    /// private `_default` binding, unless `expr` is an imported binding.
+    pub local_exports: IndexMap<JsWord, Ident>,
+
+    /// `export { foo as bar } from 'src'` and `export * as ns from 'src'`
+    pub reexports: IndexMap<JsWord, (JsWord, Imported)>,
+
+    /// `export * from 'src'`
+    pub export_alls: Vec<JsWord>,
+}
+
+impl ModuleInfo {
+    pub fn analyze(module: &Module) -> Self {
+        let mut info = ModuleInfo::default();
+        let mut default_ident = None;
+
+        for item in &module.body {
+            let decl = match item {
+                ModuleItem::ModuleDecl(decl) => decl,
+                ModuleItem::Stmt(..) => continue,
+            };
+
+            match decl {
+                ModuleDecl::Import(import) => {
+                    if import.type_only {
+                        continue;
+                    }
+                    info.add_source(&import.src.value);
+
+                    for s in &import.specifiers {
+                        let (local, imported) = match s {
+                            ImportSpecifier::Named(s) => (
+                                &s.local,
+                                Imported::Named(
+                                    s.imported.as_ref().unwrap_or(&s.local).sym.clone(),
+                                ),
+                            ),
+                            ImportSpecifier::Default(s) => {
+                                (&s.local, Imported::Named(js_word!("default")))
+                            }
+                            ImportSpecifier::Namespace(s) => (&s.local, Imported::Namespace),
+                        };
+
+                        info.imports
+                            .insert(id(local), (import.src.value.clone(), imported));
+                    }
+                }
+
+                ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => match decl {
+                    Decl::Class(ClassDecl { ident, .. }) | Decl::Fn(FnDecl { ident, .. }) => {
+                        info.local_exports.insert(ident.sym.clone(), ident.clone());
+                    }
+                    Decl::Var(var) => {
+                        let ids: Vec<Ident> = find_ids(&var.decls);
+                        for i in ids {
+                            info.local_exports.insert(i.sym.clone(), i);
+                        }
+                    }
+                    _ => {}
+                },
+
+                ModuleDecl::ExportDefaultDecl(export) => {
+                    let ident = match &export.decl {
+                        DefaultDecl::Class(ClassExpr {
+                            ident: Some(ident), ..
+                        })
+                        | DefaultDecl::Fn(FnExpr {
+                            ident: Some(ident), ..
+                        }) => ident.clone(),
+                        DefaultDecl::TsInterfaceDecl(..) => continue,
+                        _ => private_ident!("_default"),
+                    };
+                    info.local_exports.insert(js_word!("default"), ident);
+                }
+
+                ModuleDecl::ExportDefaultExpr(export) => {
+                    if let Expr::Ident(i) = &*export.expr {
+                        default_ident = Some(i.clone());
+                    }
+                    info.local_exports
+                        .insert(js_word!("default"), private_ident!("_default"));
+                }
+
+                ModuleDecl::ExportNamed(export) => {
+                    if export.type_only {
+                        continue;
+                    }
+
+                    match &export.src {
+                        Some(src) => {
+                            info.add_source(&src.value);
+
+                            for s in &export.specifiers {
+                                let (exported, imported) = match s {
+                                    ExportSpecifier::Named(s) => (
+                                        s.exported.as_ref().unwrap_or(&s.orig),
+                                        Imported::Named(s.orig.sym.clone()),
+                                    ),
+                                    ExportSpecifier::Namespace(s) => (&s.name, Imported::Namespace),
+                                    ExportSpecifier::Default(s) => {
+                                        (&s.exported, Imported::Named(js_word!("default")))
+                                    }
+                                };
+
+                                info.reexports
+                                    .insert(exported.sym.clone(), (src.value.clone(), imported));
+                            }
+                        }
+                        None => {
+                            for s in &export.specifiers {
+                                if let ExportSpecifier::Named(s) = s {
+                                    let exported = s.exported.as_ref().unwrap_or(&s.orig);
+                                    info.local_exports
+                                        .insert(exported.sym.clone(), s.orig.clone());
+                                }
+                            }
+                        }
+                    }
+                }
+
+                ModuleDecl::ExportAll(export) => {
+                    info.add_source(&export.src.value);
+                    info.export_alls.push(export.src.value.clone());
+                }
+
+                _ => {}
+            }
+        }
+
+        // `export default foo` of an imported binding exports the binding itself.
+        if let Some(i) = default_ident {
+            if info.imports.contains_key(&id(&i)) {
+                info.local_exports.insert(js_word!("default"), i);
+            }
+        }
+
+        module.visit_with(&mut DynamicImportFinder {
+            to: &mut info.dynamic_sources,
+        });
+
+        info
+    }
+
+    fn add_source(&mut self, src: &JsWord) {
+        if !self.sources.contains(src) {
+            self.sources.push(src.clone());
+        }
+    }
+}
+
+/// Returns the source of `import('src')`.
+pub(crate) fn dynamic_import_src(e: &CallExpr) -> Option<&JsWord> {
+    match e.callee {
+        ExprOrSuper::Expr(box Expr::Ident(Ident {
+            sym: js_word!("import"),
+            ..
+        })) => {}
+        _ => return None,
+    }
+
+    match e.args.first() {
+        Some(&ExprOrSpread {
+            spread: None,
+            expr: box Expr::Lit(Lit::Str(ref s)),
+        }) => Some(&s.value),
+        _ => None,
+    }
+}
+
+struct DynamicImportFinder<'a> {
+    to: &'a mut Vec<JsWord>,
+}
+
+impl Visit<CallExpr> for DynamicImportFinder<'_> {
+    fn visit(&mut self, e: &CallExpr) {
+        e.visit_children(self);
+
+        if let Some(src) = dynamic_import_src(e) {
+            if !self.to.contains(src) {
+                self.to.push(src.clone());
+            }
+        }
+    }
+}
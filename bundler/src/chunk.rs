@@ -0,0 +1,657 @@
+use crate::{
+    analyze::{dynamic_import_src, Imported},
+    id::ModuleId,
+    load::ModuleGraph,
+    BundleKind, Config, ModuleType,
+};
+use anyhow::{bail, Error};
+use fxhash::{FxHashMap, FxHashSet};
+use indexmap::{IndexMap, IndexSet};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Fold, FoldWith, Mark, SyntaxContext, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_transforms::{
+    helpers::{Helpers, InjectHelpers, HELPERS},
+    modules::common_js::common_js,
+};
+use swc_ecma_utils::{id, is_valid_ident, ExprFactory, Id};
+
+/// Creates a bundle by hoisting all statically imported modules into the
+/// scope of the entry module.
+///
+/// Modules are emitted in the order they are evaluated, and each imported
+/// binding is replaced with the binding it refers to.
+pub(crate) struct ChunkBuilder<'a> {
+    graph: &'a ModuleGraph,
+    config: &'a Config,
+    /// Names of all bundles. Used to rewrite `import()`.
+    chunk_names: &'a FxHashMap<ModuleId, String>,
+    entry: ModuleId,
+
+    /// Objects created for `import * as ns` and `export * as ns`.
+    namespaces: IndexMap<ModuleId, Ident>,
+    /// Bindings imported from external modules.
+    externals: IndexMap<JsWord, Vec<(Imported, Ident)>>,
+    /// `export * from 'external'` in the entry module.
+    external_export_alls: Vec<JsWord>,
+}
+
+impl<'a> ChunkBuilder<'a> {
+    pub fn new(
+        graph: &'a ModuleGraph,
+        config: &'a Config,
+        chunk_names: &'a FxHashMap<ModuleId, String>,
+        entry: ModuleId,
+    ) -> Self {
+        ChunkBuilder {
+            graph,
+            config,
+            chunk_names,
+            entry,
+            namespaces: Default::default(),
+            externals: Default::default(),
+            external_export_alls: Default::default(),
+        }
+    }
+
+    pub fn build(mut self, kind: &BundleKind) -> Result<Module, Error> {
+        let mut stmts = vec![];
+        for id in self.order() {
+            let hoisted = self.hoist(id)?;
+            stmts.extend(hoisted);
+        }
+
+        let mut exports = vec![];
+        for name in self.export_names(self.entry, &mut Default::default()) {
+            let local = self.resolve_export(self.entry, &name, &mut vec![])?;
+            exports.push((name, local));
+        }
+
+        // Objects are created before all modules, so getters are used to
+        // preserve live bindings.
+        let mut namespaces = vec![];
+        let mut i = 0;
+        while i < self.namespaces.len() {
+            let (&module, ident) = self.namespaces.get_index(i).unwrap();
+            let ident = ident.clone();
+            namespaces.push(self.namespace_object(module, ident)?);
+            i += 1;
+        }
+
+        let mut body = vec![];
+        for (src, specifiers) in self.externals.drain(..) {
+            body.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                span: DUMMY_SP,
+                specifiers: specifiers
+                    .into_iter()
+                    .map(|(imported, local)| match imported {
+                        Imported::Named(js_word!("default")) => {
+                            ImportSpecifier::Default(ImportDefaultSpecifier {
+                                span: DUMMY_SP,
+                                local,
+                            })
+                        }
+                        Imported::Named(name) => ImportSpecifier::Named(ImportNamedSpecifier {
+                            span: DUMMY_SP,
+                            local,
+                            imported: Some(quote_ident!(name)),
+                        }),
+                        Imported::Namespace => ImportSpecifier::Namespace(ImportStarAsSpecifier {
+                            span: DUMMY_SP,
+                            local,
+                        }),
+                    })
+                    .collect(),
+                src: quote_str!(src),
+                type_only: false,
+            })));
+        }
+        body.extend(namespaces.into_iter().map(ModuleItem::Stmt));
+        body.extend(stmts.into_iter().map(ModuleItem::Stmt));
+
+        match self.config.module {
+            ModuleType::Es | ModuleType::Cjs => {
+                if !exports.is_empty() {
+                    body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                        NamedExport {
+                            span: DUMMY_SP,
+                            specifiers: exports
+                                .into_iter()
+                                .map(|(name, local)| {
+                                    ExportSpecifier::Named(ExportNamedSpecifier {
+                                        span: DUMMY_SP,
+                                        orig: local,
+                                        exported: Some(quote_ident!(name)),
+                                    })
+                                })
+                                .collect(),
+                            src: None,
+                            type_only: false,
+                        },
+                    )));
+                }
+                body.extend(self.external_export_alls.drain(..).map(|src| {
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll {
+                        span: DUMMY_SP,
+                        src: quote_str!(src),
+                    }))
+                }));
+            }
+            ModuleType::Iife => {
+                if let Some(item) = body.iter().find_map(|item| match item {
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => Some(import),
+                    _ => None,
+                }) {
+                    bail!(
+                        "iife bundles cannot import external module `{}`",
+                        item.src.value
+                    )
+                }
+                if let Some(src) = self.external_export_alls.first() {
+                    bail!("iife bundles cannot re-export external module `{}`", src)
+                }
+
+                body = vec![ModuleItem::Stmt(self.wrap_iife(kind, body, exports)?)];
+            }
+        }
+
+        let module = Module {
+            span: DUMMY_SP,
+            body,
+            shebang: None,
+        };
+
+        if self.config.module == ModuleType::Cjs {
+            let module = HELPERS.set(&Helpers::new(false), || {
+                module
+                    .fold_with(&mut common_js(
+                        Mark::fresh(Mark::root()),
+                        Default::default(),
+                    ))
+                    .fold_with(&mut InjectHelpers)
+            });
+            return Ok(module);
+        }
+
+        Ok(module)
+    }
+
+    /// Returns statically imported modules in the order of evaluation.
+    fn order(&self) -> Vec<ModuleId> {
+        fn add(
+            graph: &ModuleGraph,
+            id: ModuleId,
+            visited: &mut FxHashSet<ModuleId>,
+            buf: &mut Vec<ModuleId>,
+        ) {
+            if !visited.insert(id) {
+                return;
+            }
+
+            let m = graph.get(id);
+            for src in &m.info.sources {
+                match m.deps.get(src) {
+                    Some(&dep) if !graph.get(dep).excluded => add(graph, dep, visited, buf),
+                    _ => {}
+                }
+            }
+
+            buf.push(id);
+        }
+
+        let mut buf = vec![];
+        add(self.graph, self.entry, &mut Default::default(), &mut buf);
+        buf
+    }
+
+    /// Converts module items of `module` into statements, and replaces imported
+    /// bindings with the bindings they refer to.
+    fn hoist(&mut self, module: ModuleId) -> Result<Vec<Stmt>, Error> {
+        let graph = self.graph;
+        let m = graph.get(module);
+        let default_export = m.info.local_exports.get(&js_word!("default"));
+
+        let mut stmts = vec![];
+        for item in m.module.body.iter().cloned() {
+            let decl = match item {
+                ModuleItem::Stmt(stmt) => {
+                    stmts.push(stmt);
+                    continue;
+                }
+                ModuleItem::ModuleDecl(decl) => decl,
+            };
+
+            match decl {
+                ModuleDecl::Import(import) => {
+                    if !m.deps.contains_key(&import.src.value) {
+                        self.externals.entry(import.src.value).or_default();
+                    }
+                }
+
+                ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => match decl {
+                    Decl::TsInterface(..) | Decl::TsTypeAlias(..) => {}
+                    Decl::TsEnum(..) | Decl::TsModule(..) => bail!(
+                        "typescript should be stripped before bundling ({})",
+                        m.path.display()
+                    ),
+                    decl => stmts.push(Stmt::Decl(decl)),
+                },
+
+                ModuleDecl::ExportDefaultDecl(export) => {
+                    let ident = default_export.cloned();
+                    match export.decl {
+                        DefaultDecl::Class(ClassExpr { class, .. }) => {
+                            stmts.push(Stmt::Decl(Decl::Class(ClassDecl {
+                                ident: ident.unwrap(),
+                                declare: false,
+                                class,
+                            })))
+                        }
+                        DefaultDecl::Fn(FnExpr { function, .. }) => {
+                            stmts.push(Stmt::Decl(Decl::Fn(FnDecl {
+                                ident: ident.unwrap(),
+                                declare: false,
+                                function,
+                            })))
+                        }
+                        DefaultDecl::TsInterfaceDecl(..) => {}
+                    }
+                }
+
+                ModuleDecl::ExportDefaultExpr(export) => {
+                    let ident = default_export.cloned().unwrap();
+                    match &*export.expr {
+                        Expr::Ident(i) if id(i) == id(&ident) => continue,
+                        _ => {}
+                    }
+
+                    stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                        span: export.span,
+                        kind: VarDeclKind::Var,
+                        declare: false,
+                        decls: vec![VarDeclarator {
+                            span: DUMMY_SP,
+                            name: Pat::Ident(ident),
+                            init: Some(export.expr),
+                            definite: false,
+                        }],
+                    })));
+                }
+
+                ModuleDecl::ExportNamed(..) => {}
+
+                ModuleDecl::ExportAll(export) => {
+                    if !m.deps.contains_key(&export.src.value) {
+                        if module != self.entry {
+                            bail!(
+                                "`export * from '{}'` is only supported in the entry module, but \
+                                 found in {}",
+                                export.src.value,
+                                m.path.display()
+                            )
+                        }
+                        self.external_export_alls.push(export.src.value);
+                    }
+                }
+
+                ModuleDecl::TsImportEquals(..)
+                | ModuleDecl::TsExportAssignment(..)
+                | ModuleDecl::TsNamespaceExport(..) => bail!(
+                    "typescript should be stripped before bundling ({})",
+                    m.path.display()
+                ),
+            }
+        }
+
+        let mut renames = FxHashMap::default();
+        for (local, (src, imported)) in &m.info.imports {
+            let to = self.resolve_import(module, src, imported, &mut vec![])?;
+            renames.insert(local.clone(), to);
+        }
+
+        let mut dynamic_imports = FxHashMap::default();
+        for src in &m.info.dynamic_sources {
+            if let Some(name) = m.deps.get(src).and_then(|dep| self.chunk_names.get(dep)) {
+                dynamic_imports.insert(src.clone(), JsWord::from(format!("./{}.js", name)));
+            }
+        }
+
+        Ok(stmts.fold_with(&mut Renamer {
+            renames: &renames,
+            dynamic_imports: &dynamic_imports,
+        }))
+    }
+
+    /// Returns names exported by `module`, including `export *`.
+    fn export_names(
+        &self,
+        module: ModuleId,
+        visited: &mut FxHashSet<ModuleId>,
+    ) -> IndexSet<JsWord> {
+        let mut names = IndexSet::new();
+        if !visited.insert(module) {
+            return names;
+        }
+
+        let m = self.graph.get(module);
+        names.extend(m.info.local_exports.keys().cloned());
+        names.extend(m.info.reexports.keys().cloned());
+
+        for src in &m.info.export_alls {
+            if let Some(&dep) = m.deps.get(src) {
+                names.extend(
+                    self.export_names(dep, visited)
+                        .into_iter()
+                        .filter(|name| *name != js_word!("default")),
+                );
+            }
+        }
+
+        names
+    }
+
+    /// Returns the binding exported from `module` as `name`.
+    fn resolve_export(
+        &mut self,
+        module: ModuleId,
+        name: &JsWord,
+        stack: &mut Vec<(ModuleId, JsWord)>,
+    ) -> Result<Ident, Error> {
+        let graph = self.graph;
+        let m = graph.get(module);
+        if stack.iter().any(|(i, n)| *i == module && n == name) {
+            bail!(
+                "circular re-export of `{}` found in {}",
+                name,
+                m.path.display()
+            )
+        }
+        stack.push((module, name.clone()));
+
+        if let Some(local) = m.info.local_exports.get(name) {
+            return match m.info.imports.get(&id(local)) {
+                Some((src, imported)) => self.resolve_import(module, src, imported, stack),
+                None => Ok(local.clone()),
+            };
+        }
+
+        if let Some((src, imported)) = m.info.reexports.get(name) {
+            return self.resolve_import(module, src, imported, stack);
+        }
+
+        if *name != js_word!("default") {
+            for src in &m.info.export_alls {
+                if let Some(&dep) = m.deps.get(src) {
+                    if self
+                        .export_names(dep, &mut Default::default())
+                        .contains(name)
+                    {
+                        return self.resolve_export(dep, name, stack);
+                    }
+                }
+            }
+        }
+
+        bail!("{} does not export `{}`", m.path.display(), name)
+    }
+
+    /// Returns the binding referenced by an import from `src` in `module`.
+    fn resolve_import(
+        &mut self,
+        module: ModuleId,
+        src: &JsWord,
+        imported: &Imported,
+        stack: &mut Vec<(ModuleId, JsWord)>,
+    ) -> Result<Ident, Error> {
+        let dep = match self.graph.get(module).deps.get(src) {
+            Some(&dep) => dep,
+            None => return Ok(self.external(src, imported)),
+        };
+
+        match imported {
+            Imported::Namespace => Ok(self.namespace(dep)),
+            Imported::Named(name) => self.resolve_export(dep, name, stack),
+        }
+    }
+
+    fn namespace(&mut self, module: ModuleId) -> Ident {
+        let graph = self.graph;
+        let path = &graph.get(module).path;
+        self.namespaces
+            .entry(module)
+            .or_insert_with(|| {
+                let name = path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                private_ident!(local_name(&name))
+            })
+            .clone()
+    }
+
+    fn external(&mut self, src: &JsWord, imported: &Imported) -> Ident {
+        let bindings = self.externals.entry(src.clone()).or_default();
+        if let Some((_, local)) = bindings.iter().find(|(i, _)| *i == *imported) {
+            return local.clone();
+        }
+
+        let local = match imported {
+            Imported::Named(name) if *name != js_word!("default") => private_ident!(name.clone()),
+            _ => private_ident!(local_name(src)),
+        };
+        bindings.push((imported.clone(), local.clone()));
+        local
+    }
+
+    /// Creates
+    ///
+    /// ```js
+    /// var ns = {
+    ///     get foo() {
+    ///         return foo;
+    ///     }
+    /// };
+    /// ```
+    fn namespace_object(&mut self, module: ModuleId, ident: Ident) -> Result<Stmt, Error> {
+        let mut props = vec![];
+        for name in self.export_names(module, &mut Default::default()) {
+            let local = self.resolve_export(module, &name, &mut vec![])?;
+
+            props.push(PropOrSpread::Prop(box Prop::Getter(GetterProp {
+                span: DUMMY_SP,
+                key: prop_name(name),
+                type_ann: None,
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![Stmt::Return(ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(box Expr::Ident(local)),
+                    })],
+                }),
+            })));
+        }
+
+        Ok(Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(ident),
+                init: Some(box Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props,
+                })),
+                definite: false,
+            }],
+        })))
+    }
+
+    /// Creates `var name = function () { ...; return { foo: foo }; }();`
+    fn wrap_iife(
+        &self,
+        kind: &BundleKind,
+        body: Vec<ModuleItem>,
+        exports: Vec<(JsWord, Ident)>,
+    ) -> Result<Stmt, Error> {
+        let mut stmts = body
+            .into_iter()
+            .map(|item| match item {
+                ModuleItem::Stmt(stmt) => stmt,
+                ModuleItem::ModuleDecl(..) => unreachable!("module declarations are removed"),
+            })
+            .collect::<Vec<_>>();
+
+        let has_exports = !exports.is_empty();
+        if has_exports {
+            stmts.push(Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(box Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: exports
+                        .into_iter()
+                        .map(|(name, local)| {
+                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                                key: prop_name(name),
+                                value: box Expr::Ident(local),
+                            }))
+                        })
+                        .collect(),
+                })),
+            }));
+        }
+
+        let call = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: ExprOrSuper::Expr(box Expr::Fn(FnExpr {
+                ident: None,
+                function: Function {
+                    params: vec![],
+                    decorators: vec![],
+                    span: DUMMY_SP,
+                    body: Some(BlockStmt {
+                        span: DUMMY_SP,
+                        stmts,
+                    }),
+                    is_generator: false,
+                    is_async: false,
+                    type_params: None,
+                    return_type: None,
+                },
+            })),
+            args: vec![],
+            type_args: None,
+        });
+
+        if !has_exports {
+            return Ok(Stmt::Expr(ExprStmt {
+                span: DUMMY_SP,
+                expr: box call,
+            }));
+        }
+
+        let name: JsWord = kind.name().into();
+        if !is_valid_ident(&name) {
+            bail!(
+                "name of an iife bundle with exports should be a valid identifier, but got `{}`",
+                name
+            )
+        }
+
+        Ok(Stmt::Decl(Decl::Var(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            declare: false,
+            decls: vec![VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(quote_ident!(name)),
+                init: Some(box call),
+                definite: false,
+            }],
+        })))
+    }
+}
+
+fn prop_name(name: JsWord) -> PropName {
+    if is_valid_ident(&name) {
+        PropName::Ident(quote_ident!(name))
+    } else {
+        PropName::Str(quote_str!(name))
+    }
+}
+
+/// Creates a valid identifier like `_foo_bar` from `./foo-bar`.
+fn local_name(s: &str) -> String {
+    let mut buf = String::from("_");
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '$' {
+            buf.push(c);
+        } else if !buf.ends_with('_') {
+            buf.push('_');
+        }
+    }
+    buf
+}
+
+/// Replaces imported bindings and sources of `import()`.
+struct Renamer<'a> {
+    renames: &'a FxHashMap<Id, Ident>,
+    dynamic_imports: &'a FxHashMap<JsWord, JsWord>,
+}
+
+impl Fold<Ident> for Renamer<'_> {
+    fn fold(&mut self, i: Ident) -> Ident {
+        match self.renames.get(&id(&i)) {
+            Some(to) => Ident {
+                sym: to.sym.clone(),
+                span: i.span.with_ctxt(to.span.ctxt()),
+                ..i
+            },
+            None => i,
+        }
+    }
+}
+
+impl Fold<Prop> for Renamer<'_> {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            // `{ foo }` should not become `{ bar }`
+            Prop::Shorthand(i) if self.renames.contains_key(&id(&i)) => {
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(Ident {
+                        span: i.span.with_ctxt(SyntaxContext::empty()),
+                        ..i.clone()
+                    }),
+                    value: box Expr::Ident(i.fold_with(self)),
+                })
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<CallExpr> for Renamer<'_> {
+    fn fold(&mut self, e: CallExpr) -> CallExpr {
+        let mut e = e.fold_children(self);
+
+        let src = match dynamic_import_src(&e).and_then(|src| self.dynamic_imports.get(src)) {
+            Some(src) => src.clone(),
+            None => return e,
+        };
+        e.args[0] = Lit::Str(quote_str!(src)).as_arg();
+        e
+    }
+}
+
+impl Fold<MemberExpr> for Renamer<'_> {
+    fn fold(&mut self, e: MemberExpr) -> MemberExpr {
+        if e.computed {
+            return e.fold_children(self);
+        }
+
+        MemberExpr {
+            obj: e.obj.fold_with(self),
+            ..e
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use fxhash::FxHashMap;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ModuleId(u32);
+
+impl fmt::Display for ModuleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ModuleIdGenerator {
+    v: u32,
+    cache: FxHashMap<PathBuf, ModuleId>,
+}
+
+impl ModuleIdGenerator {
+    /// Returns `true` as the second element if `path` is seen for the first
+    /// time.
+    pub fn gen(&mut self, path: &Path) -> (ModuleId, bool) {
+        if let Some(&id) = self.cache.get(path) {
+            return (id, false);
+        }
+
+        self.v += 1;
+        let id = ModuleId(self.v);
+        self.cache.insert(path.to_path_buf(), id);
+        (id, true)
+    }
+}
@@ -0,0 +1,207 @@
+#![feature(box_syntax)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+//! Ecmascript bundler built on top of [Load] and [Resolve].
+//!
+//! Statically imported modules are hoisted into the scope of the entry module
+//! (scope hoisting), and `hygiene()` is used to rename identifiers which
+//! conflict across modules. Each module loaded by `import()` becomes a
+//! separate bundle.
+
+#[macro_use]
+extern crate swc_ecma_utils;
+
+pub use self::id::ModuleId;
+use self::{chunk::ChunkBuilder, load::ModuleGraph, tree_shake::tree_shake};
+use anyhow::{bail, Context, Error};
+use fxhash::{FxHashMap, FxHashSet};
+use std::{collections::HashMap, path::PathBuf};
+use swc_atoms::JsWord;
+use swc_common::{FoldWith, Globals, GLOBALS};
+use swc_ecma_ast::Module;
+use swc_ecma_transforms::{fixer, hygiene};
+pub use swc_ecma_utils::{load::Load, resolve::Resolve};
+
+mod analyze;
+mod chunk;
+mod id;
+mod load;
+mod tree_shake;
+
+/// Format of emitted bundles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleType {
+    /// Exports of the entry are emitted as `export` declarations.
+    Es,
+    /// Same as [ModuleType::Es], but converted with `modules::common_js`.
+    Cjs,
+    /// Exports of the entry are returned from an immediately invoked function
+    /// and stored in a variable named after the bundle.
+    Iife,
+}
+
+impl Default for ModuleType {
+    fn default() -> Self {
+        ModuleType::Es
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Config {
+    pub module: ModuleType,
+
+    /// Import sources which are not bundled.
+    ///
+    /// Imports of external modules are preserved by `es` and `cjs` output,
+    /// and rejected by `iife` output.
+    pub external_modules: Vec<JsWord>,
+
+    /// Removes unused exports using `dce`.
+    ///
+    /// Modules which are not used and belong to a package with
+    /// `"sideEffects": false` in `package.json` are excluded from bundles.
+    pub tree_shake: bool,
+}
+
+#[derive(Debug)]
+pub struct Bundle {
+    pub kind: BundleKind,
+    /// Id of the entry module of this bundle.
+    pub id: ModuleId,
+    pub module: Module,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleKind {
+    /// An entry passed to [Bundler::bundle].
+    Named { name: String },
+    /// A module loaded by `import()`.
+    ///
+    /// `import()` expressions are rewritten to `import('./${name}.js')`.
+    Lazy { name: String },
+}
+
+impl BundleKind {
+    pub fn name(&self) -> &str {
+        match self {
+            BundleKind::Named { name } | BundleKind::Lazy { name } => name,
+        }
+    }
+}
+
+/// Bundler for ecmascript modules.
+///
+/// `base` passed to [Resolve::resolve] is the path of the importing file.
+/// Modules returned by [Load::load] should not contain typescript-only
+/// syntax.
+pub struct Bundler<'a, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Marks for each module are created using this.
+    globals: &'a Globals,
+    loader: L,
+    resolver: R,
+    config: Config,
+}
+
+impl<'a, L, R> Bundler<'a, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    pub fn new(globals: &'a Globals, loader: L, resolver: R, config: Config) -> Self {
+        Bundler {
+            globals,
+            loader,
+            resolver,
+            config,
+        }
+    }
+
+    /// Bundles `entries`, keyed by the name of each output bundle.
+    ///
+    /// Returns a bundle for each entry, followed by a bundle for each module
+    /// loaded by `import()`. A module imported by multiple bundles is
+    /// included in all of them.
+    pub fn bundle(&self, entries: HashMap<String, PathBuf>) -> Result<Vec<Bundle>, Error> {
+        GLOBALS.set(self.globals, || {
+            let mut graph = ModuleGraph::default();
+
+            // Sorted to make output deterministic.
+            let mut entries = entries.into_iter().collect::<Vec<_>>();
+            entries.sort();
+
+            let mut chunks = vec![];
+            for (name, path) in entries {
+                let id = self
+                    .load_module(&mut graph, &path)
+                    .with_context(|| format!("failed to load entry `{}`", name))?;
+                chunks.push((BundleKind::Named { name }, id));
+            }
+
+            let dynamic_imports = graph.dynamic_imports();
+            if self.config.module == ModuleType::Iife {
+                if let Some(&id) = dynamic_imports.first() {
+                    bail!(
+                        "`import()` is not supported by iife bundles (imported {})",
+                        graph.get(id).path.display()
+                    )
+                }
+            }
+
+            let mut used_names = chunks
+                .iter()
+                .map(|(kind, _)| kind.name().to_string())
+                .collect::<FxHashSet<_>>();
+            for id in dynamic_imports {
+                if chunks.iter().any(|(_, entry)| *entry == id) {
+                    continue;
+                }
+
+                let stem = graph
+                    .get(id)
+                    .path
+                    .file_stem()
+                    .map(|s| s.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| String::from("chunk"));
+                let name = if used_names.contains(&stem) {
+                    format!("{}-{}", stem, id)
+                } else {
+                    stem
+                };
+                used_names.insert(name.clone());
+
+                chunks.push((BundleKind::Lazy { name }, id));
+            }
+
+            if self.config.tree_shake {
+                let roots = chunks.iter().map(|(_, id)| *id).collect::<Vec<_>>();
+                tree_shake(&mut graph, &roots);
+            }
+
+            let chunk_names = chunks
+                .iter()
+                .map(|(kind, id)| (*id, kind.name().to_string()))
+                .collect::<FxHashMap<_, _>>();
+
+            chunks
+                .into_iter()
+                .map(|(kind, id)| {
+                    let module = ChunkBuilder::new(&graph, &self.config, &chunk_names, id)
+                        .build(&kind)
+                        .with_context(|| format!("failed to bundle `{}`", kind.name()))?;
+                    let module = module.fold_with(&mut hygiene()).fold_with(&mut fixer());
+
+                    Ok(Bundle { kind, id, module })
+                })
+                .collect()
+        })
+    }
+
+    fn is_external(&self, src: &JsWord) -> bool {
+        self.config.external_modules.contains(src)
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{
+    analyze::ModuleInfo,
+    id::{ModuleId, ModuleIdGenerator},
+    Bundler, Load, Resolve,
+};
+use anyhow::{Context, Error};
+use fxhash::{FxHashMap, FxHashSet};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, Mark, SyntaxContext, Visit, VisitWith};
+use swc_ecma_ast::*;
+use swc_ecma_transforms::resolver_with_mark;
+use swc_ecma_utils::find_ids;
+
+/// A module processed by `resolver`.
+pub(crate) struct TransformedModule {
+    pub path: Arc<PathBuf>,
+    pub module: Module,
+    pub info: ModuleInfo,
+    /// Resolved import sources. External modules are not included.
+    pub deps: FxHashMap<JsWord, ModuleId>,
+    /// Set by tree shaking if the module is not used and has no side effects.
+    pub excluded: bool,
+}
+
+#[derive(Default)]
+pub(crate) struct ModuleGraph {
+    ids: ModuleIdGenerator,
+    modules: FxHashMap<ModuleId, TransformedModule>,
+}
+
+impl ModuleGraph {
+    pub fn get(&self, id: ModuleId) -> &TransformedModule {
+        self.modules
+            .get(&id)
+            .unwrap_or_else(|| panic!("module {} is not loaded", id))
+    }
+
+    pub fn get_mut(&mut self, id: ModuleId) -> &mut TransformedModule {
+        self.modules
+            .get_mut(&id)
+            .unwrap_or_else(|| panic!("module {} is not loaded", id))
+    }
+
+    /// Returns ids of all loaded modules, sorted.
+    pub fn ids(&self) -> Vec<ModuleId> {
+        let mut ids = self.modules.keys().copied().collect::<Vec<_>>();
+        ids.sort();
+        ids
+    }
+
+    /// Returns modules loaded by `import()`, sorted by id.
+    pub fn dynamic_imports(&self) -> Vec<ModuleId> {
+        let mut ids = self
+            .modules
+            .values()
+            .flat_map(|m| {
+                m.info
+                    .dynamic_sources
+                    .iter()
+                    .filter_map(move |src| m.deps.get(src).copied())
+            })
+            .collect::<Vec<_>>();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+}
+
+impl<L, R> Bundler<'_, L, R>
+where
+    L: Load,
+    R: Resolve,
+{
+    /// Loads the module at `path` and all of its dependencies.
+    pub(crate) fn load_module(
+        &self,
+        graph: &mut ModuleGraph,
+        path: &Path,
+    ) -> Result<ModuleId, Error> {
+        let (id, is_new) = graph.ids.gen(path);
+        if !is_new {
+            return Ok(id);
+        }
+
+        log::trace!("Loading {} as module {}", path.display(), id);
+
+        let (_, module) = self
+            .loader
+            .load(path)
+            .with_context(|| format!("failed to load {}", path.display()))?;
+
+        // Top-level bindings of each module get a distinct mark, so `hygiene()`
+        // can rename conflicting bindings after modules are merged.
+        let mark = Mark::fresh(Mark::root());
+        let module = module.fold_with(&mut resolver_with_mark(mark));
+        let module = unmark_globals(module, mark);
+        let info = ModuleInfo::analyze(&module);
+
+        let mut deps = FxHashMap::default();
+        for src in info.sources.iter().chain(info.dynamic_sources.iter()) {
+            if deps.contains_key(src) || self.is_external(src) {
+                continue;
+            }
+
+            let dep_path = self
+                .resolver
+                .resolve(path, src)
+                .with_context(|| format!("failed to resolve `{}` from {}", src, path.display()))?;
+            let dep = self.load_module(graph, &dep_path)?;
+            deps.insert(src.clone(), dep);
+        }
+
+        graph.modules.insert(
+            id,
+            TransformedModule {
+                path: Arc::new(path.to_path_buf()),
+                module,
+                info,
+                deps,
+                excluded: false,
+            },
+        );
+
+        Ok(id)
+    }
+}
+
+/// `resolver` marks unresolved references with the top-level mark, too. The
+/// mark is removed from them so that `hygiene()` does not rename references to
+/// a global variable used by multiple modules.
+fn unmark_globals(module: Module, mark: Mark) -> Module {
+    let ctxt = SyntaxContext::empty().apply_mark(mark);
+
+    let mut finder = TopLevelBindingFinder {
+        ctxt,
+        found: Default::default(),
+    };
+    module.visit_with(&mut finder);
+
+    module.fold_with(&mut GlobalUnmarker {
+        ctxt,
+        declared: finder.found,
+    })
+}
+
+struct TopLevelBindingFinder {
+    ctxt: SyntaxContext,
+    found: FxHashSet<JsWord>,
+}
+
+impl TopLevelBindingFinder {
+    fn add(&mut self, i: &Ident) {
+        if i.span.ctxt() == self.ctxt {
+            self.found.insert(i.sym.clone());
+        }
+    }
+}
+
+impl Visit<VarDeclarator> for TopLevelBindingFinder {
+    fn visit(&mut self, d: &VarDeclarator) {
+        let ids: Vec<Ident> = find_ids(&d.name);
+        for i in &ids {
+            self.add(i);
+        }
+
+        d.init.visit_with(self);
+    }
+}
+
+impl Visit<FnDecl> for TopLevelBindingFinder {
+    fn visit(&mut self, f: &FnDecl) {
+        self.add(&f.ident);
+        f.function.visit_with(self);
+    }
+}
+
+impl Visit<ClassDecl> for TopLevelBindingFinder {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.add(&c.ident);
+        c.class.visit_with(self);
+    }
+}
+
+impl Visit<FnExpr> for TopLevelBindingFinder {
+    fn visit(&mut self, f: &FnExpr) {
+        if let Some(i) = &f.ident {
+            self.add(i);
+        }
+        f.function.visit_with(self);
+    }
+}
+
+impl Visit<ClassExpr> for TopLevelBindingFinder {
+    fn visit(&mut self, c: &ClassExpr) {
+        if let Some(i) = &c.ident {
+            self.add(i);
+        }
+        c.class.visit_with(self);
+    }
+}
+
+impl Visit<ImportDecl> for TopLevelBindingFinder {
+    fn visit(&mut self, import: &ImportDecl) {
+        for s in &import.specifiers {
+            match s {
+                ImportSpecifier::Named(s) => self.add(&s.local),
+                ImportSpecifier::Default(s) => self.add(&s.local),
+                ImportSpecifier::Namespace(s) => self.add(&s.local),
+            }
+        }
+    }
+}
+
+struct GlobalUnmarker {
+    ctxt: SyntaxContext,
+    declared: FxHashSet<JsWord>,
+}
+
+impl Fold<Ident> for GlobalUnmarker {
+    fn fold(&mut self, i: Ident) -> Ident {
+        if i.span.ctxt() != self.ctxt || self.declared.contains(&i.sym) {
+            return i;
+        }
+
+        Ident {
+            span: i.span.with_ctxt(SyntaxContext::empty()),
+            ..i
+        }
+    }
+}
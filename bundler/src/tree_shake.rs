@@ -0,0 +1,224 @@
+use crate::{analyze::Imported, id::ModuleId, load::ModuleGraph};
+use fxhash::{FxHashMap, FxHashSet};
+use std::{
+    borrow::Cow,
+    collections::hash_map::Entry,
+    fs, mem,
+    path::{Path, PathBuf},
+};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{pass::Repeat, FoldWith, Mark, SyntaxContext, DUMMY_SP};
+use swc_ecma_ast::Module;
+use swc_ecma_transforms::optimization::simplify::dce::{self, dce};
+use swc_ecma_utils::Id;
+
+/// Exports of a module which are used by other modules.
+#[derive(Debug, Clone)]
+enum UsedExports {
+    All,
+    Names(FxHashSet<JsWord>),
+}
+
+impl Default for UsedExports {
+    fn default() -> Self {
+        UsedExports::Names(Default::default())
+    }
+}
+
+impl UsedExports {
+    /// Returns `true` if `name` was not marked before.
+    fn add(&mut self, name: Option<&JsWord>) -> bool {
+        match (self, name) {
+            (UsedExports::All, _) => false,
+            (this, None) => {
+                *this = UsedExports::All;
+                true
+            }
+            (UsedExports::Names(names), Some(name)) => names.insert(name.clone()),
+        }
+    }
+
+    fn contains(&self, name: &JsWord) -> bool {
+        match self {
+            UsedExports::All => true,
+            UsedExports::Names(names) => names.contains(name),
+        }
+    }
+}
+
+/// Removes unused exports from all modules in `graph`, using `dce`.
+///
+/// All exports of `roots` are treated as used. Modules which are only
+/// imported for side effects and belong to a package with `"sideEffects":
+/// false` are excluded from bundles.
+///
+/// Imported bindings are treated as used even if they are not referenced.
+pub(crate) fn tree_shake(graph: &mut ModuleGraph, roots: &[ModuleId]) {
+    let used = used_exports(graph, roots);
+
+    let mut side_effects_cache = Default::default();
+    for id in graph.ids() {
+        let used = used.get(&id).cloned().unwrap_or_default();
+
+        let m = graph.get_mut(id);
+        let used = match used {
+            UsedExports::All => continue,
+            UsedExports::Names(names) => names,
+        };
+
+        if used.is_empty() && !has_side_effects(&mut side_effects_cache, &m.path) {
+            log::debug!("Excluding {} as it has no side effects", m.path.display());
+            m.excluded = true;
+            continue;
+        }
+
+        let used = used
+            .into_iter()
+            .map(|name| {
+                // Variables are compared by their local id.
+                let ctxt = m
+                    .info
+                    .local_exports
+                    .get(&name)
+                    .map(|local| local.span.ctxt())
+                    .unwrap_or_else(SyntaxContext::empty);
+                (name, ctxt)
+            })
+            .collect::<Vec<Id>>();
+
+        let module = mem::replace(
+            &mut m.module,
+            Module {
+                span: DUMMY_SP,
+                body: vec![],
+                shebang: None,
+            },
+        );
+        m.module = module.fold_with(&mut Repeat::<_, Module>::new(dce(dce::Config {
+            used: Some(Cow::Owned(used)),
+            used_mark: Mark::fresh(Mark::root()),
+        })));
+    }
+}
+
+fn used_exports(graph: &ModuleGraph, roots: &[ModuleId]) -> FxHashMap<ModuleId, UsedExports> {
+    let mut a = UsageAnalyzer::default();
+    for &id in roots {
+        a.mark(id, None);
+    }
+
+    while let Some(id) = a.queue.pop() {
+        let m = graph.get(id);
+        let exports = a.used[&id].clone();
+
+        // All dependencies are evaluated, even if no binding is used.
+        for src in &m.info.sources {
+            if let Some(&dep) = m.deps.get(src) {
+                a.reach(dep);
+            }
+        }
+
+        for (src, imported) in m.info.imports.values() {
+            if let Some(&dep) = m.deps.get(src) {
+                a.mark(dep, imported_name(imported));
+            }
+        }
+
+        for src in &m.info.dynamic_sources {
+            if let Some(&dep) = m.deps.get(src) {
+                a.mark(dep, None);
+            }
+        }
+
+        for (exported, (src, imported)) in &m.info.reexports {
+            if !exports.contains(exported) {
+                continue;
+            }
+            if let Some(&dep) = m.deps.get(src) {
+                a.mark(dep, imported_name(imported));
+            }
+        }
+
+        // Names which are not exported by `m` itself may come from `export *`.
+        for src in &m.info.export_alls {
+            let dep = match m.deps.get(src) {
+                Some(&dep) => dep,
+                None => continue,
+            };
+
+            match &exports {
+                UsedExports::All => a.mark(dep, None),
+                UsedExports::Names(names) => {
+                    for name in names {
+                        if *name != js_word!("default")
+                            && !m.info.local_exports.contains_key(name)
+                            && !m.info.reexports.contains_key(name)
+                        {
+                            a.mark(dep, Some(name));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    a.used
+}
+
+#[derive(Default)]
+struct UsageAnalyzer {
+    used: FxHashMap<ModuleId, UsedExports>,
+    /// Modules which should be (re)visited.
+    queue: Vec<ModuleId>,
+}
+
+impl UsageAnalyzer {
+    /// Marks `name` of `module` as used. [None] means all exports.
+    fn mark(&mut self, module: ModuleId, name: Option<&JsWord>) {
+        self.reach(module);
+
+        if self.used.get_mut(&module).unwrap().add(name) {
+            self.queue.push(module);
+        }
+    }
+
+    fn reach(&mut self, module: ModuleId) {
+        if let Entry::Vacant(e) = self.used.entry(module) {
+            e.insert(Default::default());
+            self.queue.push(module);
+        }
+    }
+}
+
+fn imported_name(imported: &Imported) -> Option<&JsWord> {
+    match imported {
+        Imported::Named(name) => Some(name),
+        Imported::Namespace => None,
+    }
+}
+
+/// Returns `false` if the nearest `package.json` of `path` contains
+/// `"sideEffects": false`.
+fn has_side_effects(cache: &mut FxHashMap<PathBuf, bool>, path: &Path) -> bool {
+    let dir = match path.parent() {
+        Some(dir) => dir,
+        None => return true,
+    };
+    if let Some(&v) = cache.get(dir) {
+        return v;
+    }
+
+    let pkg = dir.join("package.json");
+    let v = if pkg.is_file() {
+        fs::read(&pkg)
+            .ok()
+            .and_then(|s| serde_json::from_slice::<serde_json::Value>(&s).ok())
+            .map(|pkg| pkg.get("sideEffects") != Some(&serde_json::Value::Bool(false)))
+            .unwrap_or(true)
+    } else {
+        has_side_effects(cache, dir)
+    };
+
+    cache.insert(dir.to_path_buf(), v);
+    v
+}
@@ -0,0 +1,241 @@
+#![feature(box_syntax)]
+
+use anyhow::{bail, Error};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use swc_bundler::{BundleKind, Bundler, Config, Load, ModuleType, Resolve};
+use swc_common::{errors::Handler, Globals, SourceFile, SourceMap};
+use swc_ecma_ast::Module;
+use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
+use swc_ecma_parser::{EsConfig, Parser, Session, SourceFileInput, Syntax};
+use testing::{StdErr, Tester};
+
+struct Loader<'a> {
+    cm: Arc<SourceMap>,
+    handler: &'a Handler,
+}
+
+impl Load for Loader<'_> {
+    fn load(&self, path: &Path) -> Result<(Arc<SourceFile>, Module), Error> {
+        let fm = self.cm.load_file(path)?;
+
+        let session = Session {
+            handler: self.handler,
+        };
+        let mut parser = Parser::new(
+            session,
+            Syntax::Es(EsConfig {
+                dynamic_import: true,
+                ..Default::default()
+            }),
+            SourceFileInput::from(&*fm),
+            None,
+        );
+        let module = match parser.parse_module() {
+            Ok(module) => module,
+            Err(mut err) => {
+                err.emit();
+                bail!("failed to parse {}", path.display())
+            }
+        };
+
+        Ok((fm, module))
+    }
+}
+
+struct Resolver;
+
+impl Resolve for Resolver {
+    fn resolve(&self, base: &Path, import: &str) -> Result<PathBuf, Error> {
+        let dir = base.parent().unwrap();
+        Ok(dir
+            .join(import.trim_start_matches("./"))
+            .with_extension("js"))
+    }
+}
+
+struct Handlers;
+
+impl swc_ecma_codegen::Handlers for Handlers {}
+
+/// Bundles `entry.js` in `dir`, and returns the code of each bundle.
+fn bundle(dir: &str, config: Config) -> Result<Vec<(BundleKind, String)>, StdErr> {
+    Tester::new().print_errors(|cm, handler| {
+        let globals = Globals::new();
+        let bundler = Bundler::new(
+            &globals,
+            Loader {
+                cm: cm.clone(),
+                handler: &handler,
+            },
+            Resolver,
+            config,
+        );
+
+        let mut entries = HashMap::new();
+        entries.insert(
+            String::from("main"),
+            Path::new("tests/fixture").join(dir).join("entry.js"),
+        );
+
+        let bundles = match bundler.bundle(entries) {
+            Ok(bundles) => bundles,
+            Err(err) => {
+                handler.err(&format!("{:?}", err));
+                return Err(());
+            }
+        };
+
+        Ok(bundles
+            .into_iter()
+            .map(|bundle| {
+                let mut buf = vec![];
+                {
+                    let mut emitter = Emitter {
+                        cfg: Default::default(),
+                        cm: cm.clone(),
+                        comments: None,
+                        wr: box JsWriter::new(cm.clone(), "\n", &mut buf, None),
+                        handlers: box Handlers,
+                    };
+                    emitter.emit_module(&bundle.module).unwrap();
+                }
+
+                (bundle.kind, String::from_utf8(buf).unwrap())
+            })
+            .collect())
+    })
+}
+
+fn bundle_es(dir: &str) -> String {
+    let mut bundles = bundle(dir, Default::default()).unwrap();
+    assert_eq!(bundles.len(), 1);
+    bundles.remove(0).1
+}
+
+#[test]
+fn simple() {
+    let s = bundle_es("simple");
+    println!("{}", s);
+
+    assert!(!s.contains("import"));
+    assert!(s.contains("foo$()"));
+    // Conflicting top-level bindings are renamed, but globals are not.
+    assert!(s.contains("foo1"));
+    assert!(s.contains("console.log("));
+    assert!(s.contains("export { foo$ as default }"));
+}
+
+#[test]
+fn namespace() {
+    let s = bundle_es("namespace");
+    println!("{}", s);
+
+    assert!(s.contains("get x () {") || s.contains("get x() {"));
+    assert!(s.contains("get default"));
+    assert!(s.contains("function _default()"));
+    assert!(s.contains("export { _a as a }"));
+}
+
+#[test]
+fn dynamic_import() {
+    let bundles = bundle("dynamic", Default::default()).unwrap();
+    assert_eq!(bundles.len(), 2);
+
+    assert_eq!(
+        bundles[1].0,
+        BundleKind::Lazy {
+            name: String::from("lazy")
+        }
+    );
+    assert!(bundles[0].1.contains("import('./lazy.js')"));
+    assert!(bundles[1].1.contains("value as value"));
+}
+
+#[test]
+fn external() {
+    let s = bundle(
+        "external",
+        Config {
+            external_modules: vec!["react".into(), "react-dom".into()],
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .remove(0)
+    .1;
+    println!("{}", s);
+
+    assert!(s.contains("from 'react'"));
+    assert!(s.contains(".createElement('div')"));
+    assert!(s.contains("export * from 'react-dom'"));
+}
+
+#[test]
+fn external_iife() {
+    let res = bundle(
+        "external",
+        Config {
+            module: ModuleType::Iife,
+            external_modules: vec!["react".into(), "react-dom".into()],
+            ..Default::default()
+        },
+    );
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn cycle() {
+    let s = bundle_es("cycle");
+    println!("{}", s);
+
+    assert!(!s.contains("import"));
+    assert!(s.contains("const b = ()=>a"));
+    assert!(s.contains("const a = ()=>b"));
+    assert!(s.contains("a as a"));
+}
+
+#[test]
+fn iife() {
+    let s = bundle(
+        "simple",
+        Config {
+            module: ModuleType::Iife,
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .remove(0)
+    .1;
+    println!("{}", s);
+
+    assert!(s.contains("var main = function()"));
+    assert!(s.contains("default: foo$"));
+}
+
+#[test]
+fn tree_shake() {
+    let s = bundle(
+        "tree_shake",
+        Config {
+            tree_shake: true,
+            ..Default::default()
+        },
+    )
+    .unwrap()
+    .remove(0)
+    .1;
+    println!("{}", s);
+
+    assert!(s.contains("function used()"));
+    assert!(!s.contains("function unused()"));
+    assert!(!s.contains("unusedVar"));
+    assert!(!s.contains("fromAll"));
+    assert!(!s.contains("'pure'"));
+    assert!(s.contains("console.log('effect')"));
+    assert!(!s.contains("unusedEffect"));
+}
@@ -0,0 +1,3 @@
+import { a } from './entry';
+
+export const b = () => a;
@@ -0,0 +1,4 @@
+import React from 'react';
+export * from 'react-dom';
+
+export const el = React.createElement('div');
@@ -0,0 +1,3 @@
+import * as a from './a';
+
+export { a };
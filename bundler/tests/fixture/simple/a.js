@@ -0,0 +1,7 @@
+const foo = 2;
+
+export function foo$() {
+    return foo;
+}
+
+export { foo$ as foo };
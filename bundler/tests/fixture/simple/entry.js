@@ -0,0 +1,7 @@
+import { foo as bar } from './a';
+
+const foo = 1;
+
+console.log(foo, bar());
+
+export default bar;
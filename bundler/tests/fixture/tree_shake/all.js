@@ -0,0 +1 @@
+export const fromAll = 1;
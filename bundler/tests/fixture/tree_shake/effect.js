@@ -0,0 +1,3 @@
+export const unusedEffect = 1;
+
+console.log('effect');
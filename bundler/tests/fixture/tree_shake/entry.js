@@ -0,0 +1,5 @@
+import { used } from './lib';
+import './pure/index';
+import './effect';
+
+used();
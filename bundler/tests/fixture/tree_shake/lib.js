@@ -0,0 +1,7 @@
+export function used() {}
+
+export function unused() {}
+
+export const unusedVar = 1;
+
+export * from './all';
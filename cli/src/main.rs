@@ -0,0 +1,321 @@
+use anyhow::{bail, Context, Error};
+use rayon::prelude::*;
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{self, Read, Write},
+    path::{Component, Path, PathBuf},
+    process,
+    sync::Arc,
+};
+use structopt::StructOpt;
+use swc::{
+    common::{
+        errors::{ColorConfig, Handler},
+        FileName, SourceMap,
+    },
+    config::{ConfigFile, Options, RootMode, SourceMapsConfig},
+    project::{add_source_map_url, is_source_file},
+    Compiler, TransformOutput,
+};
+use walkdir::WalkDir;
+
+#[derive(StructOpt)]
+#[structopt(name = "swc", about = "Speedy web compiler")]
+struct Cli {
+    /// Files or directories to compile. The source is read from stdin if
+    /// nothing is given.
+    #[structopt(parse(from_os_str))]
+    files: Vec<PathBuf>,
+
+    /// Path to a `.swcrc` file to use.
+    #[structopt(long, parse(from_os_str))]
+    config_file: Option<PathBuf>,
+
+    /// Disables lookup of `.swcrc` files.
+    #[structopt(long)]
+    no_swcrc: bool,
+
+    /// The root from which `.swcrc` files are looked up.
+    #[structopt(long, parse(from_os_str))]
+    root: Option<PathBuf>,
+
+    /// One of `root`, `upward` or `upward-optional`.
+    #[structopt(long, parse(try_from_str = parse_root_mode))]
+    root_mode: Option<RootMode>,
+
+    /// Name of the environment. Defaults to `SWC_ENV` or `NODE_ENV`.
+    #[structopt(long)]
+    env_name: Option<String>,
+
+    /// Compiles an input directory into an output directory.
+    #[structopt(short = "d", long, parse(from_os_str), conflicts_with = "out-file")]
+    out_dir: Option<PathBuf>,
+
+    /// Compiles a single input file into an output file.
+    #[structopt(short = "o", long, parse(from_os_str))]
+    out_file: Option<PathBuf>,
+
+    /// One of `true`, `false`, `inline` or `both`.
+    #[structopt(short = "s", long, parse(try_from_str = parse_source_maps))]
+    source_maps: Option<SourceMapsConfig>,
+
+    /// Name of the file read from stdin, used to find `.swcrc`.
+    #[structopt(long)]
+    filename: Option<String>,
+}
+
+/// A file to compile.
+struct Input {
+    path: PathBuf,
+    /// Path of the output, relative to the output directory.
+    ///
+    /// This is relative to the input directory for files in a directory,
+    /// and relative to the current directory for files given explicitly.
+    out: PathBuf,
+}
+
+fn parse_root_mode(s: &str) -> Result<RootMode, Error> {
+    serde_json::from_value(serde_json::Value::String(s.into()))
+        .with_context(|| format!("invalid root mode: {}", s))
+}
+
+fn parse_source_maps(s: &str) -> Result<SourceMapsConfig, Error> {
+    match s {
+        "true" => Ok(SourceMapsConfig::Bool(true)),
+        "false" => Ok(SourceMapsConfig::Bool(false)),
+        "inline" | "both" => Ok(SourceMapsConfig::Str(s.into())),
+        _ => bail!("invalid value for source maps: {}", s),
+    }
+}
+
+fn main() {
+    let cli = Cli::from_args();
+
+    let code = match run(&cli) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(err) => {
+            eprintln!("error: {:?}", err);
+            2
+        }
+    };
+
+    process::exit(code)
+}
+
+/// Returns `false` if any input failed to compile.
+fn run(cli: &Cli) -> Result<bool, Error> {
+    let cm = Arc::<SourceMap>::default();
+    let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
+    let c = Compiler::new(cm.clone(), handler);
+
+    // Use the same defaults as the node binding.
+    let mut opts: Options = serde_json::from_str("{}").unwrap();
+    opts.swcrc = !cli.no_swcrc;
+    opts.config_file = cli
+        .config_file
+        .as_ref()
+        .map(|path| ConfigFile::Str(path.to_string_lossy().into_owned()));
+    opts.root = cli.root.clone();
+    if let Some(ref root_mode) = cli.root_mode {
+        opts.root_mode = root_mode.clone();
+    }
+    if let Some(ref env_name) = cli.env_name {
+        opts.env_name = env_name.clone();
+    }
+    opts.source_maps = cli.source_maps.clone();
+    if cli.out_dir.is_none() && cli.out_file.is_none() {
+        // There's no file to write source map to.
+        if let Some(SourceMapsConfig::Bool(true)) = opts.source_maps {
+            opts.source_maps = Some(SourceMapsConfig::Str("inline".into()));
+        }
+    }
+
+    if cli.files.is_empty() {
+        return compile_stdin(&c, cli, opts);
+    }
+
+    let inputs = collect_inputs(&cli.files)?;
+    if cli.out_file.is_some() && inputs.len() != 1 {
+        bail!(
+            "--out-file requires exactly one input file, but {} files are given",
+            inputs.len()
+        )
+    }
+    if cli.out_dir.is_some() {
+        check_outputs(&inputs)?;
+    }
+
+    let outputs = inputs
+        .par_iter()
+        .map(|input| {
+            let mut opts = opts.clone();
+            opts.filename = input.path.to_string_lossy().into_owned();
+
+            let fm =
+                c.cm.load_file(&input.path)
+                    .with_context(|| format!("failed to load {}", input.path.display()))?;
+            c.process_js_file(fm, &opts)
+        })
+        .collect::<Vec<_>>();
+
+    let mut ok = true;
+    for (input, output) in inputs.iter().zip(outputs) {
+        let output = match output {
+            Ok(output) => output,
+            Err(err) => {
+                eprintln!("failed to compile {}: {:?}", input.path.display(), err);
+                ok = false;
+                continue;
+            }
+        };
+
+        if let Some(ref out_file) = cli.out_file {
+            write_output(out_file, output)?;
+        } else if let Some(ref out_dir) = cli.out_dir {
+            write_output(&out_dir.join(&input.out), output)?;
+        } else {
+            io::stdout()
+                .write_all(output.code.as_bytes())
+                .context("failed to write to stdout")?;
+        }
+    }
+
+    Ok(ok && !c.handler.has_errors())
+}
+
+fn compile_stdin(c: &Compiler, cli: &Cli, mut opts: Options) -> Result<bool, Error> {
+    let mut src = String::new();
+    io::stdin()
+        .read_to_string(&mut src)
+        .context("failed to read stdin")?;
+
+    let name = match cli.filename {
+        Some(ref filename) => {
+            opts.filename = filename.clone();
+            FileName::Real(opts.cwd.join(filename))
+        }
+        None => FileName::Anon,
+    };
+    let fm = c.cm.new_source_file(name, src);
+
+    let output = match c.process_js_file(fm, &opts) {
+        Ok(output) => output,
+        Err(err) => {
+            eprintln!("failed to compile stdin: {:?}", err);
+            return Ok(false);
+        }
+    };
+
+    match cli.out_file {
+        Some(ref out_file) => write_output(out_file, output)?,
+        None => io::stdout()
+            .write_all(output.code.as_bytes())
+            .context("failed to write to stdout")?,
+    }
+
+    Ok(!c.handler.has_errors())
+}
+
+fn collect_inputs(files: &[PathBuf]) -> Result<Vec<Input>, Error> {
+    let mut inputs = vec![];
+
+    for file in files {
+        if file.is_dir() {
+            let entries = WalkDir::new(file)
+                .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+                .into_iter()
+                .filter_entry(|e| {
+                    let name = e.file_name().to_string_lossy();
+                    e.depth() == 0 || !(name.starts_with('.') || name == "node_modules")
+                });
+
+            for entry in entries {
+                let entry = entry.with_context(|| format!("failed to read {}", file.display()))?;
+                let path = entry.path();
+                if !entry.file_type().is_file() || !is_source_file(path) {
+                    continue;
+                }
+
+                inputs.push(Input {
+                    path: path.to_path_buf(),
+                    out: path.strip_prefix(file).unwrap().with_extension("js"),
+                });
+            }
+        } else if !file.exists() {
+            bail!("input file {} does not exist", file.display())
+        } else {
+            inputs.push(Input {
+                path: file.clone(),
+                out: out_path(file)?.with_extension("js"),
+            });
+        }
+    }
+
+    Ok(inputs)
+}
+
+/// Fails if multiple inputs are compiled to the same output.
+fn check_outputs(inputs: &[Input]) -> Result<(), Error> {
+    let mut outputs = HashMap::new();
+    for input in inputs {
+        if let Some(prev) = outputs.insert(&input.out, &input.path) {
+            bail!(
+                "both {} and {} are compiled to {}",
+                prev.display(),
+                input.path.display(),
+                input.out.display()
+            )
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the path of `file` relative to the current directory, or the
+/// file name if `file` is not in the current directory.
+fn out_path(file: &Path) -> Result<PathBuf, Error> {
+    let cwd = env::current_dir().context("failed to get current directory")?;
+    let rel = file.strip_prefix(&cwd).unwrap_or(file);
+
+    let mut out = PathBuf::new();
+    for c in rel.components() {
+        match c {
+            Component::Normal(c) => out.push(c),
+            Component::CurDir => {}
+            _ => {
+                return match file.file_name() {
+                    Some(name) => Ok(name.into()),
+                    None => bail!("invalid input file: {}", file.display()),
+                }
+            }
+        }
+    }
+
+    if out.as_os_str().is_empty() {
+        bail!("invalid input file: {}", file.display())
+    }
+    Ok(out)
+}
+
+/// Writes `output` to `path`, and the source map to `{path}.map`.
+fn write_output(path: &Path, output: TransformOutput) -> Result<(), Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("failed to create directory {}", dir.display()))?;
+    }
+
+    let mut code = output.code;
+    if let Some(map) = output.map {
+        let mut map_path = path.as_os_str().to_owned();
+        map_path.push(".map");
+        let map_path = PathBuf::from(map_path);
+
+        add_source_map_url(&mut code, &map_path);
+        fs::write(&map_path, map)
+            .with_context(|| format!("failed to write {}", map_path.display()))?;
+    }
+
+    fs::write(path, code).with_context(|| format!("failed to write {}", path.display()))
+}
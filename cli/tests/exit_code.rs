@@ -0,0 +1,70 @@
+use std::{
+    env, fs,
+    process::{Command, Output},
+};
+
+fn swc(args: &[&str]) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_swc"))
+        .args(args)
+        .output()
+        .expect("failed to run swc");
+    println!("{}", String::from_utf8_lossy(&output.stdout));
+    eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+#[test]
+fn success() {
+    let output = swc(&["tests/fixture/a/index.js"]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("function()"));
+}
+
+#[test]
+fn compile_error() {
+    let output = swc(&["tests/fixture/a/index.js", "tests/fixture/error.js"]);
+
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn missing_file() {
+    let output = swc(&["tests/fixture/missing.js"]);
+
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn out_dir_keeps_relative_paths() {
+    let out = env::temp_dir().join("swc-cli-relative-paths");
+    let _ = fs::remove_dir_all(&out);
+
+    let output = swc(&[
+        "tests/fixture/a/index.js",
+        "tests/fixture/b/index.js",
+        "-d",
+        out.to_str().unwrap(),
+    ]);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(out.join("tests/fixture/a/index.js").is_file());
+    assert!(out.join("tests/fixture/b/index.js").is_file());
+}
+
+#[test]
+fn out_dir_collision() {
+    let out = env::temp_dir().join("swc-cli-collision");
+    let _ = fs::remove_dir_all(&out);
+
+    let output = swc(&[
+        "tests/fixture/a",
+        "tests/fixture/b",
+        "-d",
+        out.to_str().unwrap(),
+    ]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("are compiled to index.js"));
+    assert!(!out.join("index.js").exists());
+}
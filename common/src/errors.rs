@@ -13,6 +13,10 @@ pub use self::{
     diagnostic::{Diagnostic, DiagnosticId, DiagnosticStyledString, SubDiagnostic},
     diagnostic_builder::DiagnosticBuilder,
     emitter::{ColorConfig, Emitter, EmitterWriter},
+    json::{
+        DiagnosticBuffer, JsonDiagnostic, JsonDiagnosticCode, JsonEmitter, JsonSpan,
+        JsonSubstitutionPart, JsonSuggestion,
+    },
 };
 use crate::{
     rustc_data_structures::stable_hasher::StableHasher,
@@ -34,6 +38,7 @@ use termcolor::{Color, ColorSpec};
 mod diagnostic;
 mod diagnostic_builder;
 pub mod emitter;
+mod json;
 mod lock;
 mod snippet;
 mod styled_buffer;
@@ -357,6 +362,21 @@ impl Handler {
         Handler::with_emitter_and_flags(emitter, flags)
     }
 
+    /// Creates a handler which stores diagnostics as [JsonDiagnostic] in the
+    /// returned buffer.
+    pub fn with_json_emitter(
+        can_emit_warnings: bool,
+        treat_err_as_bug: bool,
+        cm: Option<Arc<SourceMapperDyn>>,
+    ) -> (Handler, DiagnosticBuffer) {
+        let (emitter, buf) = JsonEmitter::buffered(cm);
+
+        (
+            Handler::with_emitter(can_emit_warnings, treat_err_as_bug, Box::new(emitter)),
+            buf,
+        )
+    }
+
     pub fn with_emitter(
         can_emit_warnings: bool,
         treat_err_as_bug: bool,
@@ -0,0 +1,323 @@
+//! Emitter which reports diagnostics as json.
+
+use super::{
+    Applicability, CodeSuggestion, DiagnosticBuilder, DiagnosticId, Emitter, EmitterWriter, Level,
+    SourceMapperDyn, SubDiagnostic,
+};
+use crate::{
+    sync::Lock,
+    syntax_pos::{MultiSpan, Span, SpanLabel},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    sync::Arc,
+    thread::{self, ThreadId},
+};
+
+/// A diagnostic, in a form which can be consumed by tools.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonDiagnostic {
+    /// `"error"`, `"warning"`, `"note"`, `"help"`, ...
+    pub level: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<JsonDiagnosticCode>,
+    pub message: String,
+    /// Primary and secondary spans, with their labels.
+    #[serde(default)]
+    pub spans: Vec<JsonSpan>,
+    /// Notes and helps attached to this diagnostic.
+    #[serde(default)]
+    pub children: Vec<JsonDiagnostic>,
+    #[serde(default)]
+    pub suggestions: Vec<JsonSuggestion>,
+    /// Human readable form of this diagnostic, as printed by
+    /// [EmitterWriter].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rendered: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonDiagnosticCode {
+    pub code: String,
+    /// `"error"` or `"lint"`.
+    pub kind: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonSpan {
+    pub file_name: String,
+    pub byte_start: u32,
+    pub byte_end: u32,
+    /// 1-based.
+    pub line_start: usize,
+    /// 1-based.
+    pub line_end: usize,
+    /// 1-based, in characters.
+    pub column_start: usize,
+    /// 1-based, in characters.
+    pub column_end: usize,
+    pub is_primary: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonSuggestion {
+    pub message: String,
+    /// `"machine-applicable"`, `"has-placeholders"`, `"maybe-incorrect"` or
+    /// `"unspecified"`.
+    pub applicability: String,
+    /// Each substitution is an alternative fix, which may touch multiple
+    /// spans.
+    pub substitutions: Vec<Vec<JsonSubstitutionPart>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonSubstitutionPart {
+    pub span: JsonSpan,
+    pub snippet: String,
+}
+
+/// Stores diagnostics emitted by a buffered [JsonEmitter].
+///
+/// Diagnostics are stored per thread, so diagnostics of operations running
+/// on other threads are not mixed.
+#[derive(Debug, Clone, Default)]
+pub struct DiagnosticBuffer(Arc<Lock<HashMap<ThreadId, Vec<JsonDiagnostic>>>>);
+
+impl DiagnosticBuffer {
+    /// Removes all diagnostics emitted on the current thread from the buffer.
+    pub fn take(&self) -> Vec<JsonDiagnostic> {
+        self.0
+            .lock()
+            .remove(&thread::current().id())
+            .unwrap_or_default()
+    }
+
+    /// Runs `op`, and returns diagnostics emitted on the current thread while
+    /// running it.
+    ///
+    /// Diagnostics emitted before `op` are kept in the buffer.
+    pub fn capture<F, Ret>(&self, op: F) -> (Ret, Vec<JsonDiagnostic>)
+    where
+        F: FnOnce() -> Ret,
+    {
+        let prev = self.take();
+        let ret = op();
+        let diagnostics = self.take();
+
+        if !prev.is_empty() {
+            self.0.lock().insert(thread::current().id(), prev);
+        }
+
+        (ret, diagnostics)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0
+            .lock()
+            .get(&thread::current().id())
+            .map_or(true, |v| v.is_empty())
+    }
+
+    fn push(&self, d: JsonDiagnostic) {
+        self.0
+            .lock()
+            .entry(thread::current().id())
+            .or_default()
+            .push(d)
+    }
+}
+
+enum Destination {
+    /// Each diagnostic is written as a line of json.
+    Writer(Box<dyn Write + Send>),
+    Buffer(DiagnosticBuffer),
+}
+
+/// Emits diagnostics as [JsonDiagnostic].
+pub struct JsonEmitter {
+    dst: Destination,
+    sm: Option<Arc<SourceMapperDyn>>,
+}
+
+impl JsonEmitter {
+    /// Creates an emitter which writes each diagnostic to `dst` as a line of
+    /// json.
+    pub fn new(dst: Box<dyn Write + Send>, sm: Option<Arc<SourceMapperDyn>>) -> Self {
+        JsonEmitter {
+            dst: Destination::Writer(dst),
+            sm,
+        }
+    }
+
+    /// Creates an emitter which stores diagnostics in the returned buffer.
+    pub fn buffered(sm: Option<Arc<SourceMapperDyn>>) -> (Self, DiagnosticBuffer) {
+        let buf = DiagnosticBuffer::default();
+
+        (
+            JsonEmitter {
+                dst: Destination::Buffer(buf.clone()),
+                sm,
+            },
+            buf,
+        )
+    }
+
+    fn diagnostic(&self, db: &DiagnosticBuilder<'_>) -> JsonDiagnostic {
+        JsonDiagnostic {
+            level: level_str(db.level).into(),
+            code: db.code.as_ref().map(|code| match code {
+                DiagnosticId::Error(code) => JsonDiagnosticCode {
+                    code: code.clone(),
+                    kind: "error".into(),
+                },
+                DiagnosticId::Lint(code) => JsonDiagnosticCode {
+                    code: code.clone(),
+                    kind: "lint".into(),
+                },
+            }),
+            message: db.message(),
+            spans: self.spans(&db.span),
+            children: db.children.iter().map(|c| self.child(c)).collect(),
+            suggestions: db.suggestions.iter().map(|s| self.suggestion(s)).collect(),
+            rendered: Some(self.render(db)),
+        }
+    }
+
+    fn child(&self, c: &SubDiagnostic) -> JsonDiagnostic {
+        JsonDiagnostic {
+            level: level_str(c.level).into(),
+            code: None,
+            message: c.message(),
+            spans: self.spans(c.render_span.as_ref().unwrap_or(&c.span)),
+            children: vec![],
+            suggestions: vec![],
+            rendered: None,
+        }
+    }
+
+    fn suggestion(&self, s: &CodeSuggestion) -> JsonSuggestion {
+        JsonSuggestion {
+            message: s.msg.clone(),
+            applicability: match s.applicability {
+                Applicability::MachineApplicable => "machine-applicable",
+                Applicability::HasPlaceholders => "has-placeholders",
+                Applicability::MaybeIncorrect => "maybe-incorrect",
+                Applicability::Unspecified => "unspecified",
+            }
+            .into(),
+            substitutions: s
+                .substitutions
+                .iter()
+                .map(|sub| {
+                    sub.parts
+                        .iter()
+                        .filter_map(|part| {
+                            Some(JsonSubstitutionPart {
+                                span: self.span(part.span, true, None)?,
+                                snippet: part.snippet.clone(),
+                            })
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    fn spans(&self, span: &MultiSpan) -> Vec<JsonSpan> {
+        span.span_labels()
+            .into_iter()
+            .filter_map(
+                |SpanLabel {
+                     span,
+                     is_primary,
+                     label,
+                 }| self.span(span, is_primary, label),
+            )
+            .collect()
+    }
+
+    fn span(&self, span: Span, is_primary: bool, label: Option<String>) -> Option<JsonSpan> {
+        if span.is_dummy() {
+            return None;
+        }
+        let sm = self.sm.as_ref()?;
+
+        let lo = sm.lookup_char_pos(span.lo());
+        let hi = sm.lookup_char_pos(span.hi());
+
+        Some(JsonSpan {
+            file_name: lo.file.name.to_string(),
+            byte_start: (span.lo() - lo.file.start_pos).0,
+            byte_end: (span.hi() - lo.file.start_pos).0,
+            line_start: lo.line,
+            line_end: hi.line,
+            column_start: lo.col.0 + 1,
+            column_end: hi.col.0 + 1,
+            is_primary,
+            label,
+        })
+    }
+
+    /// Renders `db` using [EmitterWriter].
+    fn render(&self, db: &DiagnosticBuilder<'_>) -> String {
+        let buf = RenderBuffer::default();
+        EmitterWriter::new(Box::new(buf.clone()), self.sm.clone(), false, false).emit(db);
+
+        let rendered = buf.0.lock();
+        String::from_utf8_lossy(&rendered).into_owned()
+    }
+}
+
+impl Emitter for JsonEmitter {
+    fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+        let d = self.diagnostic(db);
+
+        match self.dst {
+            Destination::Writer(ref mut w) => {
+                let res = serde_json::to_writer(&mut *w, &d)
+                    .map_err(io::Error::from)
+                    .and_then(|_| writeln!(w))
+                    .and_then(|_| w.flush());
+                if let Err(e) = res {
+                    panic!("failed to print diagnostics: {:?}", e);
+                }
+            }
+            Destination::Buffer(ref buf) => buf.push(d),
+        }
+    }
+}
+
+fn level_str(level: Level) -> &'static str {
+    match level {
+        Level::Bug => "bug",
+        Level::Fatal | Level::PhaseFatal | Level::Error => "error",
+        Level::Warning => "warning",
+        Level::Note => "note",
+        Level::Help => "help",
+        Level::Cancelled => "cancelled",
+        Level::FailureNote => "failure-note",
+    }
+}
+
+#[derive(Clone, Default)]
+struct RenderBuffer(Arc<Lock<Vec<u8>>>);
+
+impl Write for RenderBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
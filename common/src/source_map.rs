@@ -1003,14 +1003,29 @@ impl SourceMap {
         &self,
         mappings: &mut Vec<(BytePos, LineCol)>,
         orig: Option<&sourcemap::SourceMap>,
+    ) -> sourcemap::SourceMap {
+        self.build_source_map_with_config(mappings, orig, DefaultSourceMapGenConfig)
+    }
+
+    /// Creates a `.map` file.
+    ///
+    /// If `orig` is given, positions are mapped through it, so the result
+    /// points to the sources of `orig`.
+    pub fn build_source_map_with_config(
+        &self,
+        mappings: &mut Vec<(BytePos, LineCol)>,
+        orig: Option<&sourcemap::SourceMap>,
+        config: impl SourceMapGenConfig,
     ) -> sourcemap::SourceMap {
         let mut builder = SourceMapBuilder::new(None);
+        let root = config.source_root();
 
         // // This method is optimized based on the fact that mapping is sorted.
         // mappings.sort_by_key(|v| v.0);
 
         let mut cur_file: Option<Arc<SourceFile>> = None;
-        let mut src_id;
+        let mut src = String::new();
+        let mut orig_srcs = HashMap::new();
 
         let mut ch_start = 0;
         let mut line_ch_start = 0;
@@ -1029,8 +1044,14 @@ impl SourceMap {
                 Some(ref f) if f.start_pos <= pos && pos < f.end_pos => f,
                 _ => {
                     f = self.lookup_source_file(pos);
-                    src_id = builder.add_source(&f.name.to_string());
-                    builder.set_source_contents(src_id, Some(&f.src));
+                    // Sources of `orig` are used instead, if it exists.
+                    if orig.is_none() {
+                        src = with_source_root(root, config.file_name_to_source(&f.name));
+                        let src_id = builder.add_source(&src);
+                        if config.inline_sources_content(&f.name) {
+                            builder.set_source_contents(src_id, Some(&f.src));
+                        }
+                    }
                     cur_file = Some(f.clone());
                     ch_start = 0;
                     line_ch_start = 0;
@@ -1043,7 +1064,8 @@ impl SourceMap {
                 None => continue,
             };
 
-            let mut line = a + 1; // Line numbers start at 1
+            // Zero-based, like source maps.
+            let mut line = a;
             let linebpos = f.lines[a as usize];
             debug_assert!(
                 pos >= linebpos,
@@ -1056,21 +1078,108 @@ impl SourceMap {
             let linechpos = { self.calc_extra_bytes(&f, &mut line_ch_start, linebpos) };
 
             let mut col = max(chpos, linechpos) - min(chpos, linechpos);
+            let mut name = config.name_for_bytepos(pos);
+            let mut token_src = &*src;
 
             if let Some(orig) = &orig {
-                if let Some(token) = orig.lookup_token(line, col) {
-                    line = token.get_src_line() + 1;
-                    col = token.get_src_col();
+                let token = match orig.lookup_token(line, col) {
+                    Some(token) if token.get_dst_line() == line => token,
+                    // Not mapped by the original source map.
+                    _ => continue,
+                };
+                let source = match token.get_source() {
+                    Some(source) => source,
+                    None => continue,
+                };
+
+                line = token.get_src_line();
+                col = token.get_src_col();
+                if token.get_name().is_some() {
+                    name = token.get_name();
                 }
+
+                let orig_src_id = token.get_src_id();
+                token_src = orig_srcs.entry(orig_src_id).or_insert_with(|| {
+                    let source = with_source_root(root, source.to_string());
+                    let id = builder.add_source(&source);
+                    if config.inline_sources_content(&f.name) {
+                        builder.set_source_contents(id, orig.get_source_contents(orig_src_id));
+                    }
+                    source
+                });
             }
 
-            builder.add(lc.line, lc.col, line - 1, col, None, None);
+            builder.add(lc.line, lc.col, line, col, Some(token_src), name);
         }
 
         builder.into_sourcemap()
     }
 }
 
+/// Controls the content of source maps created by
+/// [SourceMap::build_source_map_with_config].
+pub trait SourceMapGenConfig {
+    /// Name of `f` in `sources`.
+    fn file_name_to_source(&self, f: &FileName) -> String {
+        f.to_string()
+    }
+
+    /// Original name of the identifier at `pos`, which is added to `names`.
+    fn name_for_bytepos(&self, _pos: BytePos) -> Option<&str> {
+        None
+    }
+
+    /// Embeds the content of `f` in `sourcesContent`.
+    fn inline_sources_content(&self, _f: &FileName) -> bool {
+        true
+    }
+
+    /// Directory the sources are relative to.
+    ///
+    /// As `sourceRoot` cannot be stored in [sourcemap::SourceMap], it is
+    /// joined to the entries of `sources` the way consumers resolve it.
+    fn source_root(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Prepends `root` to `src`, unless `src` is absolute.
+fn with_source_root(root: Option<&str>, src: String) -> String {
+    match root {
+        Some(root)
+            if !root.is_empty()
+                && !src.starts_with('/')
+                && !src.starts_with("http:")
+                && !src.starts_with("https:") =>
+        {
+            format!("{}/{}", root.trim_end_matches('/'), src)
+        }
+        _ => src,
+    }
+}
+
+impl<T: SourceMapGenConfig> SourceMapGenConfig for &T {
+    fn file_name_to_source(&self, f: &FileName) -> String {
+        (**self).file_name_to_source(f)
+    }
+
+    fn name_for_bytepos(&self, pos: BytePos) -> Option<&str> {
+        (**self).name_for_bytepos(pos)
+    }
+
+    fn inline_sources_content(&self, f: &FileName) -> bool {
+        (**self).inline_sources_content(f)
+    }
+
+    fn source_root(&self) -> Option<&str> {
+        (**self).source_root()
+    }
+}
+
+pub struct DefaultSourceMapGenConfig;
+
+impl SourceMapGenConfig for DefaultSourceMapGenConfig {}
+
 impl SourceMapper for SourceMap {
     fn lookup_char_pos(&self, pos: BytePos) -> Loc {
         self.lookup_char_pos(pos)
@@ -1326,6 +1435,128 @@ mod tests {
         assert!(sm.merge_spans(span1, span2).is_none());
     }
 
+    struct TestConfig {
+        source_root: Option<&'static str>,
+        inline_sources_content: bool,
+    }
+
+    impl SourceMapGenConfig for TestConfig {
+        fn name_for_bytepos(&self, pos: BytePos) -> Option<&str> {
+            if pos == BytePos(7) {
+                Some("bar")
+            } else {
+                None
+            }
+        }
+
+        fn inline_sources_content(&self, _: &FileName) -> bool {
+            self.inline_sources_content
+        }
+
+        fn source_root(&self) -> Option<&str> {
+            self.source_root
+        }
+    }
+
+    /// Builds a map for `let a;\nfoo(a);`, with the start of each line mapped
+    /// to the same position of the output.
+    fn build_map(orig: Option<&sourcemap::SourceMap>, config: TestConfig) -> sourcemap::SourceMap {
+        let sm = SourceMap::new(FilePathMapping::empty());
+        sm.new_source_file(PathBuf::from("a.js").into(), "let a;\nfoo(a);".into());
+        let mut mappings = vec![
+            (BytePos(0), LineCol { line: 0, col: 0 }),
+            (BytePos(7), LineCol { line: 1, col: 0 }),
+        ];
+        sm.build_source_map_with_config(&mut mappings, orig, config)
+    }
+
+    #[test]
+    fn source_map_names() {
+        let map = build_map(
+            None,
+            TestConfig {
+                source_root: None,
+                inline_sources_content: true,
+            },
+        );
+
+        assert_eq!(map.get_source(0), Some("a.js"));
+        assert_eq!(map.get_name_count(), 1);
+        assert_eq!(map.lookup_token(0, 0).unwrap().get_name(), None);
+        let token = map.lookup_token(1, 0).unwrap();
+        assert_eq!(token.get_src(), (1, 0));
+        assert_eq!(token.get_name(), Some("bar"));
+    }
+
+    #[test]
+    fn source_map_sources_content() {
+        let map = build_map(
+            None,
+            TestConfig {
+                source_root: None,
+                inline_sources_content: true,
+            },
+        );
+        assert_eq!(map.get_source_contents(0), Some("let a;\nfoo(a);"));
+
+        let map = build_map(
+            None,
+            TestConfig {
+                source_root: None,
+                inline_sources_content: false,
+            },
+        );
+        assert_eq!(map.get_source_contents(0), None);
+    }
+
+    #[test]
+    fn source_map_source_root() {
+        let map = build_map(
+            None,
+            TestConfig {
+                source_root: Some("src/"),
+                inline_sources_content: true,
+            },
+        );
+        assert_eq!(map.get_source(0), Some("src/a.js"));
+
+        assert_eq!(
+            with_source_root(Some("src"), "/abs/a.js".into()),
+            "/abs/a.js"
+        );
+        assert_eq!(
+            with_source_root(Some("src"), "https://example.com/a.js".into()),
+            "https://example.com/a.js"
+        );
+        assert_eq!(with_source_root(Some(""), "a.js".into()), "a.js");
+    }
+
+    #[test]
+    fn source_map_compose() {
+        let mut builder = SourceMapBuilder::new(None);
+        builder.add(0, 0, 2, 4, Some("orig.ts"), Some("x"));
+        let id = builder.add_source("orig.ts");
+        builder.set_source_contents(id, Some("// orig"));
+        // Line 1 of the input is not mapped.
+        let orig = builder.into_sourcemap();
+
+        let map = build_map(
+            Some(&orig),
+            TestConfig {
+                source_root: Some("src"),
+                inline_sources_content: true,
+            },
+        );
+
+        assert_eq!(map.get_source_count(), 1);
+        assert_eq!(map.get_source(0), Some("src/orig.ts"));
+        assert_eq!(map.get_source_contents(0), Some("// orig"));
+        assert_eq!(map.get_token_count(), 1);
+        let token = map.lookup_token(0, 0).unwrap();
+        assert_eq!(token.get_src(), (2, 4));
+        assert_eq!(token.get_name(), Some("x"));
+    }
+
     /// Returns the span corresponding to the `n`th occurrence of
     /// `substring` in `source_text`.
     trait SourceMapExtension {
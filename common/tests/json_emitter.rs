@@ -0,0 +1,136 @@
+use std::{sync::Arc, thread};
+use swc_common::{
+    errors::{DiagnosticId, Handler, JsonDiagnostic},
+    BytePos, FileName, FilePathMapping, Globals, SourceMap, Span, GLOBALS,
+};
+
+fn emit<F>(src: &str, op: F) -> Vec<JsonDiagnostic>
+where
+    F: FnOnce(&Handler, BytePos),
+{
+    GLOBALS.set(&Globals::new(), || {
+        let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+        let fm = cm.new_source_file(FileName::Real("input.js".into()), src.into());
+
+        let (handler, buf) = Handler::with_json_emitter(true, false, Some(cm.clone()));
+        op(&handler, fm.start_pos);
+
+        buf.take()
+    })
+}
+
+fn span(start: BytePos, lo: u32, hi: u32) -> Span {
+    Span::new(start + BytePos(lo), start + BytePos(hi), Default::default())
+}
+
+#[test]
+fn error() {
+    let d = emit("var a;\nwith (a) {}", |handler, start| {
+        handler
+            .struct_span_err_with_code(
+                span(start, 7, 11),
+                "with statement is not allowed",
+                DiagnosticId::Error("E0001".into()),
+            )
+            .span_label(span(start, 13, 14), "object")
+            .emit();
+    });
+
+    assert_eq!(d.len(), 1);
+    let d = &d[0];
+
+    assert_eq!(d.level, "error");
+    assert_eq!(d.message, "with statement is not allowed");
+    let code = d.code.as_ref().unwrap();
+    assert_eq!(code.code, "E0001");
+    assert_eq!(code.kind, "error");
+    assert!(d.rendered.as_ref().unwrap().contains("with statement"));
+
+    assert_eq!(d.spans.len(), 2);
+    let primary = d.spans.iter().find(|s| s.is_primary).unwrap();
+    assert_eq!(primary.file_name, "input.js");
+    assert_eq!(primary.byte_start, 7);
+    assert_eq!(primary.byte_end, 11);
+    assert_eq!(primary.line_start, 2);
+    assert_eq!(primary.column_start, 1);
+    assert_eq!(primary.column_end, 5);
+
+    let secondary = d.spans.iter().find(|s| !s.is_primary).unwrap();
+    assert_eq!(secondary.label.as_deref(), Some("object"));
+    assert_eq!(secondary.column_start, 7);
+}
+
+#[test]
+fn suggestion() {
+    let d = emit("let a = 1", |handler, start| {
+        handler
+            .struct_span_warn(span(start, 9, 9), "missing semicolon")
+            .span_suggestion(span(start, 9, 9), "add a semicolon", ";".into())
+            .emit();
+    });
+
+    assert_eq!(d.len(), 1);
+    assert_eq!(d[0].level, "warning");
+
+    let s = &d[0].suggestions;
+    assert_eq!(s.len(), 1);
+    assert_eq!(s[0].message, "add a semicolon");
+    assert_eq!(s[0].substitutions.len(), 1);
+    assert_eq!(s[0].substitutions[0][0].snippet, ";");
+    assert_eq!(s[0].substitutions[0][0].span.column_start, 10);
+}
+
+#[test]
+fn note() {
+    let d = emit("foo()", |handler, start| {
+        handler
+            .struct_span_err(span(start, 0, 3), "foo is not defined")
+            .span_note(span(start, 0, 5), "called here")
+            .emit();
+    });
+
+    assert_eq!(d[0].children.len(), 1);
+    assert_eq!(d[0].children[0].level, "note");
+    assert_eq!(d[0].children[0].message, "called here");
+    assert_eq!(d[0].children[0].spans[0].byte_end, 5);
+}
+
+#[test]
+fn capture() {
+    GLOBALS.set(&Globals::new(), || {
+        let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+        let (handler, buf) = Handler::with_json_emitter(true, false, Some(cm.clone()));
+
+        handler.warn("before");
+        let ((), d) = buf.capture(|| handler.warn("inside"));
+        assert_eq!(d.len(), 1);
+        assert_eq!(d[0].message, "inside");
+
+        let d = buf.take();
+        assert_eq!(d.len(), 1);
+        assert_eq!(d[0].message, "before");
+        assert!(buf.is_empty());
+    })
+}
+
+#[test]
+fn capture_per_thread() {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let (handler, buf) = Handler::with_json_emitter(true, false, Some(cm.clone()));
+    let handler = Arc::new(handler);
+
+    let ((), d) = buf.capture(|| {
+        handler.warn("main");
+
+        let handler = handler.clone();
+        let buf = buf.clone();
+        let d = thread::spawn(move || buf.capture(|| handler.warn("other")).1)
+            .join()
+            .unwrap();
+        assert_eq!(d.len(), 1);
+        assert_eq!(d[0].message, "other");
+    });
+
+    assert_eq!(d.len(), 1);
+    assert_eq!(d[0].message, "main");
+}
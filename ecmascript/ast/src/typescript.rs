@@ -2,11 +2,11 @@
 #![allow(missing_copy_implementations)]
 use crate::{
     class::Decorator,
-    expr::{Expr, Tpl},
+    expr::{Expr, TplElement},
     ident::Ident,
     lit::{Bool, Number, Str},
     module::ModuleItem,
-    pat::{ArrayPat, AssignPat, ObjectPat, RestPat},
+    pat::{ArrayPat, AssignPat, ObjectPat, Pat, RestPat},
 };
 use serde::{
     de::{self, Unexpected, Visitor},
@@ -407,6 +407,9 @@ pub struct TsConstructorType {
     pub type_params: Option<TsTypeParamDecl>,
     #[serde(rename = "typeAnnotation")]
     pub type_ann: TsTypeAnn,
+    /// `abstract new () => T`
+    #[serde(default, rename = "abstract")]
+    pub is_abstract: bool,
 }
 
 #[ast_node("TsTypeReference")]
@@ -486,7 +489,19 @@ pub struct TsArrayType {
 #[derive(Eq, Hash)]
 pub struct TsTupleType {
     pub span: Span,
-    pub elem_types: Vec<Box<TsType>>,
+    pub elem_types: Vec<TsTupleElement>,
+}
+
+#[ast_node("TsTupleElement")]
+#[derive(Eq, Hash)]
+pub struct TsTupleElement {
+    pub span: Span,
+    /// `Ident` or `RestPat { arg: Ident }`.
+    ///
+    /// `Ident.optional` is true for `[name?: T]`.
+    #[serde(default)]
+    pub label: Option<Pat>,
+    pub ty: TsType,
 }
 
 #[ast_node("TsOptionalType")]
@@ -680,7 +695,16 @@ pub enum TsLit {
     Bool(Bool),
 
     #[tag("TemplateLiteral")]
-    Tpl(Tpl),
+    Tpl(TsTplLitType),
+}
+
+/// `` `foo${T}` ``
+#[ast_node("TemplateLiteral")]
+#[derive(Eq, Hash)]
+pub struct TsTplLitType {
+    pub span: Span,
+    pub types: Vec<Box<TsType>>,
+    pub quasis: Vec<TplElement>,
 }
 
 // // ================
@@ -1,4 +1,45 @@
+use swc_ecma_parser::JscTarget;
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Config {
     pub minify: bool,
+
+    /// Preferred quote of string literals.
+    pub quote: Quote,
+
+    /// Indentation used by `JsWriter`.
+    pub indent: Indent,
+
+    /// Escapes non-ascii characters in strings, template literals and
+    /// identifiers.
+    pub ascii_only: bool,
+
+    /// Syntax newer than this (e.g. `\u{1F600}` escapes) is not emitted.
+    pub target: JscTarget,
+}
+
+/// The other quote is used if a string contains the preferred one but not the
+/// other one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Quote {
+    Single,
+    Double,
+}
+
+impl Default for Quote {
+    fn default() -> Self {
+        Quote::Single
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+    Spaces(usize),
+    Tab,
+}
+
+impl Default for Indent {
+    fn default() -> Self {
+        Indent::Spaces(4)
+    }
 }
@@ -1,7 +1,7 @@
 #![recursion_limit = "1024"]
 #![allow(unused_variables)]
 
-pub use self::config::Config;
+pub use self::config::{Config, Indent, Quote};
 use self::{
     list::ListFormat,
     text_writer::WriteJs,
@@ -12,6 +12,7 @@ use swc_atoms::JsWord;
 use swc_common::{comments::Comments, BytePos, SourceMap, Span, Spanned, SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_codegen_macros::emitter;
+use swc_ecma_parser::JscTarget;
 
 #[macro_use]
 pub mod macros;
@@ -318,6 +319,44 @@ impl<'a> Emitter<'a> {
         }
     }
 
+    /// Escapes non-ascii characters if `ascii_only` is enabled.
+    ///
+    /// Line terminators in string literals are escaped for targets which don't
+    /// allow them.
+    fn escape_unicode<'s>(&self, s: &'s str, is_str_lit: bool) -> Cow<'s, str> {
+        let need_escape = |c: char| {
+            (self.cfg.ascii_only && !c.is_ascii())
+                || (is_str_lit
+                    && self.cfg.target < JscTarget::Es2019
+                    && (c == '\u{2028}' || c == '\u{2029}'))
+        };
+
+        if !s.chars().any(need_escape) {
+            return Cow::Borrowed(s);
+        }
+
+        let mut buf = String::with_capacity(s.len() + 16);
+        for c in s.chars() {
+            if !need_escape(c) {
+                buf.push(c);
+            } else if (c as u32) <= 0xffff {
+                write!(buf, "\\u{:04X}", c as u32).unwrap();
+            } else if self.cfg.target >= JscTarget::Es2015 {
+                write!(buf, "\\u{{{:X}}}", c as u32).unwrap();
+            } else {
+                // `\u{...}` is not available, so a surrogate pair is emitted.
+                // This is valid only in strings, and `emit_ident` rejects such
+                // identifiers.
+                let mut pair = [0; 2];
+                for unit in c.encode_utf16(&mut pair) {
+                    write!(buf, "\\u{:04X}", unit).unwrap();
+                }
+            }
+        }
+
+        Cow::Owned(buf)
+    }
+
     fn emit_js_word(&mut self, span: Span, value: &JsWord) -> Result {
         self.wr.write_str_lit(span, &value)?;
 
@@ -333,21 +372,27 @@ impl<'a> Emitter<'a> {
         //     return Ok(());
         // }
         let value = escape(&node.value);
+        let value = self.escape_unicode(&value, true);
         // let value = node.value.replace("\n", "\\n");
 
-        if !node.value.contains('\'') {
-            punct!("'");
+        let (quote, other) = match self.cfg.quote {
+            Quote::Single => ("'", "\""),
+            Quote::Double => ("\"", "'"),
+        };
+
+        if !node.value.contains(quote) {
+            self.wr.write_punct(quote)?;
             self.wr.write_str_lit(node.span, &value)?;
-            punct!("'");
-        } else if !node.value.contains('\"') {
-            punct!("\"");
+            self.wr.write_punct(quote)?;
+        } else if !node.value.contains(other) {
+            self.wr.write_punct(other)?;
             self.wr.write_str_lit(node.span, &value)?;
-            punct!("\"");
+            self.wr.write_punct(other)?;
         } else {
-            punct!("'");
+            self.wr.write_punct(quote)?;
             self.wr
-                .write_str_lit(node.span, &value.replace("'", "\\'"))?;
-            punct!("'");
+                .write_str_lit(node.span, &value.replace(quote, &format!("\\{}", quote)))?;
+            self.wr.write_punct(quote)?;
         }
     }
 
@@ -722,9 +767,19 @@ impl<'a> Emitter<'a> {
             ClassMember::PrivateMethod(ref n) => emit!(n),
             ClassMember::PrivateProp(ref n) => emit!(n),
             ClassMember::TsIndexSignature(ref n) => emit!(n),
+            ClassMember::StaticBlock(ref n) => emit!(n),
         }
     }
 
+    #[emitter]
+    fn emit_static_block(&mut self, n: &StaticBlock) -> Result {
+        self.emit_leading_comments_of_pos(n.span().lo())?;
+
+        keyword!("static");
+        formatting_space!();
+        emit!(n.body);
+    }
+
     #[emitter]
     fn emit_private_method(&mut self, n: &PrivateMethod) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
@@ -1058,8 +1113,9 @@ impl<'a> Emitter<'a> {
 
     #[emitter]
     fn emit_quasi(&mut self, node: &TplElement) -> Result {
-        self.wr
-            .write_str_lit(node.span, &unescape(&node.raw.value))?;
+        let value = unescape(&node.raw.value);
+        let value = self.escape_unicode(&value, false);
+        self.wr.write_str_lit(node.span, &value)?;
         return Ok(());
     }
 
@@ -1293,8 +1349,23 @@ impl<'a> Emitter<'a> {
             // )?;
             unimplemented!()
         } else {
+            // A surrogate pair is not allowed in an identifier.
+            if self.cfg.ascii_only && self.cfg.target < JscTarget::Es2015 {
+                if let Some(c) = ident.sym.chars().find(|&c| (c as u32) > 0xffff) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "cannot print identifier `{}` as ascii: `{}` can only be escaped with \
+                             `\\u{{...}}`, which requires es2015",
+                            ident.sym, c
+                        ),
+                    ));
+                }
+            }
+
             // TODO: span
-            self.wr.write_symbol(ident.span, &ident.sym)?;
+            let sym = self.escape_unicode(&ident.sym, false);
+            self.wr.write_symbol(ident.span, &sym)?;
 
             if let Some(ty) = &ident.type_ann {
                 punct!(":");
@@ -1,4 +1,4 @@
-use self::swc_ecma_parser::{Parser, Session, SourceFileInput, Syntax};
+use self::swc_ecma_parser::{EsConfig, JscTarget, Parser, Session, SourceFileInput, Syntax};
 use super::*;
 use crate::config::Config;
 use std::{
@@ -26,7 +26,10 @@ impl Builder {
         let mut e = Emitter {
             cfg: self.cfg,
             cm: self.cm.clone(),
-            wr: Box::new(text_writer::JsWriter::new(self.cm.clone(), "\n", s, None)),
+            wr: Box::new(
+                text_writer::JsWriter::new(self.cm.clone(), "\n", s, None)
+                    .with_indent(self.cfg.indent),
+            ),
             comments: Some(&self.comments),
             handlers: Box::new(Noop),
         };
@@ -49,6 +52,20 @@ impl Builder {
 }
 
 fn parse_then_emit(from: &str, cfg: Config) -> String {
+    parse_then_emit_with_syntax(
+        from,
+        cfg,
+        Syntax::Es(EsConfig {
+            num_sep: true,
+            logical_assignment: true,
+            static_blocks: true,
+            private_in_object: true,
+            ..Default::default()
+        }),
+    )
+}
+
+fn parse_then_emit_with_syntax(from: &str, cfg: Config, syntax: Syntax) -> String {
     ::testing::run_test(false, |cm, handler| {
         let src = cm.new_source_file(FileName::Real("custom.js".into()), from.to_string());
         println!(
@@ -60,7 +77,7 @@ fn parse_then_emit(from: &str, cfg: Config) -> String {
         let res = {
             let mut parser = Parser::new(
                 Session { handler: &handler },
-                Syntax::default(),
+                syntax,
                 SourceFileInput::from(&*src),
                 Some(&comments),
             );
@@ -76,23 +93,55 @@ fn parse_then_emit(from: &str, cfg: Config) -> String {
 }
 
 pub(crate) fn assert_min(from: &str, to: &str) {
-    let out = parse_then_emit(from, Config { minify: true });
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify: true,
+            ..Default::default()
+        },
+    );
 
     assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to),);
 }
 
 pub(crate) fn assert_pretty(from: &str, to: &str) {
-    let out = parse_then_emit(from, Config { minify: false });
+    let out = parse_then_emit(
+        from,
+        Config {
+            minify: false,
+            ..Default::default()
+        },
+    );
 
     assert_eq!(DebugUsingDisplay(&out.trim()), DebugUsingDisplay(to),);
 }
 
+/// Parses `from` as typescript, and checks that it's printed as is.
+fn assert_ts_round_trip(from: &str) {
+    let out = parse_then_emit_with_syntax(
+        from,
+        Default::default(),
+        Syntax::Typescript(Default::default()),
+    );
+
+    assert_eq!(
+        DebugUsingDisplay(out.trim()),
+        DebugUsingDisplay(from.trim()),
+    );
+}
+
 fn test_from_to(from: &str, to: &str) {
     let out = parse_then_emit(from, Default::default());
 
     assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to.trim()),);
 }
 
+fn test_from_to_with_config(from: &str, to: &str, cfg: Config) {
+    let out = parse_then_emit(from, cfg);
+
+    assert_eq!(DebugUsingDisplay(out.trim()), DebugUsingDisplay(to.trim()),);
+}
+
 #[test]
 fn empty_stmt() {
     test_from_to(";", ";");
@@ -203,6 +252,156 @@ fn issue_639() {
     test_from_to(r"`\x1b[33m Yellow \x1b[0m`;", r"`\x1b[33m Yellow \x1b[0m`;");
 }
 
+#[test]
+fn quote_double() {
+    test_from_to_with_config(
+        r#"'a'; "b'"; 'c"'; 'd\'"';"#,
+        r#""a";
+"b'";
+'c"';
+"d'\"";"#,
+        Config {
+            quote: Quote::Double,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn indent_tab() {
+    test_from_to_with_config(
+        "if (a) { if (b) { c(); } }",
+        "if (a) {\n\tif (b) {\n\t\tc();\n\t}\n}",
+        Config {
+            indent: Indent::Tab,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn ascii_only() {
+    test_from_to_with_config(
+        "var café = 'é😀';",
+        r"var caf\u00E9 = '\u00E9\uD83D\uDE00';",
+        Config {
+            ascii_only: true,
+            target: JscTarget::Es5,
+            ..Default::default()
+        },
+    );
+
+    test_from_to_with_config(
+        "var café = `é😀`;",
+        r"var caf\u00E9 = `\u00E9\u{1F600}`;",
+        Config {
+            ascii_only: true,
+            target: JscTarget::Es2015,
+            ..Default::default()
+        },
+    );
+}
+
+#[test]
+fn ascii_only_astral_ident() {
+    test_from_to_with_config(
+        "var a𝒜 = 1;",
+        r"var a\u{1D49C} = 1;",
+        Config {
+            ascii_only: true,
+            target: JscTarget::Es2015,
+            ..Default::default()
+        },
+    );
+
+    // Surrogate pairs are not allowed in identifiers.
+    let err = ::testing::run_test(false, |cm, handler| {
+        let src = cm.new_source_file(FileName::Anon, "var a𝒜 = 1;".into());
+        let module = Parser::new(
+            Session { handler: &handler },
+            Syntax::default(),
+            SourceFileInput::from(&*src),
+            None,
+        )
+        .parse_module()
+        .map_err(|mut e| {
+            e.emit();
+        })?;
+
+        let cfg = Config {
+            ascii_only: true,
+            target: JscTarget::Es5,
+            ..Default::default()
+        };
+        let mut buf = vec![];
+        let comments = Default::default();
+        Ok(Builder { cfg, cm, comments }
+            .with("", &mut buf, |e| e.emit_module(&module))
+            .unwrap_err())
+    })
+    .unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("requires es2015"), "{}", err);
+}
+
+#[test]
+fn line_terminator_in_str() {
+    test_from_to(r"'\u{2028}\u2029';", r"'\u2028\u2029';");
+}
+
+#[test]
+fn normalize_literals() {
+    test_from_to("1_000_000;", "1000000;");
+    test_from_to("0b1010_0001;", "161;");
+    test_from_to("0o17;", "15;");
+    test_from_to("'\\x41';", "'A';");
+    // Must not be printed as a legacy octal escape.
+    test_from_to("'\\0\\x31';", "'\\x001';");
+}
+
+#[test]
+fn logical_assignment() {
+    test_from_to("a &&= b;", "a &&= b;");
+    test_from_to("a.b ||= c;", "a.b ||= c;");
+    test_from_to("a[b] ??= c;", "a[b] ??= c;");
+    assert_min("a ??= b", "a??=b;");
+}
+
+#[test]
+fn static_block() {
+    assert_min("class Foo { static { foo; } }", "class Foo{static{foo;}}");
+    assert_min(
+        "class Foo { static {} static() {} }",
+        "class Foo{static{}static(){}}",
+    );
+}
+
+#[test]
+fn private_in_object() {
+    assert_min("#x in obj", "#x in obj;");
+    assert_min("a && #x in obj", "a&&#x in obj;");
+}
+
+#[test]
+fn ts_named_tuple() {
+    assert_ts_round_trip("type A = [first: string, second?: number, ...rest: boolean[]];");
+}
+
+#[test]
+fn ts_variadic_tuple() {
+    assert_ts_round_trip("type A<T extends unknown[]> = [...T, string];");
+}
+
+#[test]
+fn ts_tpl_lit_type() {
+    assert_ts_round_trip("type A = `on${Capitalize<T>}-${string}`;");
+}
+
+#[test]
+fn ts_abstract_ctor_type() {
+    assert_ts_round_trip("let a: abstract new (a: string) => Foo;");
+}
+
 #[derive(Debug, Clone)]
 struct Buf(Arc<RwLock<Vec<u8>>>);
 impl Write for Buf {
@@ -1,5 +1,7 @@
 use super::{Result, WriteJs};
+use crate::config::Indent;
 use std::{
+    borrow::Cow,
     io::{self, Write},
     sync::Arc,
 };
@@ -15,6 +17,7 @@ pub struct JsWriter<'a, W: Write> {
     /// We may use this in future...
     _cm: Arc<SourceMap>,
     indent: usize,
+    indent_str: Cow<'static, str>,
     line_start: bool,
     line_count: usize,
     line_pos: usize,
@@ -34,6 +37,7 @@ impl<'a, W: Write> JsWriter<'a, W> {
         JsWriter {
             _cm: cm,
             indent: Default::default(),
+            indent_str: Cow::Borrowed("    "),
             line_start: true,
             line_count: 0,
             line_pos: Default::default(),
@@ -44,12 +48,23 @@ impl<'a, W: Write> JsWriter<'a, W> {
         }
     }
 
-    fn write_indent_string(&mut self) -> io::Result<usize> {
-        const INDENT: &[u8] = b"    ";
+    /// Sets the string written for each level of indentation.
+    pub fn with_indent(mut self, indent: Indent) -> Self {
+        self.indent_str = match indent {
+            Indent::Spaces(4) => Cow::Borrowed("    "),
+            Indent::Spaces(width) => Cow::Owned(" ".repeat(width)),
+            Indent::Tab => Cow::Borrowed("\t"),
+        };
+        self
+    }
 
+    fn write_indent_string(&mut self) -> io::Result<usize> {
         let mut cnt = 0;
         for _ in 0..self.indent {
-            cnt += self.raw_write(INDENT)?;
+            let written = self.wr.write(self.indent_str.as_bytes())?;
+            self.written_bytes += written;
+            self.line_pos += written;
+            cnt += written;
         }
 
         Ok(cnt)
@@ -87,6 +87,10 @@ impl<'a> Emitter<'a> {
     fn emit_ts_constructor_type(&mut self, n: &TsConstructorType) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
 
+        if n.is_abstract {
+            keyword!("abstract");
+            space!();
+        }
         keyword!("new");
         space!();
         if let Some(type_params) = &n.type_params {
@@ -349,6 +353,25 @@ impl<'a> Emitter<'a> {
         }
     }
 
+    #[emitter]
+    fn emit_ts_tpl_lit_type(&mut self, n: &TsTplLitType) -> Result {
+        debug_assert!(n.quasis.len() == n.types.len() + 1);
+
+        self.emit_leading_comments_of_pos(n.span().lo())?;
+
+        punct!("`");
+        for i in 0..(n.quasis.len() + n.types.len()) {
+            if i % 2 == 0 {
+                emit!(n.quasis[i / 2]);
+            } else {
+                punct!("${");
+                emit!(n.types[i / 2]);
+                punct!("}");
+            }
+        }
+        punct!("`");
+    }
+
     #[emitter]
     fn emit_ts_lit_type(&mut self, n: &TsLitType) -> Result {
         self.emit_leading_comments_of_pos(n.span().lo())?;
@@ -673,6 +696,22 @@ impl<'a> Emitter<'a> {
         punct!("]");
     }
 
+    #[emitter]
+    fn emit_ts_tuple_element(&mut self, n: &TsTupleElement) -> Result {
+        self.emit_leading_comments_of_pos(n.span().lo())?;
+
+        if let Some(label) = &n.label {
+            emit!(label);
+            if let Pat::Ident(Ident { optional: true, .. }) = label {
+                punct!("?");
+            }
+            punct!(":");
+            formatting_space!();
+        }
+
+        emit!(n.ty)
+    }
+
     #[emitter]
     fn emit_ts_type(&mut self, n: &TsType) -> Result {
         match n {
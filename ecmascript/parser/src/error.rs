@@ -20,9 +20,11 @@ pub(crate) struct Eof<'a> {
 
 impl<'a> From<Eof<'a>> for DiagnosticBuilder<'a> {
     fn from(Eof { handler, last }: Eof<'a>) -> Self {
-        let mut db = handler.struct_err("Unexpected eof");
-        db.set_span(last);
-        db
+        DiagnosticBuilder::from(ErrorToDiag {
+            handler,
+            span: last,
+            error: SyntaxError::Eof,
+        })
     }
 }
 
@@ -44,8 +46,20 @@ pub struct Error {
     pub error: SyntaxError,
 }
 
+impl Error {
+    /// Creates a diagnostic for this error, which is not emitted yet.
+    pub fn into_diagnostic(self, handler: &Handler) -> DiagnosticBuilder<'_> {
+        DiagnosticBuilder::from(ErrorToDiag {
+            handler,
+            span: self.span,
+            error: self.error,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum SyntaxError {
+    Eof,
     TopLevelAwait,
 
     LegacyDecimal,
@@ -107,6 +121,10 @@ pub enum SyntaxError {
 
     NullishCoalescingWithLogicalOp,
     NullishCoalescingNotEnabled,
+    LogicalAssignmentNotEnabled,
+    StaticBlocksNotEnabled,
+    PrivateInObjectNotEnabled,
+    PrivateNameWithoutIn,
 
     MultipleDefault {
         /// Span of the previous default case
@@ -147,6 +165,7 @@ pub enum SyntaxError {
     DecoratorOnExport,
 
     TsNonLastRest,
+    TsOptionalAfterRest,
     TsRequiredAfterOptional,
     TsInvalidParamPropPat,
 
@@ -232,6 +251,7 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
     #[cold]
     fn from(e: ErrorToDiag<'a>) -> Self {
         let msg: Cow<'static, _> = match e.error {
+            Eof => "Unexpected eof".into(),
             TopLevelAwait => "top level await requires target to es2017 or higher and \
                               topLevelAwait:true for ecmascript"
                 .into(),
@@ -335,6 +355,7 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
                                   allowed. Please use `export @dec class` instead."
                 .into(),
             TsNonLastRest => "A rest element must be last in a tuple type.".into(),
+            TsOptionalAfterRest => "An optional element cannot follow a rest element.".into(),
             TsRequiredAfterOptional => {
                 "A required element cannot follow an optional element.".into()
             }
@@ -377,6 +398,16 @@ impl<'a> From<ErrorToDiag<'a>> for DiagnosticBuilder<'a> {
             NullishCoalescingNotEnabled => {
                 "Nullish coalescing operator(??) requires jsc.parser.Coalescing".into()
             }
+            LogicalAssignmentNotEnabled => "Logical assignment operators(&&=, ||=, ??=) require \
+                                            jsc.parser.logicalAssignment"
+                .into(),
+            StaticBlocksNotEnabled => "Class static blocks require jsc.parser.staticBlocks".into(),
+            PrivateInObjectNotEnabled => {
+                "Private names in `in` expressions require jsc.parser.privateInObject".into()
+            }
+            PrivateNameWithoutIn => "Private names are only allowed as the left-hand side of `in` \
+                                     or in member expressions"
+                .into(),
 
             TS1056 => "jsc.taraget should be es5 or upper to use getter / setter".into(),
             TS1110 => "type expected".into(),
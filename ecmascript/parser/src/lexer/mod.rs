@@ -219,6 +219,12 @@ impl<'a, I: Input> Lexer<'a, I> {
                 Some('?') => {
                     self.input.bump();
                     self.input.bump();
+
+                    // '??='
+                    if self.input.cur() == Some('=') {
+                        self.input.bump();
+                        return Ok(Some(AssignOp(NullishAssign)));
+                    }
                     return Ok(Some(tok!("??")));
                 }
                 _ => {
@@ -325,6 +331,17 @@ impl<'a, I: Input> Lexer<'a, I> {
                 // '||', '&&'
                 if self.input.cur() == Some(c) {
                     self.input.bump();
+
+                    // '||=', '&&='
+                    if self.input.cur() == Some('=') {
+                        self.input.bump();
+                        return Ok(Some(AssignOp(match token {
+                            BitAnd => AndAssign,
+                            BitOr => OrAssign,
+                            _ => unreachable!(),
+                        })));
+                    }
+
                     return Ok(Some(BinOp(match token {
                         BitAnd => LogicalAnd,
                         BitOr => LogicalOr,
@@ -413,7 +430,12 @@ impl<'a, I: Input> Lexer<'a, I> {
             }
 
             // unexpected character
-            c => self.error_span(pos_span(start), SyntaxError::UnexpectedChar { c })?,
+            c => {
+                // Skip the character so that a recovering parser does not
+                // get the same error forever.
+                self.input.bump();
+                self.error_span(pos_span(start), SyntaxError::UnexpectedChar { c })?
+            }
         };
 
         Ok(Some(token))
@@ -429,7 +451,10 @@ impl<'a, I: Input> Lexer<'a, I> {
             return Ok(None);
         }
 
-        if self.syntax.class_private_props() || self.syntax.class_private_methods() {
+        if self.syntax.class_private_props()
+            || self.syntax.class_private_methods()
+            || self.syntax.private_in_object()
+        {
             self.input.bump(); // '#'
             return Ok(Some(Token::Hash));
         }
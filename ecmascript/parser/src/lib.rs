@@ -98,6 +98,7 @@
 #![deny(unsafe_code)]
 
 pub use self::{
+    error::{Error, SyntaxError},
     lexer::input::{Input, SourceFileInput},
     parser::*,
 };
@@ -284,6 +285,42 @@ impl Syntax {
         }
     }
 
+    pub fn logical_assignment(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig {
+                logical_assignment: true,
+                ..
+            })
+            | Syntax::Typescript(..) => true,
+
+            _ => false,
+        }
+    }
+
+    pub fn static_blocks(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig {
+                static_blocks: true,
+                ..
+            })
+            | Syntax::Typescript(..) => true,
+
+            _ => false,
+        }
+    }
+
+    pub fn private_in_object(self) -> bool {
+        match self {
+            Syntax::Es(EsConfig {
+                private_in_object: true,
+                ..
+            })
+            | Syntax::Typescript(..) => true,
+
+            _ => false,
+        }
+    }
+
     pub fn top_level_await(self) -> bool {
         match self {
             Syntax::Es(EsConfig {
@@ -347,6 +384,10 @@ pub enum JscTarget {
     Es2018,
     #[serde(rename = "es2019")]
     Es2019,
+    #[serde(rename = "es2020")]
+    Es2020,
+    #[serde(rename = "es2021")]
+    Es2021,
 }
 
 impl Default for JscTarget {
@@ -417,6 +458,21 @@ pub struct EsConfig {
     /// Stage 3.
     #[serde(default)]
     pub top_level_await: bool,
+
+    /// `&&=`, `||=` and `??=`.
+    /// Stage 4.
+    #[serde(default)]
+    pub logical_assignment: bool,
+
+    /// `static { ... }` in classes.
+    /// Stage 3.
+    #[serde(default)]
+    pub static_blocks: bool,
+
+    /// `#x in obj`.
+    /// Stage 3.
+    #[serde(default)]
+    pub private_in_object: bool,
 }
 
 /// Syntactic context.
@@ -278,7 +278,16 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 continue;
             }
 
-            elems.push(self.parse_class_member()?);
+            let start = cur_pos!();
+            let ctx = self.ctx();
+            match self.parse_class_member() {
+                Ok(member) => elems.push(member),
+                Err(err) if self.is_recovering() => {
+                    self.set_ctx(ctx);
+                    elems.push(self.recover_class_member(err, start));
+                }
+                Err(err) => return Err(err),
+            }
         }
         Ok(elems)
     }
@@ -318,6 +327,10 @@ impl<'a, I: Tokens> Parser<'a, I> {
         };
 
         if let Some(static_token) = static_token {
+            if is!('{') {
+                return self.parse_static_block(start);
+            }
+
             // Handle static(){}
             if self.is_class_method()? {
                 let key = Either::Right(PropName::Ident(Ident::new(
@@ -366,6 +379,34 @@ impl<'a, I: Tokens> Parser<'a, I> {
         self.parse_class_member_with_is_static(start, accessibility, static_token, decorators)
     }
 
+    /// Parses `{ ... }` of `static { ... }`.
+    fn parse_static_block(&mut self, start: BytePos) -> PResult<'a, ClassMember> {
+        if !self.input.syntax().static_blocks() {
+            syntax_error!(span!(start), SyntaxError::StaticBlocksNotEnabled)
+        }
+
+        let ctx = Context {
+            in_async: false,
+            in_generator: false,
+            in_function: false,
+            in_method: false,
+            in_class_prop: true,
+            is_break_allowed: false,
+            is_continue_allowed: false,
+            ..self.ctx()
+        };
+        let state = State {
+            labels: vec![],
+            ..Default::default()
+        };
+        let body = self.with_ctx(ctx).with_state(state).parse_block(false)?;
+
+        Ok(ClassMember::StaticBlock(StaticBlock {
+            span: span!(start),
+            body,
+        }))
+    }
+
     #[allow(clippy::cognitive_complexity)]
     fn parse_class_member_with_is_static(
         &mut self,
@@ -1232,4 +1273,71 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn static_block() {
+        let class_expr = |s| {
+            test_parser(
+                s,
+                Syntax::Es(crate::EsConfig {
+                    static_blocks: true,
+                    ..Default::default()
+                }),
+                |p| p.parse_expr().map_err(|mut e| e.emit()),
+            )
+        };
+
+        testing::assert_eq_ignore_span!(
+            class_expr("(class { static { foo(); } static() {} })"),
+            Box::new(Expr::Paren(ParenExpr {
+                span,
+                expr: Box::new(Expr::Class(ClassExpr {
+                    ident: None,
+                    class: Class {
+                        decorators: vec![],
+                        span,
+                        body: vec![
+                            ClassMember::StaticBlock(StaticBlock {
+                                span,
+                                body: BlockStmt {
+                                    span,
+                                    stmts: vec![Stmt::Expr(ExprStmt {
+                                        span,
+                                        expr: expr("foo()"),
+                                    })],
+                                },
+                            }),
+                            ClassMember::Method(ClassMethod {
+                                span,
+                                key: PropName::Ident(Ident::new("static".into(), span)),
+                                function: Function {
+                                    params: vec![],
+                                    decorators: vec![],
+                                    span,
+                                    body: Some(BlockStmt {
+                                        span,
+                                        stmts: vec![],
+                                    }),
+                                    is_generator: false,
+                                    is_async: false,
+                                    type_params: None,
+                                    return_type: None,
+                                },
+                                kind: MethodKind::Method,
+                                is_static: false,
+                                accessibility: None,
+                                is_abstract: false,
+                                is_optional: false,
+                            }),
+                        ],
+                        super_class: None,
+                        implements: vec![],
+                        is_abstract: false,
+                        super_type_params: None,
+                        type_params: None,
+                    },
+                })),
+            }))
+        );
+    }
 }
@@ -158,6 +158,18 @@ impl<'a, I: Tokens> Parser<'a, I> {
                         self.emit_err(cond.span(), SyntaxError::TS1100);
                     }
 
+                    match op {
+                        op!("&&=") | op!("||=") | op!("??=")
+                            if !self.input.syntax().logical_assignment() =>
+                        {
+                            self.emit_err(
+                                self.input.cur_span(),
+                                SyntaxError::LogicalAssignmentNotEnabled,
+                            )
+                        }
+                        _ => {}
+                    }
+
                     // TODO
                     PatOrExpr::Expr(cond)
                 };
@@ -818,7 +830,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         })
     }
 
-    fn parse_tpl_element(&mut self, is_tagged: bool) -> PResult<'a, TplElement> {
+    pub(super) fn parse_tpl_element(&mut self, is_tagged: bool) -> PResult<'a, TplElement> {
         let start = cur_pos!();
 
         let (raw, cooked) = match *cur!(true)? {
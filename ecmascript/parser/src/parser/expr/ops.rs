@@ -10,6 +10,11 @@ impl<'a, I: Tokens> Parser<'a, I> {
     pub(super) fn parse_bin_expr(&mut self) -> PResult<'a, Box<Expr>> {
         let ctx = self.ctx();
 
+        if is!('#') {
+            let left = self.parse_private_name_in_left()?;
+            return self.parse_bin_op_recursively(left, 0);
+        }
+
         let left = match self.parse_unary_expr() {
             Ok(v) => v,
             Err(mut err) => {
@@ -150,7 +155,11 @@ impl<'a, I: Tokens> Parser<'a, I> {
         }
 
         let right = {
-            let left_of_right = self.parse_unary_expr()?;
+            let left_of_right = if is!('#') {
+                self.parse_private_name_in_left()?
+            } else {
+                self.parse_unary_expr()?
+            };
             self.parse_bin_op_recursively(
                 left_of_right,
                 if op == op!("**") {
@@ -161,6 +170,10 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 },
             )?
         };
+        // `a + #x in b` is invalid.
+        if let Expr::PrivateName(..) = *right {
+            syntax_error!(right.span(), SyntaxError::PrivateNameWithoutIn)
+        }
         /* this check is for all ?? operators
          * a ?? b && c for this example
          * b && c => This is considered as a logical expression in the ast tree
@@ -210,6 +223,21 @@ impl<'a, I: Tokens> Parser<'a, I> {
         Ok(expr)
     }
 
+    /// Parses `#x` of `#x in obj`.
+    fn parse_private_name_in_left(&mut self) -> PResult<'a, Box<Expr>> {
+        let start = cur_pos!();
+        let name = self.parse_private_name()?;
+
+        if !self.input.syntax().private_in_object() {
+            syntax_error!(span!(start), SyntaxError::PrivateInObjectNotEnabled)
+        }
+        if !self.ctx().include_in_expr || !is!("in") {
+            syntax_error!(span!(start), SyntaxError::PrivateNameWithoutIn)
+        }
+
+        Ok(Box::new(Expr::PrivateName(name)))
+    }
+
     /// Parse unary expression and update expression.
     ///
     /// spec: 'UnaryExpression'
@@ -424,6 +424,92 @@ fn issue_675() {
     expr("Object.setPrototypeOf(this, new.target.prototype)");
 }
 
+#[test]
+fn logical_assignment() {
+    let expr = |s| {
+        test_parser(
+            s,
+            Syntax::Es(EsConfig {
+                logical_assignment: true,
+                ..Default::default()
+            }),
+            |p| p.parse_expr().map_err(|mut e| e.emit()),
+        )
+    };
+
+    assert_eq_ignore_span!(
+        expr("a.b ??= c"),
+        box Expr::Assign(AssignExpr {
+            span,
+            op: op!("??="),
+            left: PatOrExpr::Expr(member_expr("a.b")),
+            right: box Expr::Ident(Ident::new("c".into(), span)),
+        })
+    );
+    assert_eq_ignore_span!(
+        expr("a &&= b ||= c"),
+        box Expr::Assign(AssignExpr {
+            span,
+            op: op!("&&="),
+            left: PatOrExpr::Expr(box Expr::Ident(Ident::new("a".into(), span))),
+            right: box Expr::Assign(AssignExpr {
+                span,
+                op: op!("||="),
+                left: PatOrExpr::Expr(box Expr::Ident(Ident::new("b".into(), span))),
+                right: box Expr::Ident(Ident::new("c".into(), span)),
+            }),
+        })
+    );
+}
+
+#[test]
+fn private_in_object() {
+    let expr = |s| {
+        test_parser(
+            s,
+            Syntax::Es(EsConfig {
+                private_in_object: true,
+                ..Default::default()
+            }),
+            |p| {
+                p.include_in_expr(true)
+                    .parse_expr()
+                    .map_err(|mut e| e.emit())
+            },
+        )
+    };
+    let private_name = |s: &str| {
+        box Expr::PrivateName(PrivateName {
+            span,
+            id: Ident::new(s.into(), span),
+        })
+    };
+
+    assert_eq_ignore_span!(
+        expr("#x in obj"),
+        box Expr::Bin(BinExpr {
+            span,
+            op: op!("in"),
+            left: private_name("x"),
+            right: box Expr::Ident(Ident::new("obj".into(), span)),
+        })
+    );
+    assert_eq_ignore_span!(
+        expr("a && #x in obj"),
+        box Expr::Bin(BinExpr {
+            span,
+            op: op!("&&"),
+            left: box Expr::Ident(Ident::new("a".into(), span)),
+            right: box Expr::Bin(BinExpr {
+                span,
+                op: op!("in"),
+                left: private_name("x"),
+                right: box Expr::Ident(Ident::new("obj".into(), span)),
+            }),
+        })
+    );
+}
+
 #[bench]
 fn bench_new_expr_ts(b: &mut Bencher) {
     bench_parser(
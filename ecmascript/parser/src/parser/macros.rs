@@ -192,12 +192,7 @@ macro_rules! cur {
         if is_err_token {
             match $p.input.bump() {
                 $crate::token::Token::Error(e) => {
-                    let err =
-                        ::swc_common::errors::DiagnosticBuilder::from($crate::error::ErrorToDiag {
-                            handler: &$p.session.handler,
-                            span: e.span,
-                            error: e.error,
-                        });
+                    let err = $p.make_error(e.span, e.error);
                     return Err(err.into());
                 }
                 _ => unreachable!(),
@@ -208,10 +203,7 @@ macro_rules! cur {
             Some(c) => Ok(c),
             None => {
                 if $required {
-                    let err = ::swc_common::errors::DiagnosticBuilder::from($crate::error::Eof {
-                        last,
-                        handler: &$p.session.handler,
-                    });
+                    let err = $p.make_error(last, $crate::error::SyntaxError::Eof);
                     return Err(err.into());
                 }
                 Err($crate::error::Eof {
@@ -237,11 +229,8 @@ Current token is {:?}",
         match $p.input.peek() {
             Some(c) => Ok(c),
             None => {
-                let err = ::swc_common::errors::DiagnosticBuilder::from($crate::error::Eof {
-                    //TODO: Use whole span
-                    last,
-                    handler: &$p.session.handler,
-                });
+                //TODO: Use whole span
+                let err = $p.make_error(last, $crate::error::SyntaxError::Eof);
                 Err(err)
             }
         }
@@ -312,11 +301,7 @@ macro_rules! span {
 
 macro_rules! make_error {
     ($p:expr, $span:expr, $err:expr) => {{
-        ::swc_common::errors::DiagnosticBuilder::from($crate::error::ErrorToDiag {
-            handler: $p.session.handler,
-            span: $span,
-            error: $err,
-        })
+        $p.make_error($span, $err)
     }};
 }
 
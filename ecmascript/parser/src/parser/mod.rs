@@ -3,12 +3,16 @@
 pub use self::input::{Capturing, Tokens, TokensInput};
 use self::{input::Buffer, util::ParseObject};
 use crate::{
-    error::{ErrorToDiag, SyntaxError},
+    error::{Error, ErrorToDiag, SyntaxError},
     lexer::Lexer,
     token::{Token, Word},
     Context, JscTarget, Session, Syntax,
 };
-use std::ops::{Deref, DerefMut};
+use std::{
+    cell::RefCell,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
 use swc_atoms::JsWord;
 use swc_common::{comments::Comments, errors::DiagnosticBuilder, input::Input, BytePos, Span};
 use swc_ecma_ast::*;
@@ -27,6 +31,7 @@ pub mod input;
 mod jsx;
 mod object;
 mod pat;
+mod recovery;
 mod stmt;
 mod typescript;
 mod util;
@@ -42,6 +47,8 @@ pub struct Parser<'a, I: Tokens> {
     session: Session<'a>,
     state: State,
     input: Buffer<I>,
+    /// Errors recovered from. [None] if recovery is disabled.
+    recovered: Option<Rc<RefCell<self::recovery::Recovered>>>,
 }
 
 #[derive(Clone, Default)]
@@ -74,6 +81,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
             session,
             input: Buffer::new(input),
             state: Default::default(),
+            recovered: None,
         }
     }
 
@@ -164,12 +172,15 @@ impl<'a, I: Tokens> Parser<'a, I> {
             return;
         }
 
-        DiagnosticBuilder::from(ErrorToDiag {
-            handler: self.session.handler,
-            span,
-            error,
-        })
-        .emit();
+        match self.recovered {
+            Some(ref recovered) => recovered.borrow_mut().errors.push(Error { span, error }),
+            None => DiagnosticBuilder::from(ErrorToDiag {
+                handler: self.session.handler,
+                span,
+                error,
+            })
+            .emit(),
+        }
     }
 }
 
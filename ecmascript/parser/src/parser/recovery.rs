@@ -0,0 +1,356 @@
+//! Error recovery.
+//!
+//! When recovery is enabled, a statement (or a class member) which cannot be
+//! parsed is replaced with a placeholder node and the parser skips to the
+//! next statement (or member) boundary.
+
+use super::*;
+use crate::error::ErrorToDiag;
+
+#[derive(Default)]
+pub(super) struct Recovered {
+    pub errors: Vec<Error>,
+    /// Errors created since the last recovery, which are used to find the
+    /// [SyntaxError] of a [DiagnosticBuilder].
+    created: Vec<Error>,
+}
+
+#[parser]
+impl<'a, I: Tokens> Parser<'a, I> {
+    /// Parses a module, recovering from errors.
+    ///
+    /// Statements which cannot be parsed are replaced with an expression
+    /// statement of [Expr::Invalid]. Returned errors are not emitted, and
+    /// can be emitted with [Error::into_diagnostic].
+    pub fn parse_module_with_recovery(&mut self) -> (Module, Vec<Error>) {
+        self.with_recovery(
+            |p| p.parse_module(),
+            |span| Module {
+                span,
+                body: vec![],
+                shebang: None,
+            },
+        )
+    }
+
+    /// Parses a typescript module, recovering from errors.
+    ///
+    /// See [Parser::parse_module_with_recovery].
+    pub fn parse_typescript_module_with_recovery(&mut self) -> (Module, Vec<Error>) {
+        self.with_recovery(
+            |p| p.parse_typescript_module(),
+            |span| Module {
+                span,
+                body: vec![],
+                shebang: None,
+            },
+        )
+    }
+
+    /// Parses a script, recovering from errors.
+    ///
+    /// See [Parser::parse_module_with_recovery].
+    pub fn parse_script_with_recovery(&mut self) -> (Script, Vec<Error>) {
+        self.with_recovery(
+            |p| p.parse_script(),
+            |span| Script {
+                span,
+                body: vec![],
+                shebang: None,
+            },
+        )
+    }
+
+    fn with_recovery<T, F, D>(&mut self, op: F, dummy: D) -> (T, Vec<Error>)
+    where
+        F: FnOnce(&mut Self) -> PResult<'a, T>,
+        D: FnOnce(Span) -> T,
+    {
+        let start = cur_pos!();
+        self.recovered = Some(Default::default());
+
+        let res = op(self);
+        let node = match res {
+            Ok(node) => node,
+            // Errors which cannot be recovered from, like an invalid shebang.
+            Err(err) => {
+                self.store_error(err);
+                dummy(span!(start))
+            }
+        };
+
+        let errors = self
+            .recovered
+            .take()
+            .map(|recovered| recovered.replace(Default::default()).errors)
+            .unwrap_or_default();
+
+        (node, errors)
+    }
+
+    /// Creates a diagnostic for `error`.
+    ///
+    /// If recovery is enabled, `error` is recorded so that it can be returned
+    /// if the diagnostic is recovered from.
+    pub(super) fn make_error(&self, span: Span, error: SyntaxError) -> DiagnosticBuilder<'a> {
+        if let Some(ref recovered) = self.recovered {
+            recovered.borrow_mut().created.push(Error {
+                span,
+                error: error.clone(),
+            });
+        }
+
+        DiagnosticBuilder::from(ErrorToDiag {
+            handler: self.session.handler,
+            span,
+            error,
+        })
+    }
+
+    /// Returns true if errors should be recovered from.
+    ///
+    /// Recovery is disabled while backtracking, as the parser should fail
+    /// to try other syntax.
+    pub(super) fn is_recovering(&self) -> bool {
+        self.emit_err && self.recovered.is_some()
+    }
+
+    fn store_error(&self, mut err: DiagnosticBuilder<'a>) {
+        if let Some(ref recovered) = self.recovered {
+            let mut recovered = recovered.borrow_mut();
+            let span = err.span.primary_span().unwrap_or_default();
+
+            // Only an unexpected eof is not created by `make_error`.
+            let error = recovered
+                .created
+                .iter()
+                .rev()
+                .find(|e| e.span == span)
+                .cloned()
+                .unwrap_or(Error {
+                    span,
+                    error: SyntaxError::Eof,
+                });
+            recovered.created.clear();
+            recovered.errors.push(error);
+        }
+        err.cancel();
+    }
+
+    /// Stores errors of tokens which cannot be lexed.
+    pub(super) fn skip_error_tokens(&mut self) {
+        while let Some(&Token::Error(..)) = self.input.cur() {
+            match self.input.bump() {
+                Token::Error(e) => {
+                    if let Some(ref recovered) = self.recovered {
+                        recovered.borrow_mut().errors.push(e);
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    /// Stores `err` and skips to the next statement.
+    ///
+    /// Returns a placeholder for the skipped statement.
+    pub(super) fn recover_stmt(
+        &mut self,
+        err: DiagnosticBuilder<'a>,
+        start: BytePos,
+        end: Option<&Token>,
+    ) -> Stmt {
+        self.store_error(err);
+        self.skip_to_boundary(start, end);
+
+        let span = span!(start);
+        Stmt::Expr(ExprStmt {
+            span,
+            expr: Box::new(Expr::Invalid(Invalid { span })),
+        })
+    }
+
+    /// Stores `err` and skips to the next class member.
+    ///
+    /// Returns a property with an invalid key in place of the skipped member.
+    pub(super) fn recover_class_member(
+        &mut self,
+        err: DiagnosticBuilder<'a>,
+        start: BytePos,
+    ) -> ClassMember {
+        self.store_error(err);
+        self.skip_to_boundary(start, Some(&tok!('}')));
+
+        let span = span!(start);
+        ClassMember::ClassProp(ClassProp {
+            span,
+            key: Box::new(Expr::Invalid(Invalid { span })),
+            value: None,
+            type_ann: None,
+            is_static: false,
+            decorators: vec![],
+            computed: false,
+            accessibility: None,
+            is_abstract: false,
+            is_optional: false,
+            readonly: false,
+            definite: false,
+        })
+    }
+
+    /// Skips tokens until a `;` (which is consumed), `end` or a line break, at
+    /// the nesting level of `start`.
+    ///
+    /// At least one token is skipped if the parser did not advance since
+    /// `start`, so that recovery always makes progress.
+    fn skip_to_boundary(&mut self, start: BytePos, end: Option<&Token>) {
+        let mut depth = 0usize;
+
+        loop {
+            self.skip_error_tokens();
+
+            let progressed = self.input.cur_pos() > start;
+            let line_break = self.input.had_line_break_before_cur();
+            let cur = match self.input.cur() {
+                Some(cur) => cur,
+                None => return,
+            };
+
+            if depth == 0 {
+                if Some(cur) == end {
+                    if progressed {
+                        return;
+                    }
+                } else if *cur == Token::Semi {
+                    self.input.bump();
+                    return;
+                } else if progressed && line_break {
+                    return;
+                }
+            }
+
+            match *cur {
+                tok!('{') | tok!('(') | tok!('[') | tok!("${") => depth += 1,
+                tok!('}') | tok!(')') | tok!(']') => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+
+            self.input.bump();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::Error, lexer::Lexer, with_test_sess, JscTarget, Parser, Syntax, SyntaxError,
+    };
+    use swc_ecma_ast::*;
+
+    fn parse_with_errors(src: &'static str) -> (Module, Vec<Error>) {
+        with_test_sess(src, |sess, input| {
+            let lexer = Lexer::new(sess, Syntax::default(), JscTarget::Es2019, input, None);
+            Ok(Parser::new_from(sess, lexer).parse_module_with_recovery())
+        })
+        .unwrap()
+    }
+
+    fn parse(src: &'static str) -> (Module, usize) {
+        let (m, errors) = parse_with_errors(src);
+        (m, errors.len())
+    }
+
+    fn is_invalid(item: &ModuleItem) -> bool {
+        match item {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => match **expr {
+                Expr::Invalid(..) => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn no_error() {
+        let (m, errors) = parse("foo();\nbar();");
+        assert_eq!(errors, 0);
+        assert_eq!(m.body.len(), 2);
+    }
+
+    #[test]
+    fn stmt() {
+        let (m, errors) = parse("let a = ;\nfoo();");
+        assert_eq!(errors, 1);
+        assert_eq!(m.body.len(), 2);
+        assert!(is_invalid(&m.body[0]));
+        assert!(!is_invalid(&m.body[1]));
+    }
+
+    #[test]
+    fn multiple_errors() {
+        let (m, errors) = parse("let a = ;\nfoo();\nif (;\nbar();");
+        assert_eq!(errors, 2);
+        assert_eq!(m.body.len(), 4);
+        assert!(is_invalid(&m.body[2]));
+    }
+
+    #[test]
+    fn nested() {
+        let (m, errors) = parse("function foo() {\n    let a = ;\n    bar();\n}\nbaz();");
+        assert_eq!(errors, 1);
+        assert_eq!(m.body.len(), 2);
+
+        match m.body[0] {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(ref f))) => {
+                let body = &f.function.body.as_ref().unwrap().stmts;
+                assert_eq!(body.len(), 2);
+            }
+            _ => panic!("expected a function, got {:?}", m.body[0]),
+        }
+    }
+
+    #[test]
+    fn class_member() {
+        let (m, errors) = parse("class Foo {\n    a = ;\n    b() {}\n}");
+        assert_eq!(errors, 1);
+
+        match m.body[0] {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Class(ref c))) => {
+                assert_eq!(c.class.body.len(), 2);
+                match c.class.body[1] {
+                    ClassMember::Method(..) => {}
+                    _ => panic!("expected a method, got {:?}", c.class.body[1]),
+                }
+            }
+            _ => panic!("expected a class, got {:?}", m.body[0]),
+        }
+    }
+
+    #[test]
+    fn unclosed_block() {
+        let (m, errors) = parse("function foo() {\n    bar();");
+        assert_eq!(errors, 1);
+        assert_eq!(m.body.len(), 1);
+    }
+
+    #[test]
+    fn error_kinds() {
+        let (_, errors) = parse_with_errors("foo(;\nfunction f() {");
+        assert_eq!(errors.len(), 2, "{:?}", errors);
+        assert_ne!(errors[0].error, SyntaxError::Eof);
+        assert_eq!(errors[1].error, SyntaxError::Eof);
+    }
+
+    #[test]
+    fn stray_close_brace() {
+        let (m, errors) = parse("foo();\n}\nbar();");
+        assert_eq!(errors, 1);
+        assert_eq!(m.body.len(), 3);
+    }
+
+    #[test]
+    fn error_token() {
+        let (_, errors) = parse("foo\0+\0bar;\nbaz();");
+        assert!(errors > 0);
+    }
+}
@@ -25,10 +25,31 @@ impl<'a, I: Tokens> Parser<'a, I> {
 
         let mut stmts = vec![];
         while {
+            if self.is_recovering() {
+                self.skip_error_tokens();
+            }
             let c = cur!(false).ok();
             c != end
         } {
-            let stmt = self.parse_stmt_like(true, top_level)?;
+            let start = cur_pos!();
+            let ctx = self.ctx();
+            let labels = self.state.labels.len();
+
+            let stmt = match self.parse_stmt_like(true, top_level) {
+                Ok(stmt) => stmt,
+                Err(err) if self.is_recovering() => {
+                    self.set_ctx(ctx);
+                    self.state.labels.truncate(labels);
+
+                    let stmt = self.recover_stmt(err, start, end).into();
+                    stmts.push(stmt);
+                    if eof!() {
+                        break;
+                    }
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
             if allow_directives {
                 allow_directives = false;
                 if stmt.is_use_strict() {
@@ -51,7 +72,8 @@ impl<'a, I: Tokens> Parser<'a, I> {
             stmts.push(stmt);
         }
 
-        if end.is_some() {
+        // Recovered from an unexpected eof.
+        if end.is_some() && !eof!() {
             bump!();
         }
 
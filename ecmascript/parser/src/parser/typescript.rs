@@ -799,8 +799,8 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 .map(TsType::from)
                 .map(Box::new);
         }
-        if is!("new") {
-            // As in `new () => Date`
+        if is!("new") || (is!("abstract") && peeked_is!("new")) {
+            // As in `new () => Date` or `abstract new () => Date`
             return self
                 .parse_ts_fn_or_constructor_type(false)
                 .map(TsType::from)
@@ -1448,31 +1448,48 @@ impl<'a, I: Tokens> Parser<'a, I> {
         let start = cur_pos!();
         let elem_types = self.parse_ts_bracketed_list(
             ParsingContext::TupleElementTypes,
-            |p| p.parse_ts_tuple_element_type(),
+            |p| p.parse_ts_tuple_element(),
             /* bracket */ true,
             /* skipFirstToken */ false,
         )?;
 
         // Validate the elementTypes to ensure:
         //   No mandatory elements may follow optional elements
-        //   If there's a rest element, it must be at the end of the tuple
+        //   No optional elements may follow rest elements
+        //   A rest element of an array type, like `...T[]`, is the last one
+        //
+        // Variadic elements are allowed anywhere, as in `[...T, ...U]`.
 
-        let mut seen_optional_element = false;
         let len = elem_types.len();
-        for (i, elem_type) in elem_types.iter().enumerate() {
-            match **elem_type {
-                TsType::TsRestType(..) => {
+        let mut seen_optional_element = false;
+        let mut seen_rest_element = false;
+        for (i, elem) in elem_types.iter().enumerate() {
+            let (is_optional, rest_ty) = match (&elem.label, &elem.ty) {
+                (Some(Pat::Ident(Ident { optional, .. })), _) => (*optional, None),
+                (Some(Pat::Rest(..)), ty) => (false, Some(ty)),
+                (None, TsType::TsRestType(TsRestType { type_ann, .. })) => {
+                    (false, Some(&**type_ann))
+                }
+                (None, TsType::TsOptionalType(..)) => (true, None),
+                _ => (false, None),
+            };
+
+            if is_optional {
+                if seen_rest_element {
+                    syntax_error!(span!(start), SyntaxError::TsOptionalAfterRest)
+                }
+                seen_optional_element = true;
+            } else if seen_optional_element && rest_ty.is_none() {
+                syntax_error!(span!(start), SyntaxError::TsRequiredAfterOptional)
+            }
+
+            if let Some(ty) = rest_ty {
+                seen_rest_element = true;
+                if let TsType::TsArrayType(..) = ty {
                     if i != len - 1 {
                         syntax_error!(span!(start), SyntaxError::TsNonLastRest)
                     }
                 }
-                TsType::TsOptionalType(..) => {
-                    seen_optional_element = true;
-                }
-                _ if seen_optional_element => {
-                    syntax_error!(span!(start), SyntaxError::TsRequiredAfterOptional)
-                }
-                _ => {}
             }
         }
 
@@ -1482,6 +1499,64 @@ impl<'a, I: Tokens> Parser<'a, I> {
         })
     }
 
+    /// Returns true for `a: T`, `a?: T` and `...a: T`.
+    fn is_ts_labeled_tuple_element(&mut self) -> PResult<'a, bool> {
+        debug_assert!(self.input.syntax().typescript());
+
+        if is!("...") {
+            bump!();
+        }
+        if !is!(IdentName) {
+            return Ok(false);
+        }
+        bump!();
+        if is!('?') {
+            bump!();
+        }
+
+        Ok(is!(':'))
+    }
+
+    /// `tsParseTupleElementTypeWithLabel`
+    fn parse_ts_tuple_element(&mut self) -> PResult<'a, TsTupleElement> {
+        debug_assert!(self.input.syntax().typescript());
+
+        let start = cur_pos!();
+
+        if !self.ts_look_ahead(|p| p.is_ts_labeled_tuple_element())? {
+            let ty = self.parse_ts_tuple_element_type()?;
+            return Ok(TsTupleElement {
+                span: span!(start),
+                label: None,
+                ty: *ty,
+            });
+        }
+
+        let label = if is!("...") {
+            bump!();
+            let dot3_token = span!(start);
+            let arg = self.parse_ident_name()?;
+            Pat::Rest(RestPat {
+                span: span!(start),
+                dot3_token,
+                arg: Box::new(Pat::Ident(arg)),
+                type_ann: None,
+            })
+        } else {
+            let mut ident = self.parse_ident_name()?;
+            ident.optional = eat!('?');
+            Pat::Ident(ident)
+        };
+        expect!(':');
+        let ty = self.parse_ts_type()?;
+
+        Ok(TsTupleElement {
+            span: span!(start),
+            label: Some(label),
+            ty: *ty,
+        })
+    }
+
     /// `tsParseTupleElementType`
     fn parse_ts_tuple_element_type(&mut self) -> PResult<'a, Box<TsType>> {
         debug_assert!(self.input.syntax().typescript());
@@ -1534,7 +1609,9 @@ impl<'a, I: Tokens> Parser<'a, I> {
         debug_assert!(self.input.syntax().typescript());
 
         let start = cur_pos!();
+        let mut is_abstract = false;
         if !is_fn_type {
+            is_abstract = eat!("abstract");
             expect!("new");
         }
 
@@ -1558,6 +1635,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
                 type_params,
                 params,
                 type_ann,
+                is_abstract,
             })
         })
     }
@@ -1569,13 +1647,7 @@ impl<'a, I: Tokens> Parser<'a, I> {
         let start = cur_pos!();
 
         let lit = if is!('`') {
-            let tpl = self.parse_tpl()?;
-            if !tpl.exprs.is_empty() {
-                // template literals with expressions are not
-                // allowed in a type
-                self.emit_err(span!(start), SyntaxError::TS1110);
-            }
-            TsLit::Tpl(tpl)
+            TsLit::Tpl(self.parse_ts_tpl_lit_type()?)
         } else {
             match self.parse_lit()? {
                 Lit::Bool(n) => TsLit::Bool(n),
@@ -1591,6 +1663,37 @@ impl<'a, I: Tokens> Parser<'a, I> {
         })
     }
 
+    /// `tsParseTemplateLiteralType`
+    fn parse_ts_tpl_lit_type(&mut self) -> PResult<'a, TsTplLitType> {
+        debug_assert!(self.input.syntax().typescript());
+
+        let start = cur_pos!();
+
+        assert_and_bump!('`');
+
+        let cur_elem = self.parse_tpl_element(false)?;
+        let mut is_tail = cur_elem.tail;
+        let mut quasis = vec![cur_elem];
+        let mut types = vec![];
+
+        while !is_tail {
+            expect!("${");
+            types.push(self.parse_ts_type()?);
+            expect!('}');
+            let elem = self.parse_tpl_element(false)?;
+            is_tail = elem.tail;
+            quasis.push(elem);
+        }
+
+        expect!('`');
+
+        Ok(TsTplLitType {
+            span: span!(start),
+            types,
+            quasis,
+        })
+    }
+
     /// `tsParseBindingListForSignature`
     fn parse_ts_binding_list_for_signature(&mut self) -> PResult<'a, Vec<TsFnParam>> {
         debug_assert!(self.input.syntax().typescript());
@@ -2501,4 +2604,117 @@ mod tests {
         })
         .unwrap();
     }
+
+    /// Parses `type T = ...;` and returns the aliased type.
+    fn parse_type_alias(src: &'static str) -> Box<TsType> {
+        let module = test_parser(src, Syntax::Typescript(Default::default()), |p| {
+            p.parse_module().map_err(|mut e| e.emit())
+        });
+
+        match module.body.into_iter().next() {
+            Some(ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(alias)))) => alias.type_ann,
+            item => unreachable!("{:?}", item),
+        }
+    }
+
+    fn keyword(kind: TsKeywordTypeKind) -> TsType {
+        TsType::TsKeywordType(TsKeywordType {
+            span: DUMMY_SP,
+            kind,
+        })
+    }
+
+    #[test]
+    fn named_tuple_members() {
+        let actual = parse_type_alias("type T = [start: number, end?: number, ...rest: string[]];");
+
+        let expected = box TsType::TsTupleType(TsTupleType {
+            span: DUMMY_SP,
+            elem_types: vec![
+                TsTupleElement {
+                    span: DUMMY_SP,
+                    label: Some(Pat::Ident(Ident::new("start".into(), DUMMY_SP))),
+                    ty: keyword(TsKeywordTypeKind::TsNumberKeyword),
+                },
+                TsTupleElement {
+                    span: DUMMY_SP,
+                    label: Some(Pat::Ident(Ident {
+                        optional: true,
+                        ..Ident::new("end".into(), DUMMY_SP)
+                    })),
+                    ty: keyword(TsKeywordTypeKind::TsNumberKeyword),
+                },
+                TsTupleElement {
+                    span: DUMMY_SP,
+                    label: Some(Pat::Rest(RestPat {
+                        span: DUMMY_SP,
+                        dot3_token: DUMMY_SP,
+                        arg: box Pat::Ident(Ident::new("rest".into(), DUMMY_SP)),
+                        type_ann: None,
+                    })),
+                    ty: TsType::TsArrayType(TsArrayType {
+                        span: DUMMY_SP,
+                        elem_type: box keyword(TsKeywordTypeKind::TsStringKeyword),
+                    }),
+                },
+            ],
+        });
+
+        assert_eq_ignore_span!(actual, expected);
+    }
+
+    #[test]
+    fn variadic_tuple() {
+        let actual = parse_type_alias("type T = [...A, string, ...B];");
+
+        match *actual {
+            TsType::TsTupleType(TsTupleType { elem_types, .. }) => {
+                assert_eq!(elem_types.len(), 3);
+                assert!(elem_types.iter().all(|elem| elem.label.is_none()));
+                match elem_types[2].ty {
+                    TsType::TsRestType(..) => {}
+                    ref ty => unreachable!("{:?}", ty),
+                }
+            }
+            ty => unreachable!("{:?}", ty),
+        }
+    }
+
+    #[test]
+    fn template_literal_type() {
+        let actual = parse_type_alias("type T = `on${Capitalize<K>}Change${'d' | 's'}`;");
+
+        match *actual {
+            TsType::TsLitType(TsLitType {
+                lit: TsLit::Tpl(tpl),
+                ..
+            }) => {
+                assert_eq!(tpl.types.len(), 2);
+                let quasis = tpl.quasis.iter().map(|q| &*q.raw.value).collect::<Vec<_>>();
+                assert_eq!(quasis, vec!["on", "Change", ""]);
+            }
+            ty => unreachable!("{:?}", ty),
+        }
+    }
+
+    #[test]
+    fn abstract_constructor_type() {
+        let actual = parse_type_alias("type T = abstract new () => object;");
+
+        match *actual {
+            TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsConstructorType(ty)) => {
+                assert!(ty.is_abstract);
+            }
+            ty => unreachable!("{:?}", ty),
+        }
+
+        let actual = parse_type_alias("type T = new () => object;");
+
+        match *actual {
+            TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsConstructorType(ty)) => {
+                assert!(!ty.is_abstract);
+            }
+            ty => unreachable!("{:?}", ty),
+        }
+    }
 }
@@ -1 +1 @@
-type MyType = `text${test}`;
+type MyType = `text${test()}`;
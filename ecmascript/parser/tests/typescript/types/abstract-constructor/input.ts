@@ -0,0 +1,2 @@
+type C = abstract new () => object;
+let c: abstract new (a: string) => C;
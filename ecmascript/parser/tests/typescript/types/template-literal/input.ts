@@ -0,0 +1,2 @@
+type A = `${T}-${U}`;
+type B = `prefix-${string}`;
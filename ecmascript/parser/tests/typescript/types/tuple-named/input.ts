@@ -0,0 +1 @@
+let x: [first: string, second?: number, ...rest: boolean[]];
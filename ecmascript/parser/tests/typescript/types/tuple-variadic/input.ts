@@ -0,0 +1,2 @@
+type T = [...A, ...B, string];
+type U = [string, ...number[]];
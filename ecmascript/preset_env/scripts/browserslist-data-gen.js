@@ -0,0 +1,30 @@
+// Generates `src/browserslist/data.json` from `caniuse-lite` and
+// `browserslist`.
+//
+// Usage: node scripts/browserslist-data-gen.js > src/browserslist/data.json
+const browserslist = require('browserslist');
+const { agents } = require('caniuse-lite');
+const envs = require('node-releases/data/processed/envs.json');
+
+const data = {};
+
+for (const name of Object.keys(agents)) {
+    const agent = agents[name];
+    data[name] = agent.versions
+        .filter(v => !!v)
+        .map(v => [v, agent.usage_global[v] || 0, agent.release_date[v] || null]);
+}
+
+data.node = envs.map(e => [e.version, 0, Math.floor(new Date(e.date).getTime() / 1000)]);
+
+const versions = q => browserslist(q).map(v => v.split(' ')[1]);
+
+const lines = Object.keys(data).map(name => `    ${JSON.stringify(name)}: ${JSON.stringify(data[name])}`);
+
+console.log(`{
+  "agents": {
+${lines.join(',\n')}
+  },
+  "firefoxEsr": ${JSON.stringify(versions('Firefox ESR'))},
+  "maintainedNode": ${JSON.stringify(versions('maintained node versions'))}
+}`);
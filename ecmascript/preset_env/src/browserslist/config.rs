@@ -0,0 +1,378 @@
+//! Loads queries from `.browserslistrc`, `browserslist` and `package.json`,
+//! and resolves shareable configs used by `extends`.
+
+use super::Error;
+use fxhash::FxHashMap;
+use serde::Deserialize;
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use swc_common::{
+    errors::{EmitterWriter, Handler},
+    FileName, FilePathMapping, Globals, SourceMap, GLOBALS,
+};
+use swc_ecma_ast::*;
+use swc_ecma_parser::{lexer::Lexer, Parser, Session, SourceFileInput, Syntax};
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Queries {
+    Single(String),
+    Multiple(Vec<String>),
+    /// Queries for each environment, like `production`.
+    Env(FxHashMap<String, Queries>),
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageJson {
+    #[serde(default)]
+    browserslist: Option<Queries>,
+    #[serde(default)]
+    main: Option<String>,
+}
+
+/// Loads queries for the current environment.
+///
+/// Returns [None] if there's no config.
+pub(super) fn load(path: Option<&Path>) -> Result<Option<Vec<String>>, Error> {
+    if let Ok(queries) = env::var("BROWSERSLIST") {
+        return Ok(Some(vec![queries]));
+    }
+
+    let config = match env::var_os("BROWSERSLIST_CONFIG") {
+        Some(file) => Some(read_config(Path::new(&file))?),
+        None => {
+            let dir = match path {
+                Some(path) if path.is_dir() => path.to_path_buf(),
+                Some(path) => path
+                    .parent()
+                    .map(Path::to_path_buf)
+                    .unwrap_or_else(|| PathBuf::from(".")),
+                None => env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+            };
+
+            find_config(&dir)?
+        }
+    };
+
+    Ok(config.map(select_env))
+}
+
+/// Returns queries of `name`, which is a shareable config like
+/// `browserslist-config-foo`.
+pub(super) fn extends(name: &str, path: Option<&Path>) -> Result<Vec<String>, Error> {
+    let is_valid = name.starts_with("browserslist-config-")
+        || (name.starts_with('@') && name.contains("/browserslist-config"));
+    if !is_valid || name.contains("..") {
+        return Err(Error::UnknownQuery(format!("extends {}", name)));
+    }
+
+    let dir = path
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .or_else(|| env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let pkg_dir = dir
+        .ancestors()
+        .map(|dir| dir.join("node_modules").join(name))
+        .find(|dir| dir.is_dir())
+        .ok_or_else(|| Error::Config {
+            path: dir.clone(),
+            msg: format!("cannot find shareable config `{}`", name),
+        })?;
+
+    let main = match fs::read_to_string(pkg_dir.join("package.json")) {
+        Ok(s) => {
+            let pkg: PackageJson = serde_json::from_str(&s).map_err(|err| Error::Config {
+                path: pkg_dir.join("package.json"),
+                msg: err.to_string(),
+            })?;
+            pkg.main.unwrap_or_else(|| "index.js".into())
+        }
+        Err(_) => "index.js".into(),
+    };
+
+    let mut file = pkg_dir.join(main);
+    if file.is_dir() {
+        file = file.join("index.js");
+    } else if file.extension().is_none() {
+        file.set_extension("js");
+    }
+
+    let queries = if file.extension().map(|ext| ext == "json").unwrap_or(false) {
+        let s = read(&file)?;
+        serde_json::from_str(&s).map_err(|err| Error::Config {
+            path: file.clone(),
+            msg: err.to_string(),
+        })?
+    } else {
+        read_exports(&file)?
+    };
+
+    Ok(select_env(queries))
+}
+
+/// Finds a config in `dir` or its ancestors.
+fn find_config(dir: &Path) -> Result<Option<Queries>, Error> {
+    for dir in dir.ancestors() {
+        let from_pkg = match fs::read_to_string(dir.join("package.json")) {
+            Ok(s) => serde_json::from_str::<PackageJson>(&s)
+                .ok()
+                .and_then(|pkg| pkg.browserslist),
+            Err(_) => None,
+        };
+
+        let file = ["browserslist", ".browserslistrc"]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|file| file.is_file());
+
+        match (from_pkg, file) {
+            (Some(..), Some(file)) => {
+                return Err(Error::Config {
+                    path: file,
+                    msg: "`browserslist` key in package.json and a config file cannot be used at \
+                          the same time"
+                        .into(),
+                })
+            }
+            (Some(queries), None) => return Ok(Some(queries)),
+            (None, Some(file)) => return read_config(&file).map(Some),
+            (None, None) => {}
+        }
+    }
+
+    Ok(None)
+}
+
+fn read(file: &Path) -> Result<String, Error> {
+    fs::read_to_string(file).map_err(|err| Error::Config {
+        path: file.to_path_buf(),
+        msg: err.to_string(),
+    })
+}
+
+/// Parses a `.browserslistrc` file.
+fn read_config(file: &Path) -> Result<Queries, Error> {
+    let s = read(file)?;
+
+    let mut defaults = vec![];
+    let mut envs: FxHashMap<String, Queries> = Default::default();
+    let mut cur: Vec<String> = vec![];
+
+    for line in s.lines() {
+        let line = match line.find('#') {
+            Some(idx) => &line[..idx],
+            None => line,
+        }
+        .trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            let names = &line[1..line.len() - 1];
+            cur = names.split_whitespace().map(String::from).collect();
+            for name in &cur {
+                envs.entry(name.clone())
+                    .or_insert_with(|| Queries::Multiple(vec![]));
+            }
+            continue;
+        }
+
+        if cur.is_empty() {
+            defaults.push(line.to_string());
+        } else {
+            for name in &cur {
+                if let Some(Queries::Multiple(queries)) = envs.get_mut(name) {
+                    queries.push(line.to_string());
+                }
+            }
+        }
+    }
+
+    if envs.is_empty() {
+        return Ok(Queries::Multiple(defaults));
+    }
+    if !defaults.is_empty() {
+        envs.insert("defaults".into(), Queries::Multiple(defaults));
+    }
+    Ok(Queries::Env(envs))
+}
+
+/// Selects queries for `BROWSERSLIST_ENV` or `NODE_ENV`, which defaults to
+/// `production`.
+fn select_env(queries: Queries) -> Vec<String> {
+    match queries {
+        Queries::Single(q) => vec![q],
+        Queries::Multiple(q) => q,
+        Queries::Env(mut envs) => {
+            let env = env::var("BROWSERSLIST_ENV")
+                .or_else(|_| env::var("NODE_ENV"))
+                .unwrap_or_else(|_| "production".into());
+
+            envs.remove(&env)
+                .or_else(|| envs.remove("defaults"))
+                .map(select_env)
+                .unwrap_or_default()
+        }
+    }
+}
+
+/// Reads `module.exports = [...]` of a shareable config.
+fn read_exports(file: &Path) -> Result<Queries, Error> {
+    let err = |msg: &str| Error::Config {
+        path: file.to_path_buf(),
+        msg: msg.into(),
+    };
+
+    let src = read(file)?;
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let fm = cm.new_source_file(FileName::Real(file.to_path_buf()), src);
+
+    let script = GLOBALS.set(&Globals::new(), || {
+        let handler = Handler::with_emitter(
+            false,
+            false,
+            Box::new(EmitterWriter::new(
+                Box::new(io::sink()),
+                Some(cm.clone()),
+                false,
+                false,
+            )),
+        );
+        let session = Session { handler: &handler };
+
+        let lexer = Lexer::new(
+            session,
+            Syntax::default(),
+            Default::default(),
+            SourceFileInput::from(&*fm),
+            None,
+        );
+        Parser::new_from(session, lexer)
+            .parse_script()
+            .map_err(|mut e| {
+                e.cancel();
+            })
+    });
+    let script = script.map_err(|()| err("failed to parse"))?;
+
+    for stmt in script.body.into_iter().rev() {
+        let assign = match stmt {
+            Stmt::Expr(ExprStmt { expr, .. }) => match *expr {
+                Expr::Assign(assign) => assign,
+                _ => continue,
+            },
+            _ => continue,
+        };
+
+        let is_exports = match assign.left {
+            PatOrExpr::Expr(ref e) => is_module_exports(e),
+            PatOrExpr::Pat(ref p) => match **p {
+                Pat::Expr(ref e) => is_module_exports(e),
+                _ => false,
+            },
+        };
+        if !is_exports {
+            continue;
+        }
+
+        return to_queries(&assign.right)
+            .ok_or_else(|| err("`module.exports` should be an array of string literals"));
+    }
+
+    Err(err("cannot find `module.exports`"))
+}
+
+fn is_module_exports(e: &Expr) -> bool {
+    match e {
+        Expr::Member(MemberExpr {
+            obj: ExprOrSuper::Expr(obj),
+            prop,
+            computed: false,
+            ..
+        }) => match (&**obj, &**prop) {
+            (Expr::Ident(obj), Expr::Ident(prop)) => obj.sym == *"module" && prop.sym == *"exports",
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn to_queries(e: &Expr) -> Option<Queries> {
+    match e {
+        Expr::Lit(Lit::Str(s)) => Some(Queries::Single(s.value.to_string())),
+        Expr::Array(arr) => arr
+            .elems
+            .iter()
+            .map(|elem| match elem {
+                Some(ExprOrSpread { spread: None, expr }) => match **expr {
+                    Expr::Lit(Lit::Str(ref s)) => Some(s.value.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect::<Option<_>>()
+            .map(Queries::Multiple),
+        Expr::Object(obj) => obj
+            .props
+            .iter()
+            .map(|prop| match prop {
+                PropOrSpread::Prop(prop) => match **prop {
+                    Prop::KeyValue(KeyValueProp { ref key, ref value }) => {
+                        let key = match key {
+                            PropName::Ident(i) => i.sym.to_string(),
+                            PropName::Str(s) => s.value.to_string(),
+                            _ => return None,
+                        };
+                        Some((key, to_queries(value)?))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect::<Option<_>>()
+            .map(Queries::Env),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn browserslistrc() {
+        let dir = env::temp_dir().join("swc-browserslistrc-test");
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join(".browserslistrc");
+        fs::write(
+            &file,
+            "# comment\nie 11\n\n[production staging]\n> 1% # usage\nlast 2 \
+             versions\n\n[development]\nlast 1 chrome version\n",
+        )
+        .unwrap();
+
+        let queries = match read_config(&file).unwrap() {
+            Queries::Env(mut envs) => {
+                assert_eq!(
+                    select_env(envs.remove("development").unwrap()),
+                    vec!["last 1 chrome version"]
+                );
+                assert_eq!(
+                    select_env(envs.remove("staging").unwrap()),
+                    vec!["> 1%", "last 2 versions"]
+                );
+                envs.remove("defaults").unwrap()
+            }
+            q => panic!("expected queries for each environment, got {:?}", q),
+        };
+        assert_eq!(select_env(queries), vec!["ie 11"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,742 @@
+//! Native implementation of [browserslist](https://github.com/browserslist/browserslist).
+//!
+//! Browser data is read from `data.json`, which should be generated from
+//! `caniuse-lite` and `node-releases` using
+//! `scripts/browserslist-data-gen.js`. The checked-in file is a placeholder
+//! with uniform usage and only major node versions until it is regenerated.
+//!
+//! Versions of mobile browsers selected by name are resolved using data of
+//! desktop browsers, like `mobileToDesktop` of browserslist.
+
+use fxhash::FxHashMap;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+mod config;
+
+#[derive(Debug, Clone)]
+pub enum Error {
+    UnknownQuery(String),
+    UnknownBrowser(String),
+    UnknownVersion {
+        browser: String,
+        version: String,
+    },
+    /// `not` is used by the first query.
+    NotAtFirst(String),
+    /// Failed to load a config file or a shareable config.
+    Config {
+        path: PathBuf,
+        msg: String,
+    },
+    /// A target which cannot be resolved to versions, like `esmodules`.
+    UnsupportedTarget(String),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::UnknownQuery(q) => write!(f, "unknown browser query `{}`", q),
+            Error::UnknownBrowser(b) => write!(f, "unknown browser `{}`", b),
+            Error::UnknownVersion { browser, version } => {
+                write!(f, "unknown version `{}` of `{}`", version, browser)
+            }
+            Error::NotAtFirst(q) => write!(f, "`{}` cannot be the first query", q),
+            Error::Config { path, msg } => write!(f, "{}: {}", path.display(), msg),
+            Error::UnsupportedTarget(t) => write!(f, "unsupported target `{}`", t),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Data {
+    agents: FxHashMap<String, Vec<VersionData>>,
+    firefox_esr: Vec<String>,
+    maintained_node: Vec<String>,
+}
+
+/// `[version, global usage, release date]`
+///
+/// Release date is [None] for unreleased versions.
+#[derive(Debug, Deserialize)]
+struct VersionData(String, f64, Option<u64>);
+
+static DATA: Lazy<Data> = Lazy::new(|| {
+    serde_json::from_str(include_str!("data.json")).expect("failed to parse browserslist data")
+});
+
+/// Mobile browsers resolved using data of desktop browsers, if selected by
+/// name.
+const MOBILE_TO_DESKTOP: &[(&str, &str)] = &[
+    ("and_chr", "chrome"),
+    ("and_ff", "firefox"),
+    ("ie_mob", "ie"),
+    ("op_mob", "opera"),
+    ("android", "chrome"),
+];
+
+/// The first version of android browser which follows versions of chrome.
+const ANDROID_EVERGREEN: f64 = 37.0;
+
+/// Resolves `queries` into a list of `browser version`, like `chrome 80`.
+///
+/// If `queries` is empty, queries are loaded from `.browserslistrc`,
+/// `browserslist` or `package.json` in `path` or its ancestors, and
+/// `defaults` is used if there's no config.
+pub fn resolve<S>(queries: &[S], path: Option<&Path>) -> Result<Vec<String>, Error>
+where
+    S: AsRef<str>,
+{
+    let queries: Vec<&str> = queries
+        .iter()
+        .map(|q| q.as_ref().trim())
+        .filter(|q| !q.is_empty())
+        .collect();
+
+    let queries = if queries.is_empty() {
+        config::load(path)?.unwrap_or_else(|| vec!["defaults".into()])
+    } else {
+        queries.into_iter().map(String::from).collect()
+    };
+
+    let mut result = Resolver { path }.resolve(&queries.join(", "))?;
+
+    result.sort_by(|(n1, v1), (n2, v2)| {
+        n1.cmp(n2).then_with(|| {
+            version_num(v2)
+                .partial_cmp(&version_num(v1))
+                .unwrap_or(Ordering::Equal)
+        })
+    });
+    result.dedup();
+
+    Ok(result
+        .into_iter()
+        .map(|(name, version)| format!("{} {}", name, version))
+        .collect())
+}
+
+type Distribs = Vec<(String, String)>;
+
+struct Resolver<'a> {
+    /// Used to resolve shareable configs.
+    path: Option<&'a Path>,
+}
+
+impl Resolver<'_> {
+    fn resolve(&self, queries: &str) -> Result<Distribs, Error> {
+        let mut result: Distribs = vec![];
+
+        for (i, (and, query)) in split(queries).into_iter().enumerate() {
+            let (not, query) = match strip_word(query, "not") {
+                Some(query) => {
+                    if i == 0 {
+                        return Err(Error::NotAtFirst(query.into()));
+                    }
+                    (true, query)
+                }
+                None => (false, query),
+            };
+
+            let selected = self.select(query)?;
+
+            if and || not {
+                result.retain(|d| selected.contains(d) != not);
+            } else {
+                result.extend(selected);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Selects browsers matching a single query, like `last 2 versions`.
+    fn select(&self, query: &str) -> Result<Distribs, Error> {
+        let lower = query.to_lowercase();
+        let words: Vec<&str> = lower.split_whitespace().collect();
+
+        match &*words {
+            ["defaults"] => self.resolve("> 0.5%, last 2 versions, Firefox ESR, not dead"),
+
+            ["dead"] => self.resolve("ie <= 10, ie_mob <= 11, bb <= 10, op_mob <= 12.1, samsung 4"),
+
+            ["extends", name] => self.resolve(&config::extends(name, self.path)?.join(", ")),
+
+            ["last", n, "versions"] | ["last", n, "version"] => {
+                let n = count(query, n)?;
+                Ok(all_agents(|name| {
+                    released(versions(name))
+                        .into_iter()
+                        .rev()
+                        .take(n)
+                        .map(|v| v.0.clone())
+                        .collect()
+                }))
+            }
+
+            ["last", n, "major", "versions"] | ["last", n, "major", "version"] => {
+                let n = count(query, n)?;
+                Ok(all_agents(|name| last_major_versions(versions(name), n)))
+            }
+
+            ["last", n, name, "versions"] | ["last", n, name, "version"] => {
+                let n = count(query, n)?;
+                let name = agent_name(name)?;
+                Ok(released(named_versions(&name))
+                    .into_iter()
+                    .rev()
+                    .take(n)
+                    .map(|v| (name.clone(), v.0.clone()))
+                    .collect())
+            }
+
+            ["last", n, name, "major", "versions"] | ["last", n, name, "major", "version"] => {
+                let n = count(query, n)?;
+                let name = agent_name(name)?;
+                Ok(last_major_versions(named_versions(&name), n)
+                    .into_iter()
+                    .map(|v| (name.clone(), v))
+                    .collect())
+            }
+
+            ["last", n, "years"] | ["last", n, "year"] => {
+                let years: f64 = n.parse().map_err(|_| Error::UnknownQuery(query.into()))?;
+                let since = now().saturating_sub((years * 365.259641 * 86400.0) as u64);
+                Ok(since_date(since))
+            }
+
+            ["since", date] => {
+                let date = parse_date(date).ok_or_else(|| Error::UnknownQuery(query.into()))?;
+                Ok(since_date(date))
+            }
+
+            ["unreleased", "versions"] => Ok(all_agents(|name| {
+                versions(name)
+                    .into_iter()
+                    .filter(|v| v.2.is_none())
+                    .map(|v| v.0.clone())
+                    .collect()
+            })),
+
+            ["unreleased", name, "versions"] => {
+                let name = agent_name(name)?;
+                Ok(named_versions(&name)
+                    .into_iter()
+                    .filter(|v| v.2.is_none())
+                    .map(|v| (name.clone(), v.0.clone()))
+                    .collect())
+            }
+
+            ["firefox", "esr"] | ["ff", "esr"] | ["fx", "esr"] => Ok(DATA
+                .firefox_esr
+                .iter()
+                .map(|v| ("firefox".into(), v.clone()))
+                .collect()),
+
+            ["maintained", "node", "versions"] => Ok(DATA
+                .maintained_node
+                .iter()
+                .map(|v| ("node".into(), v.clone()))
+                .collect()),
+
+            ["node", version] => {
+                let v = node_version(version).ok_or_else(|| Error::UnknownVersion {
+                    browser: "node".into(),
+                    version: (*version).into(),
+                })?;
+                Ok(vec![("node".into(), v)])
+            }
+
+            _ => self.select_by_op(query, &lower),
+        }
+    }
+
+    /// Handles queries with comparison operators, like `> 1%` or `ie <= 10`,
+    /// and queries for a version, like `chrome 80`.
+    fn select_by_op(&self, query: &str, lower: &str) -> Result<Distribs, Error> {
+        if let Some((op, rest)) = split_op(lower) {
+            if rest.ends_with('%') {
+                let percent: f64 = rest[..rest.len() - 1]
+                    .trim()
+                    .parse()
+                    .map_err(|_| Error::UnknownQuery(query.into()))?;
+
+                let mut result = vec![];
+                for (name, versions) in &DATA.agents {
+                    if name == "node" {
+                        continue;
+                    }
+                    for v in versions {
+                        if op.matches(v.1.partial_cmp(&percent)) {
+                            result.push((name.clone(), v.0.clone()));
+                        }
+                    }
+                }
+                return Ok(result);
+            }
+
+            return Err(Error::UnknownQuery(query.into()));
+        }
+
+        let mut words = lower.splitn(2, ' ');
+        let (name, rest) = match (words.next(), words.next()) {
+            (Some(name), Some(rest)) => (name, rest.trim()),
+            _ => return Err(Error::UnknownQuery(query.into())),
+        };
+        let name = agent_name(name)?;
+
+        // `ie <= 10`
+        if let Some((op, version)) = split_op(rest) {
+            if name == "node" {
+                let version = parse_semver(version);
+                return Ok(versions(&name)
+                    .into_iter()
+                    .filter(|v| op.matches(Some(parse_semver(&v.0).cmp(&version))))
+                    .map(|v| (name.clone(), v.0.clone()))
+                    .collect());
+            }
+
+            let version = version_num(version);
+            return Ok(released(named_versions(&name))
+                .into_iter()
+                .filter(|v| op.matches(version_num(&v.0).partial_cmp(&version)))
+                .map(|v| (name.clone(), v.0.clone()))
+                .collect());
+        }
+
+        // `safari tp`
+        if rest == "tp" {
+            return Ok(vec![(name, "TP".into())]);
+        }
+
+        // `chrome 70-75`
+        if let Some(idx) = rest.find('-') {
+            if find_version(&name, rest).is_none() {
+                let from = version_num(&rest[..idx]);
+                let to = version_num(&rest[idx + 1..]);
+                return Ok(released(named_versions(&name))
+                    .into_iter()
+                    .filter(|v| {
+                        let v = version_num(&v.0);
+                        from <= v && v <= to
+                    })
+                    .map(|v| (name.clone(), v.0.clone()))
+                    .collect());
+            }
+        }
+
+        match find_version(&name, rest) {
+            Some(version) => Ok(vec![(name, version)]),
+            None => Err(Error::UnknownVersion {
+                browser: name,
+                version: rest.into(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Gt,
+    GtEq,
+    Lt,
+    LtEq,
+}
+
+impl Op {
+    fn matches(self, ord: Option<Ordering>) -> bool {
+        match (self, ord) {
+            (Op::Gt, Some(Ordering::Greater)) => true,
+            (Op::GtEq, Some(Ordering::Greater)) | (Op::GtEq, Some(Ordering::Equal)) => true,
+            (Op::Lt, Some(Ordering::Less)) => true,
+            (Op::LtEq, Some(Ordering::Less)) | (Op::LtEq, Some(Ordering::Equal)) => true,
+            _ => false,
+        }
+    }
+}
+
+fn split_op(s: &str) -> Option<(Op, &str)> {
+    let s = s.trim();
+    let (op, len) = if s.starts_with(">=") {
+        (Op::GtEq, 2)
+    } else if s.starts_with("<=") {
+        (Op::LtEq, 2)
+    } else if s.starts_with('>') {
+        (Op::Gt, 1)
+    } else if s.starts_with('<') {
+        (Op::Lt, 1)
+    } else {
+        return None;
+    };
+
+    Some((op, s[len..].trim()))
+}
+
+/// Splits `queries` by `,`, `or` and `and`.
+///
+/// Returns `(is_and, query)`.
+fn split(queries: &str) -> Vec<(bool, &str)> {
+    let mut result = vec![];
+
+    for q in queries.split(',') {
+        for q in split_word(q, "or") {
+            for (i, q) in split_word(q, "and").into_iter().enumerate() {
+                let q = q.trim();
+                if !q.is_empty() {
+                    result.push((i != 0, q));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// Splits `s` by `word`, which is surrounded by whitespaces.
+fn split_word<'a>(s: &'a str, word: &str) -> Vec<&'a str> {
+    let lower = s.to_lowercase();
+    let pat = format!(" {} ", word);
+
+    let mut result = vec![];
+    let mut last = 0;
+    while let Some(idx) = lower[last..].find(&pat) {
+        result.push(&s[last..last + idx]);
+        last += idx + pat.len();
+    }
+    result.push(&s[last..]);
+
+    result
+}
+
+/// Returns `query` without the leading `word`.
+fn strip_word<'a>(query: &'a str, word: &str) -> Option<&'a str> {
+    let mut words = query.splitn(2, char::is_whitespace);
+    match (words.next(), words.next()) {
+        (Some(w), Some(rest)) if w.eq_ignore_ascii_case(word) => Some(rest.trim()),
+        _ => None,
+    }
+}
+
+fn count(query: &str, n: &str) -> Result<usize, Error> {
+    n.parse().map_err(|_| Error::UnknownQuery(query.into()))
+}
+
+/// Normalizes aliases of browser names.
+fn agent_name(name: &str) -> Result<String, Error> {
+    let name = match name {
+        "fx" | "ff" => "firefox",
+        "ios" => "ios_saf",
+        "explorer" => "ie",
+        "blackberry" => "bb",
+        "explorermobile" => "ie_mob",
+        "operamini" => "op_mini",
+        "operamobile" => "op_mob",
+        "chromeandroid" => "and_chr",
+        "firefoxandroid" => "and_ff",
+        "ucandroid" => "and_uc",
+        "qqandroid" => "and_qq",
+        _ => name,
+    };
+
+    if DATA.agents.contains_key(name) {
+        Ok(name.into())
+    } else {
+        Err(Error::UnknownBrowser(name.into()))
+    }
+}
+
+fn desktop_name(name: &str) -> Option<&'static str> {
+    MOBILE_TO_DESKTOP
+        .iter()
+        .find(|(mobile, _)| *mobile == name)
+        .map(|(_, desktop)| *desktop)
+}
+
+/// All versions of a browser, in release order.
+fn versions(name: &str) -> Vec<&'static VersionData> {
+    DATA.agents[name].iter().collect()
+}
+
+/// Versions of a browser selected by name, like `and_chr >= 80`.
+///
+/// Data of mobile browsers only contains the latest version, so versions of
+/// the desktop browser are used instead.
+fn named_versions(name: &str) -> Vec<&'static VersionData> {
+    let desktop = match desktop_name(name) {
+        Some(desktop) => &DATA.agents[desktop],
+        None => return versions(name),
+    };
+
+    if name == "android" {
+        // Versions before chrome 37 are not based on chrome.
+        DATA.agents[name]
+            .iter()
+            .filter(|v| version_num(&v.0) < ANDROID_EVERGREEN)
+            .chain(
+                desktop
+                    .iter()
+                    .filter(|v| version_num(&v.0) >= ANDROID_EVERGREEN),
+            )
+            .collect()
+    } else {
+        desktop.iter().collect()
+    }
+}
+
+fn released(versions: Vec<&'static VersionData>) -> Vec<&'static VersionData> {
+    versions.into_iter().filter(|v| v.2.is_some()).collect()
+}
+
+/// Applies `op` to all browsers except node.
+fn all_agents<F>(mut op: F) -> Distribs
+where
+    F: FnMut(&str) -> Vec<String>,
+{
+    let mut result = vec![];
+    for name in DATA.agents.keys() {
+        if name == "node" {
+            continue;
+        }
+        result.extend(op(name).into_iter().map(|v| (name.clone(), v)));
+    }
+    result
+}
+
+fn last_major_versions(versions: Vec<&'static VersionData>, n: usize) -> Vec<String> {
+    let released = released(versions);
+
+    let mut majors: Vec<u64> = released.iter().map(|v| major(&v.0)).collect();
+    majors.dedup();
+    let min = match majors.iter().rev().take(n).last() {
+        Some(&min) => min,
+        None => return vec![],
+    };
+
+    released
+        .into_iter()
+        .rev()
+        .filter(|v| major(&v.0) >= min)
+        .map(|v| v.0.clone())
+        .collect()
+}
+
+fn since_date(since: u64) -> Distribs {
+    all_agents(|name| {
+        released(versions(name))
+            .into_iter()
+            .filter(|v| v.2.map(|date| date >= since).unwrap_or(false))
+            .map(|v| v.0.clone())
+            .collect()
+    })
+}
+
+/// Finds a version of a browser, handling ranges like `13.4-13.5`.
+fn find_version(name: &str, version: &str) -> Option<String> {
+    let versions = named_versions(name);
+
+    let matches = |v: &str| {
+        v == version
+            || v.split('-').any(|part| {
+                part == version || part.trim_end_matches(".0") == version.trim_end_matches(".0")
+            })
+    };
+
+    versions
+        .iter()
+        .find(|v| v.0 == version)
+        .or_else(|| versions.iter().find(|v| matches(&v.0)))
+        .map(|v| v.0.clone())
+}
+
+/// Finds the latest node version matching `version`, like `10` or `10.4`.
+fn node_version(version: &str) -> Option<String> {
+    let prefix: Vec<&str> = version.split('.').collect();
+
+    versions("node")
+        .into_iter()
+        .rev()
+        .find(|v| v.0.split('.').zip(&prefix).all(|(a, b)| a == *b))
+        .map(|v| v.0.clone())
+}
+
+/// `parseFloat` of javascript.
+fn version_num(v: &str) -> f64 {
+    let mut end = 0;
+    let mut seen_dot = false;
+    for (i, c) in v.char_indices() {
+        match c {
+            '0'..='9' => end = i + 1,
+            '.' if !seen_dot => seen_dot = true,
+            _ => break,
+        }
+    }
+
+    v[..end].parse().unwrap_or(0.0)
+}
+
+fn major(v: &str) -> u64 {
+    version_num(v) as u64
+}
+
+fn parse_semver(v: &str) -> (u64, u64, u64) {
+    let mut parts = v.split('.').map(|p| p.parse().unwrap_or(0));
+    (
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    )
+}
+
+/// Parses `YYYY`, `YYYY-MM` or `YYYY-MM-DD` as a unix timestamp.
+fn parse_date(s: &str) -> Option<u64> {
+    let mut parts = s.split('-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next().map(|m| m.parse().ok()).unwrap_or(Some(1))?;
+    let day: i64 = parts.next().map(|d| d.parse().ok()).unwrap_or(Some(1))?;
+    if month < 1 || 12 < month || day < 1 || 31 < day {
+        return None;
+    }
+
+    // Days from civil, by Howard Hinnant.
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = (if y >= 0 { y } else { y - 399 }) / 400;
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+
+    if days < 0 {
+        return None;
+    }
+    Some(days as u64 * 86400)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(q: &str) -> Vec<String> {
+        resolve(&[q], None).unwrap_or_else(|err| panic!("failed to resolve `{}`: {}", q, err))
+    }
+
+    #[test]
+    fn version() {
+        assert_eq!(query("chrome 80"), vec!["chrome 80"]);
+        assert_eq!(query("ios_saf 13.4"), vec!["ios_saf 13.4-13.5"]);
+        assert_eq!(query("Firefox ESR"), vec!["firefox 68"]);
+    }
+
+    #[test]
+    fn comparison() {
+        assert_eq!(
+            query("ie >= 9"),
+            vec!["ie 11", "ie 10", "ie 9"],
+            "should be sorted by version"
+        );
+        assert_eq!(
+            query("safari 12-13"),
+            vec!["safari 13", "safari 12.1", "safari 12"]
+        );
+    }
+
+    #[test]
+    fn last_versions() {
+        let res = query("last 2 chrome versions");
+        assert_eq!(res, vec!["chrome 83", "chrome 81"]);
+
+        let res = query("last 1 versions");
+        assert!(res.contains(&"and_chr 81".to_string()));
+        assert!(res.contains(&"ie 11".to_string()));
+        assert!(!res.iter().any(|v| v.starts_with("node")));
+    }
+
+    #[test]
+    fn mobile_to_desktop() {
+        assert_eq!(query("and_chr 80"), vec!["and_chr 80"]);
+        assert_eq!(
+            query("last 2 and_chr versions"),
+            vec!["and_chr 83", "and_chr 81"]
+        );
+        assert_eq!(query("android 4.4"), vec!["android 4.4"]);
+    }
+
+    #[test]
+    fn usage() {
+        let res = query("> 5%");
+        assert!(res.contains(&"and_chr 81".to_string()));
+        assert!(!res.contains(&"ie 11".to_string()));
+    }
+
+    #[test]
+    fn not() {
+        let res = query("ie >= 9, not ie 10");
+        assert_eq!(res, vec!["ie 11", "ie 9"]);
+
+        let res = query("ie >= 6 and > 1%");
+        assert_eq!(res, vec!["ie 11"]);
+
+        assert!(resolve(&["not ie 10"], None).is_err());
+    }
+
+    #[test]
+    fn defaults() {
+        let res = query("defaults");
+        assert!(res.contains(&"firefox 68".to_string()));
+        assert!(
+            !res.contains(&"ie 10".to_string()),
+            "dead browsers should be excluded"
+        );
+        assert!(!res.iter().any(|v| v.starts_with("ie_mob")));
+    }
+
+    #[test]
+    fn node() {
+        assert_eq!(query("node 10"), vec!["node 10.0.0"]);
+        assert_eq!(query("node >= 13"), vec!["node 14.0.0", "node 13.0.0"]);
+    }
+
+    /// Checks queries against data generated by
+    /// `scripts/browserslist-data-gen.js`.
+    #[test]
+    #[ignore]
+    fn generated_data() {
+        assert_eq!(query("node 10.4"), vec!["node 10.4.1"]);
+        assert_eq!(query("node 8.0"), vec!["node 8.0.0"]);
+
+        let res = query("> 0.5%");
+        assert!(!res.is_empty());
+        assert!(!res.contains(&"chrome 4".to_string()));
+
+        assert!(query("cover 50%").len() < query("cover 90%").len());
+    }
+
+    #[test]
+    fn since() {
+        let res = query("since 2020-04");
+        assert!(res.contains(&"chrome 83".to_string()));
+        assert!(!res.contains(&"chrome 80".to_string()));
+    }
+
+    #[test]
+    fn date() {
+        assert_eq!(parse_date("1970"), Some(0));
+        assert_eq!(parse_date("2020-04-07"), Some(1586217600));
+    }
+}
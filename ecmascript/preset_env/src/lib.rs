@@ -11,29 +11,31 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use st_map::StaticMap;
 use std::{
-    convert::{TryFrom, TryInto},
-    process::Command,
+    convert::TryFrom,
+    path::{Path, PathBuf},
 };
 use swc_atoms::{js_word, JsWord};
 use swc_common::{chain, Fold, FoldWith, FromVariant, Mark, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_transforms::{
-    compat::{es2015, es2016, es2017, es2018, es3},
+    compat::{es2015, es2016, es2017, es2018, es2021, es3},
     pass::{noop, Optional, Pass},
     util::prepend_stmts,
 };
 
 #[macro_use]
 mod util;
+pub mod browserslist;
 mod corejs2;
 mod corejs3;
 mod regenerator;
 mod transform_data;
 mod version;
 
-pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
+/// Returns an error if `targets` of `c` cannot be resolved.
+pub fn preset_env(global_mark: Mark, c: Config) -> Result<impl Pass, browserslist::Error> {
     let loose = c.loose;
-    let targets: Versions = c.targets.try_into().expect("failed to parse targets");
+    let targets = targets_to_versions(c.targets, c.config_path.as_deref())?;
     let is_any_target = targets.is_any_target();
 
     let (include, included_modules) = FeatureOrModule::split(c.include);
@@ -67,9 +69,28 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
         }};
     }
 
+    // ES2021
+    let pass = add!(
+        pass,
+        LogicalAssignmentOperators,
+        es2021::logical_assignments()
+    );
+
     // ES2018
+    let pass = add!(
+        pass,
+        AsyncGeneratorFunctions,
+        es2018::async_generator_functions()
+    );
     let pass = add!(pass, ObjectRestSpread, es2018::object_rest_spread());
     let pass = add!(pass, OptionalCatchBinding, es2018::optional_catch_binding());
+    let pass = add!(pass, DotAllRegex, es2018::dot_all_regex());
+    let pass = add!(
+        pass,
+        NamedCapturingGroupsRegex,
+        es2018::named_capturing_groups_regex()
+    );
+    let pass = add!(pass, UnicodePropertyRegex, es2018::unicode_property_regex());
 
     // ES2017
     let pass = add!(pass, AsyncToGenerator, es2017::async_to_generator());
@@ -85,7 +106,9 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
         es2015::TemplateLiteral::default(),
         true
     );
+    let pass = add!(pass, NewTarget, es2015::new_target());
     let pass = add!(pass, Classes, es2015::Classes::default());
+    let pass = add!(pass, ObjectSuper, es2015::object_super());
     let pass = add!(
         pass,
         Spread,
@@ -95,6 +118,7 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
     let pass = add!(pass, FunctionName, es2015::function_name());
     let pass = add!(pass, ArrowFunctions, es2015::arrow());
     let pass = add!(pass, DuplicateKeys, es2015::duplicate_keys());
+    let pass = add!(pass, UnicodeRegex, es2015::unicode_regex());
     let pass = add!(pass, StickyRegex, es2015::StickyRegex);
     // TODO:    InstanceOf,
     let pass = add!(pass, TypeOfSymbol, es2015::TypeOfSymbol);
@@ -123,16 +147,10 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
     let pass = add!(pass, Regenerator, es2015::regenerator(global_mark), true);
     let pass = add!(pass, BlockScoping, es2015::block_scoping(), true);
 
+    let pass = add!(pass, BigInt, es2021::literals());
+
     // TODO:
-    //    Literals,
-    //    ObjectSuper,
-    //    DotAllRegex,
-    //    UnicodeRegex,
-    //    NewTarget,
-    //    AsyncGeneratorFunctions,
-    //    UnicodePropertyRegex,
     //    JsonStrings,
-    //    NamedCapturingGroupsRegex,
 
     // ES 3
     let pass = add!(pass, PropertyLiterals, es3::PropertyLiteral);
@@ -149,7 +167,7 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
         println!("Targets: {:?}", targets);
     }
 
-    chain!(
+    Ok(chain!(
         pass,
         Polyfills {
             mode: c.mode,
@@ -164,11 +182,11 @@ pub fn preset_env(global_mark: Mark, c: Config) -> impl Pass {
             includes: included_modules,
             excludes: excluded_modules,
         }
-    )
+    ))
 }
 
 /// A map without allocation.
-#[derive(Debug, Default, Deserialize, Clone, Copy, StaticMap)]
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, StaticMap)]
 #[serde(deny_unknown_fields)]
 pub struct BrowserData<T: Default> {
     #[serde(default)]
@@ -334,7 +352,7 @@ impl Fold<Script> for Polyfills {
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
 pub enum Mode {
     #[serde(rename = "usage")]
     Usage,
@@ -361,32 +379,37 @@ impl BrowserData<Option<Version>> {
             }
         }
 
-        let browsers = lines.map(|v| {
-            let mut v = v.split(' ');
-            (remap(v.next().unwrap()), v.next().unwrap().to_string())
-        });
-
         let mut data: Versions = BrowserData::default();
-        for (browser, version) in browsers {
+        for line in lines {
+            let mut parts = line.split(' ');
+            let (browser, version) = match (parts.next(), parts.next()) {
+                (Some(browser), Some(version)) => (remap(browser), version),
+                _ => return Err(line),
+            };
+
             match &*browser {
                 "and_qq" | "and_uc" | "baidu" | "bb" | "kaios" | "op_mini" => continue,
 
                 _ => {}
             }
 
-            let version = if version.contains("-") {
-                version.split('-').next().unwrap().parse().unwrap()
-            } else {
-                version.parse().unwrap()
-            };
+            // `ios_saf 13.4-13.5`
+            let version: Version = version
+                .split('-')
+                .next()
+                .unwrap()
+                .parse()
+                .map_err(|_| line)?;
+
+            let slot = data
+                .iter_mut()
+                .find(|(k, _)| browser == *k)
+                .map(|(_, v)| v)
+                .ok_or(line)?;
 
             // lowest version
-            if data[&browser].is_none() || data[&browser].unwrap() > version {
-                for (k, v) in data.iter_mut() {
-                    if browser == k {
-                        *v = Some(version);
-                    }
-                }
+            if slot.is_none() || slot.unwrap() > version {
+                *slot = Some(version);
             }
         }
 
@@ -394,7 +417,7 @@ impl BrowserData<Option<Version>> {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Config {
     #[serde(default)]
@@ -434,13 +457,19 @@ pub struct Config {
 
     #[serde(default)]
     pub force_all_transforms: bool,
+
+    /// Path of the file being compiled.
+    ///
+    /// Browserslist config files are searched from the directory of it.
+    #[serde(default)]
+    pub config_path: Option<PathBuf>,
 }
 
 fn default_targets() -> Option<Targets> {
     Some(Targets::Query(Query::Single("".into())))
 }
 
-#[derive(Debug, Clone, Deserialize, FromVariant)]
+#[derive(Debug, Clone, Deserialize, Serialize, FromVariant)]
 #[serde(untagged)]
 pub enum FeatureOrModule {
     Feature(Feature),
@@ -465,7 +494,7 @@ impl FeatureOrModule {
     }
 }
 
-#[derive(Debug, Clone, Deserialize, FromVariant)]
+#[derive(Debug, Clone, Deserialize, Serialize, FromVariant)]
 #[serde(untagged)]
 pub enum Targets {
     Query(Query),
@@ -474,124 +503,188 @@ pub enum Targets {
     HashMap(FxHashMap<String, QueryOrVersion>),
 }
 
-#[derive(Debug, Clone, Copy, Deserialize)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub struct EsModules {
     esmodules: bool,
 }
 
-#[derive(Debug, Clone, Deserialize, FromVariant)]
+#[derive(Debug, Clone, Deserialize, Serialize, FromVariant)]
 #[serde(untagged)]
 pub enum QueryOrVersion {
     Query(Query),
     Version(Version),
 }
 
-#[derive(Debug, Clone, Deserialize, FromVariant, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[derive(
+    Debug, Clone, Deserialize, Serialize, FromVariant, Eq, PartialEq, PartialOrd, Ord, Hash,
+)]
 #[serde(untagged)]
 pub enum Query {
     Single(String),
     Multiple(Vec<String>),
 }
 
-type QueryResult = Result<Versions, ()>;
+type QueryResult = Result<Versions, browserslist::Error>;
 
 impl Query {
-    fn exec(&self) -> QueryResult {
-        fn query<T>(s: &[T]) -> QueryResult
+    /// Returns true if queries are loaded from a browserslist config.
+    fn uses_config(&self) -> bool {
+        match *self {
+            Query::Single(ref s) => s.trim().is_empty(),
+            Query::Multiple(ref s) => s.iter().all(|s| s.trim().is_empty()),
+        }
+    }
+
+    fn exec(&self, path: Option<&Path>) -> QueryResult {
+        fn query<T>(s: &[T], path: Option<&Path>) -> QueryResult
         where
-            T: AsRef<str> + Serialize,
+            T: AsRef<str>,
         {
-            let output = {
-                let output = Command::new("node")
-                    .arg("-e")
-                    .arg(include_str!("query.js"))
-                    .arg(serde_json::to_string(&s).expect("failed to serialize with serde"))
-                    .output()
-                    .expect("failed to collect output");
-                if !output.status.success() {
-                    println!("query.js: Status {:?}", output.status,);
-                    println!(
-                        "{}\n{}",
-                        String::from_utf8_lossy(&output.stdout),
-                        String::from_utf8_lossy(&output.stderr),
-                    );
-                    return Err(());
+            let queries: Vec<&str> = s
+                .iter()
+                .map(|s| s.as_ref())
+                .filter(|s| !s.starts_with("esmodules"))
+                .collect();
+
+            let browsers = browserslist::resolve(&queries, path)?;
+
+            let versions = BrowserData::parse_versions(
+                browsers.iter().map(|s| &**s).filter(|s| !s.contains("TP")),
+            )
+            .map_err(|line| {
+                let mut parts = line.splitn(2, ' ');
+                browserslist::Error::UnknownVersion {
+                    browser: parts.next().unwrap_or_default().into(),
+                    version: parts.next().unwrap_or_default().into(),
                 }
-
-                output.stdout
-            };
-
-            let browsers: Vec<String> =
-                serde_json::from_slice(&output).expect("failed to read browser data output");
-            let versions = BrowserData::parse_versions(browsers.iter().map(|s| &**s))
-                .expect("failed to parse browser version");
+            })?;
 
             Ok(versions)
         }
 
-        static CACHE: Lazy<DashMap<Query, QueryResult>> = Lazy::new(Default::default);
+        static CACHE: Lazy<DashMap<(Query, Option<PathBuf>), QueryResult>> =
+            Lazy::new(Default::default);
+
+        let exec = || match *self {
+            Query::Single(ref s) => query(&[s], path),
+            Query::Multiple(ref s) => query(&s, path),
+        };
 
-        if let Some(v) = CACHE.get(self) {
+        // Configs may be modified while watching files.
+        if self.uses_config() {
+            return exec();
+        }
+
+        let key = (self.clone(), path.map(Path::to_path_buf));
+        if let Some(v) = CACHE.get(&key) {
             return match &*v {
                 Ok(v) => Ok(*v),
-                Err(err) => Err(*err),
+                Err(err) => Err(err.clone()),
             };
         }
 
-        let result = match *self {
-            Query::Single(ref s) => query(&[s]),
-            Query::Multiple(ref s) => query(&s),
-        };
+        let result = exec();
 
-        CACHE.insert(self.clone(), result);
+        CACHE.insert(key, result.clone());
 
         result
     }
 }
 
-impl TryFrom<Option<Targets>> for Versions {
-    type Error = ();
+/// Resolves `targets`.
+///
+/// If `targets` is a query, browserslist configs are searched from `path`.
+pub fn targets_to_versions(
+    v: Option<Targets>,
+    path: Option<&Path>,
+) -> Result<Versions, browserslist::Error> {
+    match v {
+        None => Ok(Default::default()),
+        Some(Targets::Versions(v)) => Ok(v),
+        Some(Targets::Query(q)) => q.exec(path),
+        Some(Targets::HashMap(mut map)) => {
+            let mut versions = match map.remove("browsers") {
+                Some(QueryOrVersion::Query(q)) => q.exec(path)?,
+                Some(QueryOrVersion::Version(v)) => {
+                    return Err(browserslist::Error::UnknownQuery(format!(
+                        "{}.{}.{}",
+                        v.major, v.minor, v.patch
+                    )))
+                }
+                None => Default::default(),
+            };
 
-    fn try_from(v: Option<Targets>) -> Result<Self, Self::Error> {
-        match v {
-            None => Ok(Default::default()),
-            Some(Targets::Versions(v)) => Ok(v),
-            Some(Targets::Query(q)) => q.exec(),
-            Some(Targets::HashMap(mut map)) => {
-                let q = map.remove("browsers").map(|q| match q {
-                    QueryOrVersion::Query(q) => q.exec().expect("failed to run query"),
-                    _ => unreachable!(),
-                });
-
-                let node = map.remove("node").map(|q| match q {
+            // Versions of each browser, like `{ "chrome": "58", "node": 10 }`.
+            for (browser, v) in map {
+                let version = match v {
                     QueryOrVersion::Version(v) => v,
-                    QueryOrVersion::Query(..) => unreachable!(),
-                });
-
-                if map.is_empty() {
-                    if let Some(mut q) = q {
-                        q.node = node;
-                        return Ok(q);
+                    QueryOrVersion::Query(Query::Single(ref s)) => {
+                        s.parse().map_err(|_| browserslist::Error::UnknownVersion {
+                            browser: browser.clone(),
+                            version: s.clone(),
+                        })?
                     }
-                }
+                    QueryOrVersion::Query(Query::Multiple(s)) => {
+                        return Err(browserslist::Error::UnknownVersion {
+                            browser,
+                            version: s.join(", "),
+                        })
+                    }
+                };
 
-                unimplemented!("Targets: {:?}", map)
+                match versions.iter_mut().find(|(k, _)| browser == *k) {
+                    Some((_, v)) => *v = Some(version),
+                    None => return Err(browserslist::Error::UnknownBrowser(browser)),
+                }
             }
-            _ => unimplemented!("Option<Targets>: {:?}", v),
+
+            Ok(versions)
+        }
+        Some(Targets::EsModules(..)) => {
+            Err(browserslist::Error::UnsupportedTarget("esmodules".into()))
         }
     }
 }
 
+impl TryFrom<Option<Targets>> for Versions {
+    type Error = browserslist::Error;
+
+    fn try_from(v: Option<Targets>) -> Result<Self, Self::Error> {
+        targets_to_versions(v, None)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Query;
+    use super::*;
 
     #[test]
     fn test_empty() {
-        let res = Query::Single("".into()).exec().unwrap();
+        let res = Query::Single("".into()).exec(None).unwrap();
         assert!(
             !res.is_any_target(),
             "empty query should return non-empty result"
         );
     }
+
+    #[test]
+    fn targets_map() {
+        let targets: Targets = serde_json::from_str(r#"{ "chrome": "58", "node": 10 }"#).unwrap();
+        let res = targets_to_versions(Some(targets), None).unwrap();
+        assert_eq!(res.chrome, Some("58".parse().unwrap()));
+        assert_eq!(res.node, Some("10".parse().unwrap()));
+        assert_eq!(res.ie, None);
+    }
+
+    #[test]
+    fn invalid_targets() {
+        let targets: Targets = serde_json::from_str(r#"{ "chrome": "latest" }"#).unwrap();
+        assert!(targets_to_versions(Some(targets), None).is_err());
+
+        let targets: Targets = serde_json::from_str(r#"{ "netscape": "4" }"#).unwrap();
+        assert!(targets_to_versions(Some(targets), None).is_err());
+
+        let targets = Targets::Query(Query::Single("chrome 9999".into()));
+        assert!(targets_to_versions(Some(targets), None).is_err());
+    }
 }
@@ -97,6 +97,12 @@ pub enum Feature {
     /// `proposal-optional-catch-binding`
     OptionalCatchBinding,
 
+    /// `proposal-logical-assignment-operators`
+    LogicalAssignmentOperators,
+
+    /// `transform-bigint`
+    BigInt,
+
     /// `transform-named-capturing-groups-regex`
     NamedCapturingGroupsRegex,
 
@@ -151,4 +157,29 @@ mod tests {
             true
         ));
     }
+
+    #[test]
+    fn bigint() {
+        assert!(Feature::BigInt.should_enable(
+            BrowserData {
+                safari: Some("13.0.0".parse().unwrap()),
+                ..Default::default()
+            },
+            false
+        ));
+        assert!(Feature::BigInt.should_enable(
+            BrowserData {
+                chrome: Some("60.0.0".parse().unwrap()),
+                ..Default::default()
+            },
+            false
+        ));
+        assert!(!Feature::BigInt.should_enable(
+            BrowserData {
+                chrome: Some("67.0.0".parse().unwrap()),
+                ..Default::default()
+            },
+            true
+        ));
+    }
 }
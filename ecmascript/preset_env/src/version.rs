@@ -1,5 +1,5 @@
 use crate::Versions;
-use serde::{de, de::Visitor, Deserialize, Deserializer};
+use serde::{de, de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
 use std::{cmp, cmp::Ordering, fmt, hash, str::FromStr};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,9 +15,7 @@ impl FromStr for Version {
     fn from_str(v: &str) -> Result<Self, Self::Err> {
         if !v.contains(".") {
             return Ok(Version {
-                major: v
-                    .parse()
-                    .unwrap_or_else(|err| panic!("failed to parse `{}` as a version: {}", v, err)),
+                major: v.parse().map_err(|_| ())?,
                 minor: 0,
                 patch: 0,
             });
@@ -26,15 +24,13 @@ impl FromStr for Version {
         if v.split(".").count() == 2 {
             let mut s = v.split(".");
             return Ok(Version {
-                major: s.next().unwrap().parse().unwrap(),
-                minor: s.next().unwrap().parse().unwrap(),
+                major: s.next().unwrap().parse().map_err(|_| ())?,
+                minor: s.next().unwrap().parse().map_err(|_| ())?,
                 patch: 0,
             });
         }
 
-        let v = v
-            .parse::<semver::Version>()
-            .unwrap_or_else(|err| panic!("failed to parse {} as semver: {}", v, err));
+        let v = v.parse::<semver::Version>().map_err(|_| ())?;
 
         Ok(Version {
             major: v.major as _,
@@ -79,6 +75,18 @@ impl hash::Hash for Version {
     }
 }
 
+impl Serialize for Version {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&format_args!(
+            "{}.{}.{}",
+            self.major, self.minor, self.patch
+        ))
+    }
+}
+
 struct SerdeVisitor;
 
 impl<'de> Visitor<'de> for SerdeVisitor {
@@ -197,15 +197,21 @@ fn exec(c: PresetConfig, dir: PathBuf) -> Result<(), Error> {
                     force_all_transforms: c.force_all_transforms,
                     shipped_proposals: c.shipped_proposals,
                     targets: c.targets,
+                    config_path: if c.config_path.is_empty() {
+                        None
+                    } else {
+                        Some(dir.join(&c.config_path))
+                    },
                 },
-            );
+            )
+            .expect("failed to resolve targets");
 
             let print = |m: &Module| {
                 let mut buf = vec![];
                 {
                     let handlers = box MyHandlers;
                     let mut emitter = Emitter {
-                        cfg: swc_ecma_codegen::Config { minify: false },
+                        cfg: Default::default(),
                         comments: None,
                         cm: cm.clone(),
                         wr: box swc_ecma_codegen::text_writer::JsWriter::new(
@@ -2,9 +2,10 @@ pub use self::{
     arrow::arrow, block_scoped_fn::BlockScopedFns, block_scoping::block_scoping, classes::Classes,
     computed_props::computed_properties, destructuring::destructuring,
     duplicate_keys::duplicate_keys, for_of::for_of, function_name::function_name,
-    instanceof::InstanceOf, parameters::parameters, regenerator::regenerator,
-    shorthand_property::Shorthand, spread::spread, sticky_regex::StickyRegex,
-    template_literal::TemplateLiteral, typeof_symbol::TypeOfSymbol,
+    instanceof::InstanceOf, new_target::new_target, object_super::object_super,
+    parameters::parameters, regenerator::regenerator, shorthand_property::Shorthand,
+    spread::spread, sticky_regex::StickyRegex, template_literal::TemplateLiteral,
+    typeof_symbol::TypeOfSymbol, unicode_regex::unicode_regex,
 };
 use crate::pass::Pass;
 use serde::Deserialize;
@@ -21,6 +22,8 @@ mod duplicate_keys;
 pub mod for_of;
 mod function_name;
 mod instanceof;
+mod new_target;
+mod object_super;
 mod parameters;
 mod regenerator;
 mod shorthand_property;
@@ -28,12 +31,14 @@ pub mod spread;
 mod sticky_regex;
 mod template_literal;
 mod typeof_symbol;
+mod unicode_regex;
 
 fn exprs() -> impl Pass {
     chain_at!(
         Expr,
         arrow(),
         duplicate_keys(),
+        unicode_regex(),
         StickyRegex,
         InstanceOf,
         TypeOfSymbol,
@@ -46,7 +51,9 @@ pub fn es2015(global_mark: Mark, c: Config) -> impl Pass {
     chain!(
         BlockScopedFns,
         TemplateLiteral::default(),
+        new_target(),
         Classes::default(),
+        object_super(),
         spread(c.spread),
         function_name(),
         exprs(),
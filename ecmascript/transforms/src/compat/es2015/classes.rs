@@ -382,6 +382,9 @@ impl Classes {
                 ClassMember::PrivateProp(..) => unreachable!(
                     "classes pass: private property\nclass_properties pass should remove this"
                 ),
+                ClassMember::StaticBlock(..) => unreachable!(
+                    "classes pass: static block\nclass_properties pass should remove this"
+                ),
                 ClassMember::TsIndexSignature(..) => {
                     // We just strip this.
                 }
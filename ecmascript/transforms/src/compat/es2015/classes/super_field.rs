@@ -274,8 +274,14 @@ impl<'a> SuperCalleeFolder<'a> {
             }
         }
 
-        let proto_arg =
-            get_prototype_of(self.class_name.clone().member(quote_ident!("prototype"))).as_arg();
+        let proto_arg = get_prototype_of(if self.is_static {
+            // Foo
+            Expr::Ident(self.class_name.clone())
+        } else {
+            // Foo.prototype
+            self.class_name.clone().member(quote_ident!("prototype"))
+        })
+        .as_arg();
 
         let prop_arg = match *prop {
             Expr::Ident(Ident {
@@ -339,6 +345,10 @@ impl<'a> SuperCalleeFolder<'a> {
                         op!("&=") => op!("&"),
                         op!("^=") => op!("^"),
                         op!("**=") => op!("**"),
+                        // Lowered by `compat::es2021` before this pass.
+                        op!("&&=") => op!("&&"),
+                        op!("||=") => op!("||"),
+                        op!("??=") => op!("??"),
                     },
                     right: rhs,
                 }
@@ -0,0 +1,278 @@
+use crate::{pass::Pass, util::undefined};
+use std::mem::replace;
+use swc_common::{Fold, FoldWith, Visit, VisitWith, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_utils::quote_ident;
+
+/// `@babel/plugin-transform-new-target`
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// function Foo() {
+///     console.log(new.target);
+/// }
+///
+/// class Bar {
+///     constructor() {
+///         console.log(new.target);
+///     }
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function Foo() {
+///     console.log(this instanceof Foo ? this.constructor : void 0);
+/// }
+///
+/// class Bar {
+///     constructor() {
+///         console.log(this.constructor);
+///     }
+/// }
+/// ```
+pub fn new_target() -> impl Pass {
+    NewTarget {
+        target: Target::None,
+    }
+}
+
+struct NewTarget {
+    target: Target,
+}
+
+/// What `new.target` refers to.
+enum Target {
+    /// Function called with `new` or not.
+    Fn(Ident),
+    /// Class constructor, which is always called with `new`.
+    Constructor,
+    /// Methods, accessors and the top level.
+    None,
+}
+
+noop_fold_type!(NewTarget);
+
+impl NewTarget {
+    fn fold_with_target<T>(&mut self, target: Target, node: T) -> T
+    where
+        T: FoldWith<Self>,
+    {
+        let old = replace(&mut self.target, target);
+        let node = node.fold_children(self);
+        self.target = old;
+        node
+    }
+}
+
+impl Fold<FnDecl> for NewTarget {
+    fn fold(&mut self, f: FnDecl) -> FnDecl {
+        self.fold_with_target(Target::Fn(f.ident.clone()), f)
+    }
+}
+
+impl Fold<FnExpr> for NewTarget {
+    fn fold(&mut self, mut f: FnExpr) -> FnExpr {
+        if f.ident.is_none() && uses_new_target(&f.function) {
+            f.ident = Some(private_ident!("_target"));
+        }
+
+        match f.ident.clone() {
+            Some(ident) => self.fold_with_target(Target::Fn(ident), f),
+            None => self.fold_with_target(Target::None, f),
+        }
+    }
+}
+
+impl Fold<Constructor> for NewTarget {
+    fn fold(&mut self, c: Constructor) -> Constructor {
+        self.fold_with_target(Target::Constructor, c)
+    }
+}
+
+macro_rules! method {
+    ($T:tt) => {
+        impl Fold<$T> for NewTarget {
+            fn fold(&mut self, m: $T) -> $T {
+                self.fold_with_target(Target::None, m)
+            }
+        }
+    };
+}
+
+method!(ClassMethod);
+method!(PrivateMethod);
+method!(MethodProp);
+method!(GetterProp);
+method!(SetterProp);
+
+impl Fold<Expr> for NewTarget {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::MetaProp(MetaPropExpr { meta, prop })
+                if meta.sym == *"new" && prop.sym == *"target" =>
+            {
+                let span = meta.span.with_hi(prop.span.hi());
+                let this = || box Expr::This(ThisExpr { span });
+                let constructor = || {
+                    box Expr::Member(MemberExpr {
+                        span,
+                        obj: ExprOrSuper::Expr(this()),
+                        prop: box Expr::Ident(quote_ident!("constructor")),
+                        computed: false,
+                    })
+                };
+
+                match self.target {
+                    // this instanceof Foo ? this.constructor : void 0
+                    Target::Fn(ref ident) => Expr::Cond(CondExpr {
+                        span,
+                        test: box Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: this(),
+                            op: op!("instanceof"),
+                            right: box Expr::Ident(ident.clone()),
+                        }),
+                        cons: constructor(),
+                        alt: undefined(DUMMY_SP),
+                    }),
+                    Target::Constructor => *constructor(),
+                    Target::None => *undefined(span),
+                }
+            }
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+/// Returns true if `new.target` of `f` is used.
+fn uses_new_target(f: &Function) -> bool {
+    struct Finder {
+        found: bool,
+    }
+
+    impl Visit<MetaPropExpr> for Finder {
+        fn visit(&mut self, e: &MetaPropExpr) {
+            if e.meta.sym == *"new" && e.prop.sym == *"target" {
+                self.found = true;
+            }
+        }
+    }
+
+    /// Nested functions have their own `new.target`.
+    impl Visit<Function> for Finder {
+        fn visit(&mut self, _: &Function) {}
+    }
+
+    impl Visit<Constructor> for Finder {
+        fn visit(&mut self, _: &Constructor) {}
+    }
+
+    let mut v = Finder { found: false };
+    f.params.visit_with(&mut v);
+    f.body.visit_with(&mut v);
+    v.found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        fn_decl,
+        "function Foo() {
+    const a = () => new.target;
+    return new.target;
+}",
+        "function Foo() {
+    const a = () => this instanceof Foo ? this.constructor : void 0;
+    return this instanceof Foo ? this.constructor : void 0;
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        fn_expr,
+        "var Foo = function () {
+    return new.target;
+};
+var Bar = function () {
+    return 1;
+};",
+        "var Foo = function _target() {
+    return this instanceof _target ? this.constructor : void 0;
+};
+var Bar = function () {
+    return 1;
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        class,
+        "class Foo {
+    constructor() {
+        this.a = new.target;
+    }
+    method() {
+        return new.target;
+    }
+}",
+        "class Foo {
+    constructor() {
+        this.a = this.constructor;
+    }
+    method() {
+        return void 0;
+    }
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        object_method,
+        "function Foo() {
+    return {
+        method() {
+            return new.target;
+        }
+    };
+}",
+        "function Foo() {
+    return {
+        method() {
+            return void 0;
+        }
+    };
+}"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| new_target(),
+        exec,
+        "function Foo() {
+    return new.target;
+}
+class Bar {
+    constructor() {
+        this.target = new.target;
+    }
+}
+class Baz extends Bar {}
+
+expect(new Foo()).toBe(Foo);
+expect(Foo()).toBeUndefined();
+expect(new Bar().target).toBe(Bar);
+expect(new Baz().target).toBe(Baz);"
+    );
+}
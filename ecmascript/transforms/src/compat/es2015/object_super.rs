@@ -0,0 +1,468 @@
+use super::classes::SuperFieldAccessFolder;
+use crate::{
+    pass::Pass,
+    util::{prepend, ExprFactory, StmtLike},
+};
+use std::mem::replace;
+use swc_common::{Fold, FoldWith, Mark, Visit, VisitWith, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_utils::quote_ident;
+
+/// `@babel/plugin-transform-object-super`
+///
+/// This pass should be applied after classes are compiled, because `super`
+/// in class methods is not touched by this pass.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// const obj = {
+///     foo() {
+///         return super.foo();
+///     }
+/// };
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var _obj;
+/// const obj = _obj = {
+///     foo() {
+///         return _get(_getPrototypeOf(_obj), "foo", this).call(this);
+///     }
+/// };
+/// ```
+///
+/// `_obj` is declared in the function which creates the object. Like
+/// `logical_assignments`, an arrow function with an expression body is
+/// converted to use a block body, and default values and class fields are
+/// wrapped in an arrow function.
+pub fn object_super() -> impl Pass {
+    ObjectSuper::default()
+}
+
+#[derive(Default)]
+struct ObjectSuper {
+    vars: Vec<VarDeclarator>,
+}
+
+noop_fold_type!(ObjectSuper);
+
+impl<T> Fold<Vec<T>> for ObjectSuper
+where
+    T: FoldWith<Self> + StmtLike,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(stmts.len() + 2);
+
+        for stmt in stmts {
+            let stmt = stmt.fold_with(self);
+
+            if !self.vars.is_empty() {
+                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls: replace(&mut self.vars, Default::default()),
+                    declare: false,
+                }))));
+            }
+
+            buf.push(stmt);
+        }
+
+        buf
+    }
+}
+
+impl Fold<ArrowExpr> for ObjectSuper {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let outer = replace(&mut self.vars, Default::default());
+
+        let params = f.params.fold_with(self);
+        let body = match f.body {
+            BlockStmtOrExpr::Expr(expr) => {
+                let expr = expr.fold_with(self);
+                let vars = replace(&mut self.vars, Default::default());
+
+                if vars.is_empty() {
+                    BlockStmtOrExpr::Expr(expr)
+                } else {
+                    BlockStmtOrExpr::BlockStmt(declare_and_return(vars, expr))
+                }
+            }
+            body => body.fold_with(self),
+        };
+
+        self.vars = outer;
+
+        ArrowExpr { params, body, ..f }
+    }
+}
+
+impl Fold<AssignPat> for ObjectSuper {
+    fn fold(&mut self, p: AssignPat) -> AssignPat {
+        AssignPat {
+            left: p.left.fold_with(self),
+            right: self.fold_isolated(p.right),
+            ..p
+        }
+    }
+}
+
+impl Fold<AssignPatProp> for ObjectSuper {
+    fn fold(&mut self, p: AssignPatProp) -> AssignPatProp {
+        AssignPatProp {
+            value: p.value.map(|v| self.fold_isolated(v)),
+            ..p
+        }
+    }
+}
+
+impl Fold<ClassProp> for ObjectSuper {
+    fn fold(&mut self, mut p: ClassProp) -> ClassProp {
+        let value = p.value.take().map(|v| self.fold_isolated(v));
+
+        ClassProp {
+            value,
+            ..p.fold_children(self)
+        }
+    }
+}
+
+impl Fold<PrivateProp> for ObjectSuper {
+    fn fold(&mut self, mut p: PrivateProp) -> PrivateProp {
+        let value = p.value.take().map(|v| self.fold_isolated(v));
+
+        PrivateProp {
+            value,
+            ..p.fold_children(self)
+        }
+    }
+}
+
+impl ObjectSuper {
+    /// Folds an expression which is not evaluated with the enclosing
+    /// statement, and wraps it in an arrow function if `_obj` is required.
+    fn fold_isolated(&mut self, e: Box<Expr>) -> Box<Expr> {
+        let outer = replace(&mut self.vars, Default::default());
+        let e = e.fold_with(self);
+        let vars = replace(&mut self.vars, outer);
+
+        if vars.is_empty() {
+            return e;
+        }
+
+        // (() => { var _obj; return e; })()
+        box Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: ArrowExpr {
+                span: DUMMY_SP,
+                params: vec![],
+                body: BlockStmtOrExpr::BlockStmt(declare_and_return(vars, e)),
+                is_async: false,
+                is_generator: false,
+                type_params: Default::default(),
+                return_type: Default::default(),
+            }
+            .as_callee(),
+            args: vec![],
+            type_args: Default::default(),
+        })
+    }
+}
+
+impl Fold<Expr> for ObjectSuper {
+    fn fold(&mut self, e: Expr) -> Expr {
+        // Nested objects are handled first, so `super` found below belongs to
+        // this object.
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Object(obj) if uses_super(&obj) => {
+                let span = obj.span;
+                let obj_ident = private_ident!("_obj");
+                self.vars.push(VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(obj_ident.clone()),
+                    init: None,
+                    definite: false,
+                });
+
+                let props = obj
+                    .props
+                    .into_iter()
+                    .map(|prop| match prop {
+                        PropOrSpread::Prop(box Prop::Method(mut m)) => {
+                            let mut function = m.function;
+                            function.body = fold_method_body(&obj_ident, function.body);
+                            m.function = function;
+                            PropOrSpread::Prop(box Prop::Method(m))
+                        }
+                        PropOrSpread::Prop(box Prop::Getter(mut g)) => {
+                            g.body = fold_method_body(&obj_ident, g.body);
+                            PropOrSpread::Prop(box Prop::Getter(g))
+                        }
+                        PropOrSpread::Prop(box Prop::Setter(mut s)) => {
+                            s.body = fold_method_body(&obj_ident, s.body);
+                            PropOrSpread::Prop(box Prop::Setter(s))
+                        }
+                        _ => prop,
+                    })
+                    .collect();
+
+                Expr::Assign(AssignExpr {
+                    span,
+                    op: op!("="),
+                    left: PatOrExpr::Pat(box Pat::Ident(obj_ident)),
+                    right: box Expr::Object(ObjectLit {
+                        span: DUMMY_SP,
+                        props,
+                    }),
+                })
+            }
+            _ => e,
+        }
+    }
+}
+
+/// Replaces `super` in the body of a method with the prototype of `obj_ident`.
+fn fold_method_body(obj_ident: &Ident, body: Option<BlockStmt>) -> Option<BlockStmt> {
+    let mut vars = vec![];
+    let mut folder = SuperFieldAccessFolder {
+        class_name: obj_ident,
+        vars: &mut vars,
+        constructor_this_mark: None,
+        is_static: true,
+        folding_constructor: false,
+        in_nested_scope: false,
+        in_injected_define_property_call: false,
+        this_alias_mark: None,
+    };
+    let mut body = body.fold_with(&mut folder);
+
+    if let Some(mark) = folder.this_alias_mark {
+        prepend(&mut body.as_mut().unwrap().stmts, this_alias(mark));
+    }
+
+    if !vars.is_empty() {
+        prepend(
+            &mut body.as_mut().unwrap().stmts,
+            Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: vars,
+            })),
+        );
+    }
+
+    body
+}
+
+/// `{ var vars; return arg; }`
+fn declare_and_return(vars: Vec<VarDeclarator>, arg: Box<Expr>) -> BlockStmt {
+    BlockStmt {
+        span: DUMMY_SP,
+        stmts: vec![
+            Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls: vars,
+                declare: false,
+            })),
+            Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(arg),
+            }),
+        ],
+    }
+}
+
+fn this_alias(mark: Mark) -> Stmt {
+    Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        declare: false,
+        kind: VarDeclKind::Var,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Ident(quote_ident!(DUMMY_SP.apply_mark(mark), "_this")),
+            init: Some(box Expr::This(ThisExpr { span: DUMMY_SP })),
+            definite: false,
+        }],
+    }))
+}
+
+/// Returns true if a method of `obj` uses `super`.
+fn uses_super(obj: &ObjectLit) -> bool {
+    struct SuperFinder {
+        found: bool,
+    }
+
+    impl Visit<Super> for SuperFinder {
+        fn visit(&mut self, _: &Super) {
+            self.found = true;
+        }
+    }
+
+    obj.props.iter().any(|prop| {
+        let mut v = SuperFinder { found: false };
+        match prop {
+            PropOrSpread::Prop(box Prop::Method(m)) => m.function.body.visit_with(&mut v),
+            PropOrSpread::Prop(box Prop::Getter(g)) => g.body.visit_with(&mut v),
+            PropOrSpread::Prop(box Prop::Setter(s)) => s.body.visit_with(&mut v),
+            _ => {}
+        }
+        v.found
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        get,
+        "const obj = {
+    foo() {
+        return super.foo();
+    },
+    get bar() {
+        return super.bar;
+    }
+};",
+        "var _obj;
+const obj = _obj = {
+    foo() {
+        return _get(_getPrototypeOf(_obj), 'foo', this).call(this);
+    },
+    get bar() {
+        return _get(_getPrototypeOf(_obj), 'bar', this);
+    }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        set,
+        "const obj = {
+    set foo(v) {
+        super.foo = v;
+    }
+};",
+        "var _obj;
+const obj = _obj = {
+    set foo(v) {
+        _set(_getPrototypeOf(_obj), 'foo', v, this, true);
+    }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        no_super,
+        "const obj = {
+    foo() {
+        return 1;
+    }
+};",
+        "const obj = {
+    foo() {
+        return 1;
+    }
+};"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        nested,
+        "function f() {
+    return {
+        foo() {
+            return {
+                bar() {
+                    return super.bar();
+                }
+            };
+        }
+    };
+}",
+        "function f() {
+    return {
+        foo() {
+            var _obj;
+            return _obj = {
+                bar() {
+                    return _get(_getPrototypeOf(_obj), 'bar', this).call(this);
+                }
+            };
+        }
+    };
+}"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        arrow_body,
+        "const make = p => ({
+    __proto__: p,
+    foo() {
+        return super.foo();
+    }
+});",
+        "const make = p => {
+    var _obj;
+    return _obj = {
+        __proto__: p,
+        foo() {
+            return _get(_getPrototypeOf(_obj), 'foo', this).call(this);
+        }
+    };
+};"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        exec_arrow_instances,
+        "const make = (p) => ({
+    __proto__: p,
+    foo() {
+        return super.foo() + '!';
+    }
+});
+const a = make({ foo: () => 'a' });
+const b = make({ foo: () => 'b' });
+
+expect(a.foo()).toBe('a!');
+expect(b.foo()).toBe('b!');"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| object_super(),
+        exec,
+        "const base = {
+    foo() {
+        return 'base ' + this.name;
+    }
+};
+const obj = {
+    name: 'obj',
+    foo() {
+        return super.foo() + '!';
+    }
+};
+Object.setPrototypeOf(obj, base);
+
+expect(obj.foo()).toBe('base obj!');"
+    );
+}
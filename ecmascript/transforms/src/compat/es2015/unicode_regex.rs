@@ -0,0 +1,182 @@
+use crate::{
+    compat::regex::{self, Atom, Class, ClassEscape, ClassItem, CodePointSet, Disjunction, Term},
+    pass::Pass,
+};
+use swc_common::{Fold, FoldWith};
+use swc_ecma_ast::*;
+
+/// Compile the `u` flag of regex literals.
+///
+/// Code points above U+FFFF are matched as surrogate pairs, and property
+/// escapes are expanded.
+///
+/// Case-insensitive matching (the `i` flag) of the output uses the case
+/// folding of non-unicode mode, which differs for a few code points.
+///
+///# Example
+///## In
+///
+/// ```js
+/// /\u{1F4A9}./u;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// /\uD83D\uDCA9(?:[\x00-\t\v-\f\x0E-\u2027\u202A-\uD7FF\uE000-\uFFFF]|[\uD800-\uDBFF][\uDC00-\uDFFF]|[\uD800-\uDFFF])/;
+/// ```
+pub fn unicode_regex() -> impl Pass {
+    UnicodeRegex
+}
+
+#[derive(Clone, Copy)]
+struct UnicodeRegex;
+
+noop_fold_type!(UnicodeRegex);
+
+impl Fold<Expr> for UnicodeRegex {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Lit(Lit::Regex(r)) if r.flags.contains('u') => {
+                let mut pattern = match regex::parse(&r.exp, true) {
+                    Ok(pattern) => pattern,
+                    Err(..) => return Expr::Lit(Lit::Regex(r)),
+                };
+
+                if lower(&mut pattern, r.flags.contains('s')).is_none() {
+                    return Expr::Lit(Lit::Regex(r));
+                }
+
+                Expr::Lit(Lit::Regex(Regex {
+                    exp: regex::print(&pattern, false).into(),
+                    flags: regex::remove_flag(&r.flags, 'u'),
+                    ..r
+                }))
+            }
+            _ => e,
+        }
+    }
+}
+
+/// Returns [None] if the pattern uses an unknown property.
+fn lower(d: &mut Disjunction, dot_all: bool) -> Option<()> {
+    for term in d.alternatives.iter_mut().flatten() {
+        let atom = match term {
+            Term::Atom(atom, _) => atom,
+            Term::Assertion(..) => continue,
+        };
+
+        match atom {
+            Atom::Dot => {
+                let set = if dot_all {
+                    CodePointSet::from_ranges(vec![(0, regex::MAX_CODE_POINT)])
+                } else {
+                    regex::dot()
+                };
+                *atom = regex::surrogate_class(&set);
+            }
+            Atom::Escape(e) if !is_bmp_escape(e) => {
+                *atom = regex::surrogate_class(&regex::escape_set(e)?);
+            }
+            Atom::Class(class) if needs_lowering(class) => {
+                *atom = regex::surrogate_class(&class_set(class)?);
+            }
+            Atom::Group(group) => lower(&mut group.body, dot_all)?,
+            // Astral code points are printed as surrogate pairs.
+            _ => {}
+        }
+    }
+
+    Some(())
+}
+
+/// Returns true if `e` matches the same code units without the `u` flag.
+fn is_bmp_escape(e: &ClassEscape) -> bool {
+    match *e {
+        ClassEscape::Digit { negated }
+        | ClassEscape::Space { negated }
+        | ClassEscape::Word { negated } => !negated,
+        ClassEscape::Property { .. } => false,
+    }
+}
+
+fn needs_lowering(class: &Class) -> bool {
+    class.negated
+        || class.items.iter().any(|item| match *item {
+            ClassItem::Char(c) => c > 0xffff,
+            ClassItem::Range(_, end) => end > 0xffff,
+            ClassItem::Escape(ref e) => !is_bmp_escape(e),
+        })
+}
+
+fn class_set(class: &Class) -> Option<CodePointSet> {
+    let mut set = CodePointSet::default();
+    for item in &class.items {
+        match *item {
+            ClassItem::Char(c) => set.add(c, c),
+            ClassItem::Range(start, end) => set.add(start, end),
+            ClassItem::Escape(ref e) => set.union(&regex::escape_set(e)?),
+        }
+    }
+
+    Some(if class.negated { set.complement() } else { set })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_regex(),
+        babel_basic,
+        r"var re = /\u{1F4A9}+/u;",
+        r"var re = /(?:\uD83D\uDCA9)+/;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_regex(),
+        babel_class,
+        r"var re = /[a\u{1F600}-\u{1F64F}]/gu;",
+        r"var re = /(?:[a]|\uD83D[\uDE00-\uDE4F])/g;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_regex(),
+        babel_bmp_only,
+        r"var re = /[a-z]\d\u{41}/u;",
+        r"var re = /[a-z]\dA/;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_regex(),
+        babel_negated_class,
+        r"var re = /[^\0-\uFFFF]/u;",
+        r"var re = /(?:[\uD800-\uDBFF][\uDC00-\uDFFF])/;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_regex(),
+        babel_ignore_without_flag,
+        r"var re = /[^a]/;",
+        r"var re = /[^a]/;"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_regex(),
+        exec_astral,
+        r"expect(/^.$/u.test('💩')).toBe(true);
+expect(/^[💩-💫]$/u.test('💪')).toBe(true);
+expect(/^[^a]$/u.test('💩')).toBe(true);
+expect(/^\S$/u.test('💩')).toBe(true);
+expect(/^\p{Emoji_Presentation}$/u.test('💩')).toBe(true);
+expect(/^\u{1F4A9}{2}$/u.test('💩💩')).toBe(true);"
+    );
+}
@@ -1,12 +1,26 @@
 pub use self::{
+    async_generator_functions::async_generator_functions, dot_all_regex::dot_all_regex,
+    named_capturing_groups_regex::named_capturing_groups_regex,
     object_rest_spread::object_rest_spread, optional_catch_binding::optional_catch_binding,
+    unicode_property_regex::unicode_property_regex,
 };
 use crate::pass::Pass;
 use swc_common::chain;
 
+mod async_generator_functions;
+mod dot_all_regex;
+mod named_capturing_groups_regex;
 mod object_rest_spread;
 mod optional_catch_binding;
+mod unicode_property_regex;
 
 pub fn es2018() -> impl Pass {
-    chain!(object_rest_spread(), optional_catch_binding())
+    chain!(
+        async_generator_functions(),
+        object_rest_spread(),
+        optional_catch_binding(),
+        dot_all_regex(),
+        named_capturing_groups_regex(),
+        unicode_property_regex()
+    )
 }
@@ -0,0 +1,477 @@
+use crate::{
+    pass::Pass,
+    util::{ExprFactory, StmtLike},
+};
+use swc_common::{Fold, FoldWith, Span, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// `@babel/plugin-proposal-async-generator-functions`
+///
+/// ## In
+///
+/// ```js
+/// async function* foo() {
+///   for await (const x of bar) {
+///     yield await x;
+///   }
+/// }
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// function foo() {
+///   return _wrapAsyncGenerator(function* () {
+///     var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+///     try {
+///       for (var _iterator = _asyncIterator(bar), _step, _value; _step = yield _awaitAsyncGenerator(_iterator.next()), ...) {
+///         const x = _value;
+///         yield yield _awaitAsyncGenerator(x);
+///       }
+///     } catch (err) {
+///       ...
+///     }
+///   }).apply(this, arguments);
+/// }
+/// ```
+pub fn async_generator_functions() -> impl Pass {
+    AsyncGeneratorFunctions
+}
+
+struct AsyncGeneratorFunctions;
+
+noop_fold_type!(AsyncGeneratorFunctions);
+
+impl AsyncGeneratorFunctions {
+    /// Returns [Err] if `stmt` is not a `for await` statement.
+    fn lower_for_await(&mut self, stmt: Stmt) -> Result<Vec<Stmt>, Stmt> {
+        match stmt {
+            Stmt::ForOf(
+                s
+                @
+                ForOfStmt {
+                    await_token: Some(..),
+                    ..
+                },
+            ) => Ok(fold_for_await(s.fold_children(self), None)),
+
+            // The label should be kept on the loop.
+            Stmt::Labeled(LabeledStmt {
+                span,
+                label,
+                body:
+                    box Stmt::ForOf(
+                        s
+                        @
+                        ForOfStmt {
+                            await_token: Some(..),
+                            ..
+                        },
+                    ),
+            }) => Ok(fold_for_await(s.fold_children(self), Some((span, label)))),
+
+            _ => Err(stmt),
+        }
+    }
+}
+
+impl<T> Fold<Vec<T>> for AsyncGeneratorFunctions
+where
+    T: StmtLike + FoldWith<Self>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(stmts.len());
+
+        for stmt in stmts {
+            match stmt.try_into_stmt() {
+                Ok(stmt) => match self.lower_for_await(stmt) {
+                    Ok(stmts) => buf.extend(stmts.into_iter().map(T::from_stmt)),
+                    Err(stmt) => buf.push(T::from_stmt(stmt.fold_with(self))),
+                },
+                Err(item) => buf.push(item.fold_with(self)),
+            }
+        }
+
+        buf
+    }
+}
+
+impl Fold<Stmt> for AsyncGeneratorFunctions {
+    /// Handles `for await` which is not in a statement list, like
+    /// `if (a) for await (const b of c) {}`.
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        match self.lower_for_await(stmt) {
+            Ok(stmts) => Stmt::Block(BlockStmt {
+                span: DUMMY_SP,
+                stmts,
+            }),
+            Err(stmt) => stmt.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Function> for AsyncGeneratorFunctions {
+    fn fold(&mut self, f: Function) -> Function {
+        let f = f.fold_children(self);
+        if !f.is_async || !f.is_generator {
+            return f;
+        }
+
+        let body = match f.body {
+            Some(body) => body.fold_with(&mut AwaitToYield),
+            None => return f,
+        };
+
+        // Placeholders to preserve `length` of the function.
+        let params = f
+            .params
+            .iter()
+            .take_while(|p| match p.pat {
+                Pat::Assign(..) | Pat::Rest(..) => false,
+                _ => true,
+            })
+            .map(|p| Param {
+                span: DUMMY_SP,
+                decorators: Default::default(),
+                pat: Pat::Ident(private_ident!(p.span, "_x")),
+            })
+            .collect();
+
+        // function* (a, b) { ... }
+        let generator = FnExpr {
+            ident: None,
+            function: Function {
+                span: DUMMY_SP,
+                params: f.params,
+                decorators: Default::default(),
+                body: Some(body),
+                is_generator: true,
+                is_async: false,
+                type_params: Default::default(),
+                return_type: Default::default(),
+            },
+        };
+
+        // _wrapAsyncGenerator(function* () {}).apply(this, arguments)
+        let wrapped = CallExpr {
+            span: DUMMY_SP,
+            callee: helper!(wrap_async_generator, "wrapAsyncGenerator"),
+            args: vec![generator.as_arg()],
+            type_args: Default::default(),
+        }
+        .apply(
+            DUMMY_SP,
+            box Expr::This(ThisExpr { span: DUMMY_SP }),
+            vec![quote_ident!("arguments").as_arg()],
+        );
+
+        Function {
+            params,
+            body: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![Stmt::Return(ReturnStmt {
+                    span: DUMMY_SP,
+                    arg: Some(box wrapped),
+                })],
+            }),
+            is_async: false,
+            is_generator: false,
+            ..f
+        }
+    }
+}
+
+/// Converts `await` and `yield*` in the body of an async generator.
+struct AwaitToYield;
+
+noop_fold_type!(AwaitToYield);
+
+macro_rules! noop {
+    ($T:path) => {
+        impl Fold<$T> for AwaitToYield {
+            /// Don't recurse into function.
+            fn fold(&mut self, f: $T) -> $T {
+                f
+            }
+        }
+    };
+}
+noop!(Function);
+noop!(ArrowExpr);
+noop!(Constructor);
+
+impl Fold<Expr> for AwaitToYield {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        let expr = expr.fold_children(self);
+
+        match expr {
+            // yield _awaitAsyncGenerator(arg)
+            Expr::Await(AwaitExpr { span, arg }) => Expr::Yield(YieldExpr {
+                span,
+                delegate: false,
+                arg: Some(box Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: helper!(await_async_generator, "awaitAsyncGenerator"),
+                    args: vec![arg.as_arg()],
+                    type_args: Default::default(),
+                })),
+            }),
+
+            // yield* _asyncGeneratorDelegate(_asyncIterator(arg), _awaitAsyncGenerator)
+            Expr::Yield(YieldExpr {
+                span,
+                delegate: true,
+                arg: Some(arg),
+            }) => {
+                let iterator = CallExpr {
+                    span: DUMMY_SP,
+                    callee: helper!(async_iterator, "asyncIterator"),
+                    args: vec![arg.as_arg()],
+                    type_args: Default::default(),
+                };
+
+                Expr::Yield(YieldExpr {
+                    span,
+                    delegate: true,
+                    arg: Some(box Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: helper!(async_generator_delegate, "asyncGeneratorDelegate"),
+                        args: vec![
+                            iterator.as_arg(),
+                            helper_expr!(await_async_generator, "awaitAsyncGenerator").as_arg(),
+                        ],
+                        type_args: Default::default(),
+                    })),
+                })
+            }
+
+            _ => expr,
+        }
+    }
+}
+
+/// Desugars `for await` into a `for` statement which calls
+/// `_asyncIterator`.
+///
+/// `await` expressions in the result are converted by [AwaitToYield] if the
+/// statement is in an async generator, or by `async_to_generator`.
+fn fold_for_await(s: ForOfStmt, label: Option<(Span, Ident)>) -> Vec<Stmt> {
+    let span = s.span;
+
+    let normal_completion = private_ident!("_iteratorNormalCompletion");
+    let did_error = private_ident!("_didIteratorError");
+    let error = private_ident!("_iteratorError");
+    let iterator = private_ident!("_iterator");
+    let step = private_ident!("_step");
+    let value = private_ident!("_value");
+    let err_param = private_ident!("err");
+
+    let var = |name: &Ident, init: Option<Expr>| VarDeclarator {
+        span: DUMMY_SP,
+        name: Pat::Ident(name.clone()),
+        init: init.map(Box::new),
+        definite: false,
+    };
+    let assign = |name: &Ident, right: Expr| {
+        Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            op: op!("="),
+            left: PatOrExpr::Pat(box Pat::Ident(name.clone())),
+            right: box right,
+        })
+    };
+    let await_expr = |arg: Expr| {
+        Expr::Await(AwaitExpr {
+            span: DUMMY_SP,
+            arg: box arg,
+        })
+    };
+    let bool_lit = |value| {
+        Expr::Lit(Lit::Bool(Bool {
+            span: DUMMY_SP,
+            value,
+        }))
+    };
+    let not = |arg: Expr| {
+        Expr::Unary(UnaryExpr {
+            span: DUMMY_SP,
+            op: op!("!"),
+            arg: box arg,
+        })
+    };
+
+    // var _iteratorNormalCompletion = true, _didIteratorError = false,
+    // _iteratorError;
+    let state = Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        decls: vec![
+            var(&normal_completion, Some(bool_lit(true))),
+            var(&did_error, Some(bool_lit(false))),
+            var(&error, None),
+        ],
+        declare: false,
+    }));
+
+    // const x = _value;
+    let binding = match s.left {
+        VarDeclOrPat::VarDecl(mut decl) => {
+            decl.decls[0].init = Some(box Expr::Ident(value.clone()));
+            Stmt::Decl(Decl::Var(decl))
+        }
+        VarDeclOrPat::Pat(pat) => AssignExpr {
+            span: DUMMY_SP,
+            op: op!("="),
+            left: PatOrExpr::Pat(box pat),
+            right: box Expr::Ident(value.clone()),
+        }
+        .into_stmt(),
+    };
+
+    // _step = await _iterator.next(), _iteratorNormalCompletion = _step.done,
+    // _value = await _step.value, !_iteratorNormalCompletion
+    let test = Expr::Seq(SeqExpr {
+        span: DUMMY_SP,
+        exprs: vec![
+            box assign(
+                &step,
+                await_expr(Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: iterator.clone().member(quote_ident!("next")).as_callee(),
+                    args: vec![],
+                    type_args: Default::default(),
+                })),
+            ),
+            box assign(
+                &normal_completion,
+                step.clone().member(quote_ident!("done")),
+            ),
+            box assign(
+                &value,
+                await_expr(step.clone().member(quote_ident!("value"))),
+            ),
+            box not(Expr::Ident(normal_completion.clone())),
+        ],
+    });
+
+    let for_stmt = Stmt::For(ForStmt {
+        span,
+        init: Some(VarDeclOrExpr::VarDecl(VarDecl {
+            span: DUMMY_SP,
+            kind: VarDeclKind::Var,
+            decls: vec![
+                var(
+                    &iterator,
+                    Some(Expr::Call(CallExpr {
+                        span: DUMMY_SP,
+                        callee: helper!(async_iterator, "asyncIterator"),
+                        args: vec![s.right.as_arg()],
+                        type_args: Default::default(),
+                    })),
+                ),
+                var(&step, None),
+                var(&value, None),
+            ],
+            declare: false,
+        })),
+        test: Some(box test),
+        update: Some(box assign(&normal_completion, bool_lit(true))),
+        body: box Stmt::Block(BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![binding, *s.body],
+        }),
+    });
+
+    let for_stmt = match label {
+        Some((span, label)) => Stmt::Labeled(LabeledStmt {
+            span,
+            label,
+            body: box for_stmt,
+        }),
+        None => for_stmt,
+    };
+
+    // catch (err) { _didIteratorError = true; _iteratorError = err; }
+    let handler = CatchClause {
+        span: DUMMY_SP,
+        param: Some(Pat::Ident(err_param.clone())),
+        body: BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![
+                assign(&did_error, bool_lit(true)).into_stmt(),
+                assign(&error, Expr::Ident(err_param)).into_stmt(),
+            ],
+        },
+    };
+
+    // if (!_iteratorNormalCompletion && _iterator.return != null) {
+    //     await _iterator.return();
+    // }
+    let close = Stmt::If(IfStmt {
+        span: DUMMY_SP,
+        test: box Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            op: op!("&&"),
+            left: box not(Expr::Ident(normal_completion)),
+            right: box Expr::Bin(BinExpr {
+                span: DUMMY_SP,
+                op: op!("!="),
+                left: box iterator.clone().member(quote_ident!("return")),
+                right: box Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+            }),
+        }),
+        cons: box Stmt::Block(BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![await_expr(Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: iterator.member(quote_ident!("return")).as_callee(),
+                args: vec![],
+                type_args: Default::default(),
+            }))
+            .into_stmt()],
+        }),
+        alt: None,
+    });
+
+    // if (_didIteratorError) { throw _iteratorError; }
+    let rethrow = Stmt::If(IfStmt {
+        span: DUMMY_SP,
+        test: box Expr::Ident(did_error),
+        cons: box Stmt::Block(BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![Stmt::Throw(ThrowStmt {
+                span: DUMMY_SP,
+                arg: box Expr::Ident(error),
+            })],
+        }),
+        alt: None,
+    });
+
+    let finalizer = BlockStmt {
+        span: DUMMY_SP,
+        stmts: vec![Stmt::Try(TryStmt {
+            span: DUMMY_SP,
+            block: BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![close],
+            },
+            handler: None,
+            finalizer: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts: vec![rethrow],
+            }),
+        })],
+    };
+
+    let try_stmt = Stmt::Try(TryStmt {
+        span,
+        block: BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![for_stmt],
+        },
+        handler: Some(handler),
+        finalizer: Some(finalizer),
+    });
+
+    vec![state, try_stmt]
+}
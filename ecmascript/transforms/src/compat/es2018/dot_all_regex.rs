@@ -0,0 +1,110 @@
+use crate::{
+    compat::regex::{self, Atom, Class, ClassEscape, ClassItem},
+    pass::Pass,
+};
+use swc_common::{Fold, FoldWith};
+use swc_ecma_ast::*;
+
+/// Compile the `s` flag of regex literals.
+///
+///# Example
+///## In
+///
+/// ```js
+/// /a.b/s;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// /a[\s\S]b/;
+/// ```
+pub fn dot_all_regex() -> impl Pass {
+    DotAllRegex
+}
+
+#[derive(Clone, Copy)]
+struct DotAllRegex;
+
+noop_fold_type!(DotAllRegex);
+
+impl Fold<Expr> for DotAllRegex {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Lit(Lit::Regex(r)) if r.flags.contains('s') => {
+                let unicode = r.flags.contains('u');
+                let mut pattern = match regex::parse(&r.exp, unicode) {
+                    Ok(pattern) => pattern,
+                    Err(..) => return Expr::Lit(Lit::Regex(r)),
+                };
+
+                pattern.visit_atoms_mut(&mut |atom| {
+                    if *atom == Atom::Dot {
+                        *atom = Atom::Class(Class {
+                            negated: false,
+                            items: vec![
+                                ClassItem::Escape(ClassEscape::Space { negated: false }),
+                                ClassItem::Escape(ClassEscape::Space { negated: true }),
+                            ],
+                        });
+                    }
+                });
+
+                Expr::Lit(Lit::Regex(Regex {
+                    exp: regex::print(&pattern, unicode).into(),
+                    flags: regex::remove_flag(&r.flags, 's'),
+                    ..r
+                }))
+            }
+            _ => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| dot_all_regex(),
+        babel_simple,
+        "var a = /./s;",
+        r"var a = /[\s\S]/;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| dot_all_regex(),
+        babel_with_unicode_flag,
+        r"var a = /^.+\.$/su;",
+        r"var a = /^[\s\S]+\.$/u;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| dot_all_regex(),
+        babel_dot_in_class,
+        r"var a = /[.]\n./gs;",
+        r"var a = /[.]\n[\s\S]/g;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| dot_all_regex(),
+        babel_ignore_without_flag,
+        "var a = /./;",
+        "var a = /./;"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| dot_all_regex(),
+        exec_line_terminators,
+        r"expect(/^a.b$/s.test('a\nb')).toBe(true);
+expect(/^a.b$/s.test('a\u2028b')).toBe(true);
+expect(/^a.b$/.test('a\nb')).toBe(false);"
+    );
+}
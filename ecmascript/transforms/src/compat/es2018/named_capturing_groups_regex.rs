@@ -0,0 +1,160 @@
+use crate::{
+    compat::regex::{self, Atom, GroupKind},
+    pass::Pass,
+    util::ExprFactory,
+};
+use swc_atoms::JsWord;
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// Compile named capturing groups of regex literals.
+///
+/// Names are removed from the pattern, and the regex is wrapped with a helper
+/// which provides `groups` using the index of each group.
+///
+///# Example
+///## In
+///
+/// ```js
+/// /(?<year>\d{4})-(?<month>\d{2})/;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// _wrapRegExp(/(\d{4})-(\d{2})/, { year: 1, month: 2 });
+/// ```
+pub fn named_capturing_groups_regex() -> impl Pass {
+    NamedCapturingGroupsRegex
+}
+
+#[derive(Clone, Copy)]
+struct NamedCapturingGroupsRegex;
+
+noop_fold_type!(NamedCapturingGroupsRegex);
+
+impl Fold<Expr> for NamedCapturingGroupsRegex {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Lit(Lit::Regex(r)) if r.exp.contains("(?<") => match lower(&r) {
+                Some((exp, groups)) => {
+                    let props = groups
+                        .into_iter()
+                        .map(|(name, index)| {
+                            PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                                key: PropName::Ident(Ident::new(name.into(), DUMMY_SP)),
+                                value: box Expr::Lit(Lit::Num(Number {
+                                    span: DUMMY_SP,
+                                    value: index as f64,
+                                })),
+                            }))
+                        })
+                        .collect();
+
+                    Expr::Call(CallExpr {
+                        span: r.span,
+                        callee: helper!(wrap_reg_exp, "wrapRegExp"),
+                        args: vec![
+                            Expr::Lit(Lit::Regex(Regex { exp, ..r })).as_arg(),
+                            Expr::Object(ObjectLit {
+                                span: DUMMY_SP,
+                                props,
+                            })
+                            .as_arg(),
+                        ],
+                        type_args: Default::default(),
+                    })
+                }
+                None => Expr::Lit(Lit::Regex(r)),
+            },
+            _ => e,
+        }
+    }
+}
+
+/// Returns the pattern without names, and the index of each named group.
+///
+/// Returns [None] if the pattern is invalid or has no named group.
+fn lower(r: &Regex) -> Option<(JsWord, Vec<(String, u32)>)> {
+    let unicode = r.flags.contains('u');
+    let mut pattern = regex::parse(&r.exp, unicode).ok()?;
+
+    let mut index = 0;
+    let mut groups = vec![];
+    pattern.visit_atoms_mut(&mut |atom| {
+        if let Atom::Group(group) = atom {
+            if let GroupKind::Capturing(ref mut name) = group.kind {
+                index += 1;
+                if let Some(name) = name.take() {
+                    groups.push((name, index));
+                }
+            }
+        }
+    });
+    if groups.is_empty() {
+        return None;
+    }
+
+    let mut valid = true;
+    pattern.visit_atoms_mut(&mut |atom| {
+        if let Atom::NamedBackreference(name) = atom {
+            match groups.iter().find(|group| group.0 == *name) {
+                Some(&(_, index)) => *atom = Atom::Backreference(index),
+                None => valid = false,
+            }
+        }
+    });
+    if !valid {
+        return None;
+    }
+
+    Some((regex::print(&pattern, unicode).into(), groups))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| named_capturing_groups_regex(),
+        babel_basic,
+        r#"var re = /(?<year>\d{4})-(?<month>\d{2})-(?<day>\d{2})/;"#,
+        r#"var re = _wrapRegExp(/(\d{4})-(\d{2})-(\d{2})/, {
+  year: 1,
+  month: 2,
+  day: 3
+});"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| named_capturing_groups_regex(),
+        babel_backreference,
+        r#"var re = /(a)(?<b>b)\k<b>(?:c)/g;"#,
+        r#"var re = _wrapRegExp(/(a)(b)\2(?:c)/g, {
+  b: 2
+});"#
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| named_capturing_groups_regex(),
+        babel_lookbehind,
+        r#"var re = /(?<=a)(?<!b)c/;"#,
+        r#"var re = /(?<=a)(?<!b)c/;"#
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| named_capturing_groups_regex(),
+        exec_groups,
+        r#"var re = /(?<year>\d{4})-(?<month>\d{2})/;
+var result = re.exec('2020-05');
+expect(result.groups.year).toBe('2020');
+expect(result.groups.month).toBe('05');
+expect('2020-05'.replace(re, '$<month>/$<year>')).toBe('05/2020');"#
+    );
+}
@@ -0,0 +1,142 @@
+use crate::{
+    compat::regex::{self, Atom, ClassEscape, ClassItem},
+    pass::Pass,
+};
+use swc_common::{Fold, FoldWith};
+use swc_ecma_ast::*;
+
+/// Compile unicode property escapes of regex literals.
+///
+/// Property escapes are replaced with the code points they match, so the `u`
+/// flag is preserved.
+///
+///# Example
+///## In
+///
+/// ```js
+/// /\p{ASCII_Hex_Digit}/u;
+/// ```
+///
+///## Out
+///
+/// ```js
+/// /[0-9A-Fa-f]/u;
+/// ```
+pub fn unicode_property_regex() -> impl Pass {
+    UnicodePropertyRegex
+}
+
+#[derive(Clone, Copy)]
+struct UnicodePropertyRegex;
+
+noop_fold_type!(UnicodePropertyRegex);
+
+impl Fold<Expr> for UnicodePropertyRegex {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Lit(Lit::Regex(r))
+                if r.flags.contains('u') && (r.exp.contains("\\p") || r.exp.contains("\\P")) =>
+            {
+                match lower(&r.exp) {
+                    Some(exp) => Expr::Lit(Lit::Regex(Regex {
+                        exp: exp.into(),
+                        ..r
+                    })),
+                    None => Expr::Lit(Lit::Regex(r)),
+                }
+            }
+            _ => e,
+        }
+    }
+}
+
+/// Returns [None] if the pattern is invalid or uses an unknown property.
+fn lower(exp: &str) -> Option<String> {
+    let mut pattern = regex::parse(exp, true).ok()?;
+
+    let mut valid = true;
+    pattern.visit_atoms_mut(&mut |atom| match atom {
+        Atom::Escape(e @ ClassEscape::Property { .. }) => match regex::escape_set(e) {
+            Some(set) => *atom = regex::unicode_class(&set),
+            None => valid = false,
+        },
+        Atom::Class(class) => {
+            let mut items = Vec::with_capacity(class.items.len());
+            for item in class.items.drain(..) {
+                match item {
+                    ClassItem::Escape(e @ ClassEscape::Property { .. }) => {
+                        match regex::escape_set(&e) {
+                            Some(set) => items.extend(regex::class_items(&set)),
+                            None => valid = false,
+                        }
+                    }
+                    _ => items.push(item),
+                }
+            }
+            class.items = items;
+        }
+        _ => {}
+    });
+    if !valid {
+        return None;
+    }
+
+    Some(regex::print(&pattern, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_property_regex(),
+        babel_binary_property,
+        r"var a = /\p{ASCII_Hex_Digit}/u;",
+        r"var a = /[0-9A-Fa-f]/u;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_property_regex(),
+        babel_in_class,
+        r"var a = /[^\p{ASCII_Hex_Digit}_]/u;",
+        r"var a = /[^0-9A-Fa-f_]/u;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_property_regex(),
+        babel_negated,
+        r"var a = /\P{Any}/u;",
+        r"var a = /[]/u;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_property_regex(),
+        babel_unknown_property,
+        r"var a = /\p{Unknown}/u;",
+        r"var a = /\p{Unknown}/u;"
+    );
+
+    test!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_property_regex(),
+        babel_ignore_without_unicode_flag,
+        r"var a = /\p{L}/;",
+        r"var a = /\p{L}/;"
+    );
+
+    test_exec!(
+        ::swc_ecma_parser::Syntax::default(),
+        |_| unicode_property_regex(),
+        exec_script,
+        r"var re = /^\p{Script=Greek}+$/u;
+expect(re.test('μετά')).toBe(true);
+expect(re.test('after')).toBe(false);
+expect(/^\p{Lu}\p{Ll}$/u.test('Ab')).toBe(true);"
+    );
+}
@@ -0,0 +1,9 @@
+pub use self::{literals::literals, logical_assignments::logical_assignments};
+use crate::pass::Pass;
+
+mod literals;
+mod logical_assignments;
+
+pub fn es2021() -> impl Pass {
+    logical_assignments()
+}
@@ -0,0 +1,86 @@
+use crate::{pass::Pass, util::ExprFactory};
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// Lowers literals which can't be represented in older targets.
+///
+/// Numeric literals (including ones with separators, binary and octal
+/// literals) and string literals (including legacy octal and unicode escapes)
+/// are printed from their values by the code generator, so they don't need
+/// to be transformed.
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// const big = 1_000n;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// const big = BigInt("1000");
+/// ```
+pub fn literals() -> impl Pass {
+    Literals
+}
+
+#[derive(Clone, Copy)]
+struct Literals;
+
+noop_fold_type!(Literals);
+
+impl Fold<Expr> for Literals {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Lit(Lit::BigInt(BigInt { span, value })) => Expr::Call(CallExpr {
+                span,
+                callee: quote_ident!("BigInt").as_callee(),
+                args: vec![Lit::Str(Str {
+                    span: DUMMY_SP,
+                    value: value.to_string().into(),
+                    has_escape: false,
+                })
+                .as_arg()],
+                type_args: Default::default(),
+            }),
+            _ => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_ecma_parser::{EsConfig, Syntax};
+
+    fn syntax() -> Syntax {
+        Syntax::Es(EsConfig {
+            num_sep: true,
+            ..Default::default()
+        })
+    }
+
+    test!(
+        syntax(),
+        |_| literals(),
+        big_int,
+        "const a = 10n;
+const b = -0x10n + a;",
+        "const a = BigInt('10');
+const b = -BigInt('16') + a;"
+    );
+
+    test!(
+        syntax(),
+        |_| literals(),
+        numeric_separator,
+        "const a = 1_000_000;
+const b = 0b1010_0001;",
+        "const a = 1000000;
+const b = 161;"
+    );
+}
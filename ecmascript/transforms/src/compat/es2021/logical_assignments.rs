@@ -0,0 +1,485 @@
+use crate::{
+    pass::Pass,
+    util::{alias_ident_for, undefined, ExprFactory, StmtLike},
+};
+use std::mem::replace;
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// `@babel/plugin-proposal-logical-assignment-operators`
+///
+/// # Example
+///
+/// ## In
+///
+/// ```js
+/// a ||= b;
+/// obj.foo.bar &&= c;
+/// x ??= y;
+/// ```
+///
+/// ## Out
+///
+/// ```js
+/// var _foo;
+/// a || (a = b);
+/// (_foo = obj.foo).bar && (_foo.bar = c);
+/// x !== null && x !== void 0 ? x : x = y;
+/// ```
+///
+/// Temporary variables are declared in the function which evaluates the
+/// expression. An arrow function with an expression body is converted to use a
+/// block body if it requires a temporary variable, and default values and
+/// class fields are wrapped in an arrow function.
+pub fn logical_assignments() -> impl Pass {
+    LogicalAssignments::default()
+}
+
+#[derive(Debug, Default)]
+struct LogicalAssignments {
+    vars: Vec<VarDeclarator>,
+}
+
+noop_fold_type!(LogicalAssignments);
+
+impl<T> Fold<Vec<T>> for LogicalAssignments
+where
+    T: FoldWith<Self> + StmtLike,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let mut buf = Vec::with_capacity(stmts.len() + 2);
+
+        for stmt in stmts {
+            let stmt = stmt.fold_with(self);
+
+            if !self.vars.is_empty() {
+                buf.push(T::from_stmt(Stmt::Decl(Decl::Var(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls: replace(&mut self.vars, Default::default()),
+                    declare: false,
+                }))));
+            }
+
+            buf.push(stmt);
+        }
+
+        buf
+    }
+}
+
+impl Fold<ArrowExpr> for LogicalAssignments {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let outer = replace(&mut self.vars, Default::default());
+
+        let params = f.params.fold_with(self);
+        let body = match f.body {
+            BlockStmtOrExpr::Expr(expr) => {
+                let expr = expr.fold_with(self);
+                let vars = replace(&mut self.vars, Default::default());
+
+                if vars.is_empty() {
+                    BlockStmtOrExpr::Expr(expr)
+                } else {
+                    BlockStmtOrExpr::BlockStmt(declare_and_return(vars, expr))
+                }
+            }
+            body => body.fold_with(self),
+        };
+
+        self.vars = outer;
+
+        ArrowExpr { params, body, ..f }
+    }
+}
+
+/// Default values are evaluated only if required, and may be a part of
+/// parameters.
+impl Fold<AssignPat> for LogicalAssignments {
+    fn fold(&mut self, p: AssignPat) -> AssignPat {
+        AssignPat {
+            left: p.left.fold_with(self),
+            right: self.fold_isolated(p.right),
+            ..p
+        }
+    }
+}
+
+impl Fold<AssignPatProp> for LogicalAssignments {
+    fn fold(&mut self, p: AssignPatProp) -> AssignPatProp {
+        AssignPatProp {
+            value: p.value.map(|v| self.fold_isolated(v)),
+            ..p
+        }
+    }
+}
+
+/// Class fields are evaluated for each instance.
+impl Fold<ClassProp> for LogicalAssignments {
+    fn fold(&mut self, mut p: ClassProp) -> ClassProp {
+        let value = p.value.take().map(|v| self.fold_isolated(v));
+
+        ClassProp {
+            value,
+            ..p.fold_children(self)
+        }
+    }
+}
+
+impl Fold<PrivateProp> for LogicalAssignments {
+    fn fold(&mut self, mut p: PrivateProp) -> PrivateProp {
+        let value = p.value.take().map(|v| self.fold_isolated(v));
+
+        PrivateProp {
+            value,
+            ..p.fold_children(self)
+        }
+    }
+}
+
+impl Fold<Expr> for LogicalAssignments {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            Expr::Assign(AssignExpr {
+                span,
+                op,
+                left,
+                right,
+            }) if op == op!("&&=") || op == op!("||=") || op == op!("??=") => {
+                let left = match left {
+                    PatOrExpr::Expr(e) | PatOrExpr::Pat(box Pat::Expr(e)) => e,
+                    PatOrExpr::Pat(box Pat::Ident(i)) => box Expr::Ident(i),
+                    left => {
+                        return Expr::Assign(AssignExpr {
+                            span,
+                            op,
+                            left,
+                            right,
+                        })
+                    }
+                };
+
+                let (read, target) = self.memorize_target(left);
+                let assign = box Expr::Assign(AssignExpr {
+                    span: DUMMY_SP,
+                    op: op!("="),
+                    left: match *target {
+                        Expr::Ident(i) => PatOrExpr::Pat(box Pat::Ident(i)),
+                        target => PatOrExpr::Pat(box Pat::Expr(box target)),
+                    },
+                    right,
+                });
+
+                if op == op!("&&=") || op == op!("||=") {
+                    return Expr::Bin(BinExpr {
+                        span,
+                        left: read,
+                        op: if op == op!("&&=") {
+                            op!("&&")
+                        } else {
+                            op!("||")
+                        },
+                        right: assign,
+                    });
+                }
+
+                // `??=`
+                let (init, value) = self.memorize(read, "_ref");
+                Expr::Cond(CondExpr {
+                    span,
+                    test: box Expr::Bin(BinExpr {
+                        span: DUMMY_SP,
+                        left: box Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: init,
+                            op: op!("!=="),
+                            right: box Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+                        }),
+                        op: op!("&&"),
+                        right: box Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: value.clone(),
+                            op: op!("!=="),
+                            right: undefined(DUMMY_SP),
+                        }),
+                    }),
+                    cons: value,
+                    alt: assign,
+                })
+            }
+
+            _ => e,
+        }
+    }
+}
+
+impl LogicalAssignments {
+    /// Folds an expression which is not evaluated with the enclosing
+    /// statement.
+    ///
+    /// If a temporary variable is required, the expression is wrapped in an
+    /// arrow function so that the variable is not shared.
+    fn fold_isolated(&mut self, e: Box<Expr>) -> Box<Expr> {
+        let outer = replace(&mut self.vars, Default::default());
+        let e = e.fold_with(self);
+        let vars = replace(&mut self.vars, outer);
+
+        if vars.is_empty() {
+            return e;
+        }
+
+        // (() => { var _ref; return e; })()
+        box Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: ArrowExpr {
+                span: DUMMY_SP,
+                params: vec![],
+                body: BlockStmtOrExpr::BlockStmt(declare_and_return(vars, e)),
+                is_async: false,
+                is_generator: false,
+                type_params: Default::default(),
+                return_type: Default::default(),
+            }
+            .as_callee(),
+            args: vec![],
+            type_args: Default::default(),
+        })
+    }
+
+    /// Returns `(read, target)`, where `read` evaluates the object (and the
+    /// computed property) of a member expression only once, and `target`
+    /// refers to the same property.
+    fn memorize_target(&mut self, e: Box<Expr>) -> (Box<Expr>, Box<Expr>) {
+        match *e {
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop,
+                computed,
+            }) => {
+                let (read_obj, obj) = match obj {
+                    ExprOrSuper::Expr(obj) => {
+                        let (read, obj) = self.memorize(obj, "_ref");
+                        (ExprOrSuper::Expr(read), ExprOrSuper::Expr(obj))
+                    }
+                    ExprOrSuper::Super(s) => (ExprOrSuper::Super(s), ExprOrSuper::Super(s)),
+                };
+                let (read_prop, prop) = if computed {
+                    self.memorize(prop, "_ref")
+                } else {
+                    (prop.clone(), prop)
+                };
+
+                (
+                    box Expr::Member(MemberExpr {
+                        span,
+                        obj: read_obj,
+                        prop: read_prop,
+                        computed,
+                    }),
+                    box Expr::Member(MemberExpr {
+                        span,
+                        obj,
+                        prop,
+                        computed,
+                    }),
+                )
+            }
+            _ => (e.clone(), e),
+        }
+    }
+
+    /// Stores `e` to a new variable if it may have side effects.
+    ///
+    /// Returns `(init, alias)`, where `init` should be evaluated first.
+    fn memorize(&mut self, e: Box<Expr>, default: &str) -> (Box<Expr>, Box<Expr>) {
+        match *e {
+            Expr::Ident(..) | Expr::This(..) | Expr::Lit(..) => (e.clone(), e),
+            _ => {
+                let alias = alias_ident_for(&e, default);
+                self.vars.push(VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(alias.clone()),
+                    init: None,
+                    definite: false,
+                });
+
+                (
+                    box Expr::Assign(AssignExpr {
+                        span: DUMMY_SP,
+                        op: op!("="),
+                        left: PatOrExpr::Pat(box Pat::Ident(alias.clone())),
+                        right: e,
+                    }),
+                    box Expr::Ident(alias),
+                )
+            }
+        }
+    }
+}
+
+/// `{ var vars; return arg; }`
+fn declare_and_return(vars: Vec<VarDeclarator>, arg: Box<Expr>) -> BlockStmt {
+    BlockStmt {
+        span: DUMMY_SP,
+        stmts: vec![
+            Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls: vars,
+                declare: false,
+            })),
+            Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(arg),
+            }),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_ecma_parser::{EsConfig, Syntax};
+
+    fn syntax() -> Syntax {
+        Syntax::Es(EsConfig {
+            logical_assignment: true,
+            ..Default::default()
+        })
+    }
+
+    test!(
+        syntax(),
+        |_| logical_assignments(),
+        ident,
+        "a &&= b;
+a ||= b;
+a ??= b;",
+        "a && (a = b);
+a || (a = b);
+a !== null && a !== void 0 ? a : a = b;"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assignments(),
+        member,
+        "obj.foo.bar ||= c;
+this.x &&= y;",
+        "var _foo;
+(_foo = obj.foo).bar || (_foo.bar = c);
+this.x && (this.x = y);"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assignments(),
+        computed,
+        "a[b()] &&= c;
+a[0] ||= c;",
+        "var _ref;
+a[_ref = b()] && (a[_ref] = c);
+a[0] || (a[0] = c);"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assignments(),
+        nullish_member,
+        "obj.foo ??= c;",
+        "var _foo;
+(_foo = obj.foo) !== null && _foo !== void 0 ? _foo : obj.foo = c;"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assignments(),
+        nested_fn,
+        "function f() {
+    a.b.c ??= d;
+}",
+        "function f() {
+    var _b, _c;
+    (_c = (_b = a.b).c) !== null && _c !== void 0 ? _c : _b.c = d;
+}"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assignments(),
+        arrow_body,
+        "const f = o => o.x.a ||= f(o.x.b);
+const g = o => o.a ||= 1;",
+        "const f = o => {
+    var _x;
+    return (_x = o.x).a || (_x.a = f(o.x.b));
+};
+const g = o => o.a || (o.a = 1);"
+    );
+
+    test!(
+        syntax(),
+        |_| logical_assignments(),
+        default_param,
+        "function f(a = b.c.d ??= 1) {}",
+        "function f(a = (() => {
+    var _c, _d;
+    return (_d = (_c = b.c).d) !== null && _d !== void 0 ? _d : _c.d = 1;
+})()) {}"
+    );
+
+    test!(
+        Syntax::Es(EsConfig {
+            logical_assignment: true,
+            class_props: true,
+            ..Default::default()
+        }),
+        |_| logical_assignments(),
+        class_field,
+        "class A {
+    x = this.y.z &&= 1;
+}",
+        "class A {
+    x = (() => {
+        var _y;
+        return (_y = this.y).z && (_y.z = 1);
+    })();
+}"
+    );
+
+    test_exec!(
+        syntax(),
+        |_| logical_assignments(),
+        exec_arrow_recursion,
+        "const f = (o) => o.x.a ||= (o.x.b ? f(o.x.b) : 1);
+const inner = { x: { a: 0, b: null } };
+const outer = { x: { a: 0, b: inner } };
+
+f(outer);
+
+expect(inner.x.a).toBe(1);
+expect(outer.x.a).toBe(1);"
+    );
+
+    test_exec!(
+        syntax(),
+        |_| logical_assignments(),
+        exec_evaluates_once,
+        "let count = 0;
+const obj = { a: null, b: 1 };
+const get = () => {
+    count++;
+    return obj;
+};
+
+get().a ??= 2;
+get().b &&= 3;
+get().c ||= 4;
+
+expect(obj).toEqual({ a: 2, b: 3, c: 4 });
+expect(count).toBe(3);"
+    );
+}
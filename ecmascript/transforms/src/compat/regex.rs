@@ -0,0 +1,219 @@
+//! Parser and printer for patterns of regex literals, used to compile regex
+//! features which cannot be polyfilled.
+
+pub(crate) use self::{
+    ast::*,
+    parser::parse,
+    printer::print,
+    set::{dot, escape_set, CodePointSet, MAX_CODE_POINT},
+};
+use swc_atoms::JsWord;
+
+mod ast;
+mod parser;
+mod printer;
+mod set;
+mod unicode;
+
+/// Removes `flag` from `flags`.
+pub(crate) fn remove_flag(flags: &str, flag: char) -> JsWord {
+    flags
+        .chars()
+        .filter(|&c| c != flag)
+        .collect::<String>()
+        .into()
+}
+
+/// Creates an atom matching a code point in `set`, for patterns in unicode
+/// mode.
+pub(crate) fn unicode_class(set: &CodePointSet) -> Atom {
+    Atom::Class(Class {
+        negated: false,
+        items: class_items(set),
+    })
+}
+
+/// Items of a character class in unicode mode which match `set`.
+pub(crate) fn class_items(set: &CodePointSet) -> Vec<ClassItem> {
+    set.ranges()
+        .iter()
+        .map(|&(start, end)| class_item(start, end))
+        .collect()
+}
+
+/// Creates an atom matching a code point in `set`, for patterns not in unicode
+/// mode.
+///
+/// Code points above U+FFFF are matched as surrogate pairs, which are tried
+/// before lone surrogates.
+pub(crate) fn surrogate_class(set: &CodePointSet) -> Atom {
+    let mut bmp = vec![];
+    let mut surrogates = vec![];
+    let mut alternatives = vec![];
+
+    for &(start, end) in set.ranges() {
+        if start < 0xd800 {
+            bmp.push(class_item(start, end.min(0xd7ff)));
+        }
+        if start <= 0xdfff && end >= 0xd800 {
+            surrogates.push(class_item(start.max(0xd800), end.min(0xdfff)));
+        }
+        if start <= 0xffff && end >= 0xe000 {
+            bmp.push(class_item(start.max(0xe000), end.min(0xffff)));
+        }
+
+        if end > 0xffff {
+            astral(start.max(0x10000), end, &mut alternatives);
+        }
+    }
+
+    if alternatives.is_empty() {
+        bmp.extend(surrogates);
+        return Atom::Class(Class {
+            negated: false,
+            items: bmp,
+        });
+    }
+
+    let class = |items| {
+        vec![Term::Atom(
+            Atom::Class(Class {
+                negated: false,
+                items,
+            }),
+            None,
+        )]
+    };
+    if !bmp.is_empty() {
+        alternatives.insert(0, class(bmp));
+    }
+    if !surrogates.is_empty() {
+        alternatives.push(class(surrogates));
+    }
+
+    Atom::Group(Box::new(Group {
+        kind: GroupKind::NonCapturing,
+        body: Disjunction { alternatives },
+    }))
+}
+
+fn class_item(start: u32, end: u32) -> ClassItem {
+    if start == end {
+        ClassItem::Char(start)
+    } else {
+        ClassItem::Range(start, end)
+    }
+}
+
+/// Matches `[start, end]` as a surrogate pair.
+fn astral(start: u32, end: u32, alternatives: &mut Vec<Alternative>) {
+    let split = |c: u32| (0xd800 + ((c - 0x10000) >> 10), 0xdc00 + (c & 0x3ff));
+    let atom = |start: u32, end: u32| {
+        let atom = if start == end {
+            Atom::Char(start)
+        } else {
+            Atom::Class(Class {
+                negated: false,
+                items: vec![ClassItem::Range(start, end)],
+            })
+        };
+        Term::Atom(atom, None)
+    };
+
+    let (start_lead, start_trail) = split(start);
+    let (end_lead, end_trail) = split(end);
+
+    if start_lead == end_lead {
+        alternatives.push(vec![
+            atom(start_lead, start_lead),
+            atom(start_trail, end_trail),
+        ]);
+        return;
+    }
+
+    let (mut lead_start, mut lead_end) = (start_lead, end_lead);
+    if start_trail != 0xdc00 {
+        alternatives.push(vec![
+            atom(start_lead, start_lead),
+            atom(start_trail, 0xdfff),
+        ]);
+        lead_start += 1;
+    }
+    let tail = if end_trail != 0xdfff {
+        lead_end -= 1;
+        Some(vec![atom(end_lead, end_lead), atom(0xdc00, end_trail)])
+    } else {
+        None
+    };
+
+    if lead_start <= lead_end {
+        alternatives.push(vec![atom(lead_start, lead_end), atom(0xdc00, 0xdfff)]);
+    }
+    alternatives.extend(tail);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(src: &str, unicode: bool, expected: &str) {
+        let d = parse(src, unicode).unwrap_or_else(|err| panic!("{}: {:?}", src, err));
+        assert_eq!(print(&d, unicode), expected);
+    }
+
+    #[test]
+    fn print_basic() {
+        roundtrip("a|b(c)*?d{2,}\\1", false, "a|b(c)*?d{2,}\\1");
+        roundtrip("[^a-z\\d\\-]+", false, "[^a-z\\d\\-]+");
+        roundtrip(
+            "(?<year>\\d{4})-\\k<year>",
+            false,
+            "(?<year>\\d{4})-\\k<year>",
+        );
+        roundtrip("(?<=a)(?<!b)(?=c)(?!d)", false, "(?<=a)(?<!b)(?=c)(?!d)");
+        roundtrip("\\u{1F600}", true, "\\u{1F600}");
+        roundtrip("\\uD83D\\uDE00", true, "\\u{1F600}");
+        roundtrip("\\p{Script=Greek}", true, "\\p{Script=Greek}");
+    }
+
+    #[test]
+    fn annex_b() {
+        roundtrip("]{", false, "\\]\\{");
+        roundtrip("\\c", false, "\\\\c");
+        roundtrip("\\q", false, "q");
+    }
+
+    #[test]
+    fn parse_error() {
+        assert!(parse("(a", false).is_err());
+        assert!(parse("a)", false).is_err());
+        assert!(parse("[b-a]", false).is_err());
+        assert!(parse("\\u{110000}", true).is_err());
+        assert!(parse("(?<a>b", false).is_err());
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        let set = CodePointSet::from_ranges(vec![(0x61, 0x61), (0x1f600, 0x1f64f)]);
+        assert_eq!(
+            print(
+                &Disjunction {
+                    alternatives: vec![vec![Term::Atom(surrogate_class(&set), None)]]
+                },
+                false
+            ),
+            "(?:[a]|\\uD83D[\\uDE00-\\uDE4F])"
+        );
+
+        let set = CodePointSet::from_ranges(vec![(0x10000, MAX_CODE_POINT)]);
+        assert_eq!(
+            print(
+                &Disjunction {
+                    alternatives: vec![vec![Term::Atom(surrogate_class(&set), None)]]
+                },
+                false
+            ),
+            "(?:[\\uD800-\\uDBFF][\\uDC00-\\uDFFF])"
+        );
+    }
+}
@@ -0,0 +1,121 @@
+/// `a|b`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Disjunction {
+    pub alternatives: Vec<Alternative>,
+}
+
+pub(crate) type Alternative = Vec<Term>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Term {
+    Assertion(Assertion),
+    Atom(Atom, Option<Quantifier>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Assertion {
+    /// `^`
+    Start,
+    /// `$`
+    End,
+    /// `\b`
+    WordBoundary,
+    /// `\B`
+    NotWordBoundary,
+}
+
+/// `*`, `+`, `?`, `{min,max}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Quantifier {
+    pub min: u32,
+    pub max: Option<u32>,
+    pub lazy: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Atom {
+    /// A code point, or a code unit if the pattern is not in unicode mode.
+    Char(u32),
+    /// `.`
+    Dot,
+    Class(Class),
+    Escape(ClassEscape),
+    Group(Box<Group>),
+    /// `\1`
+    Backreference(u32),
+    /// `\k<name>`
+    NamedBackreference(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Group {
+    pub kind: GroupKind,
+    pub body: Disjunction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum GroupKind {
+    /// `(a)` or `(?<name>a)`
+    Capturing(Option<String>),
+    /// `(?:a)`
+    NonCapturing,
+    /// `(?=a)`
+    Lookahead,
+    /// `(?!a)`
+    NegativeLookahead,
+    /// `(?<=a)`
+    Lookbehind,
+    /// `(?<!a)`
+    NegativeLookbehind,
+}
+
+/// `[a-z]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Class {
+    pub negated: bool,
+    pub items: Vec<ClassItem>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ClassItem {
+    Char(u32),
+    Range(u32, u32),
+    Escape(ClassEscape),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ClassEscape {
+    /// `\d` or `\D`
+    Digit { negated: bool },
+    /// `\s` or `\S`
+    Space { negated: bool },
+    /// `\w` or `\W`
+    Word { negated: bool },
+    /// `\p{name=value}` or `\P{name=value}`
+    Property {
+        negated: bool,
+        name: String,
+        value: Option<String>,
+    },
+}
+
+impl Disjunction {
+    /// Calls `op` for all atoms, including atoms in groups.
+    ///
+    /// Atoms are visited in the order of the source, so capturing groups are
+    /// visited in the order of their indices.
+    pub fn visit_atoms_mut<F>(&mut self, op: &mut F)
+    where
+        F: FnMut(&mut Atom),
+    {
+        for term in self.alternatives.iter_mut().flatten() {
+            if let Term::Atom(atom, _) = term {
+                op(atom);
+
+                if let Atom::Group(group) = atom {
+                    group.body.visit_atoms_mut(op);
+                }
+            }
+        }
+    }
+}
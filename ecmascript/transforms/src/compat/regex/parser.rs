@@ -0,0 +1,624 @@
+use super::ast::*;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Error {
+    pub pos: usize,
+    pub msg: &'static str,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.msg, self.pos)
+    }
+}
+
+type PResult<T> = Result<T, Error>;
+
+/// Parses the pattern of a regex literal.
+///
+/// If `unicode` is false, the pattern is parsed as a sequence of utf-16 code
+/// units, with the syntax of Annex B.
+pub(crate) fn parse(src: &str, unicode: bool) -> PResult<Disjunction> {
+    let chars: Vec<u32> = if unicode {
+        src.chars().map(|c| c as u32).collect()
+    } else {
+        src.encode_utf16().map(u32::from).collect()
+    };
+
+    let mut p = Parser {
+        has_named_groups: has_named_groups(&chars),
+        chars,
+        pos: 0,
+        unicode,
+    };
+
+    let d = p.parse_disjunction()?;
+    if p.pos != p.chars.len() {
+        return p.error("unmatched `)`");
+    }
+
+    Ok(d)
+}
+
+/// Returns true if there's `(?<` which is not followed by `=` or `!`.
+fn has_named_groups(chars: &[u32]) -> bool {
+    chars.windows(4).any(|w| {
+        w[0] == '(' as u32
+            && w[1] == '?' as u32
+            && w[2] == '<' as u32
+            && w[3] != '=' as u32
+            && w[3] != '!' as u32
+    })
+}
+
+struct Parser {
+    chars: Vec<u32>,
+    pos: usize,
+    unicode: bool,
+    /// `\k` is a named backreference only if there's a named group.
+    has_named_groups: bool,
+}
+
+enum ClassAtom {
+    Char(u32),
+    Escape(ClassEscape),
+}
+
+impl Parser {
+    fn error<T>(&self, msg: &'static str) -> PResult<T> {
+        Err(Error { pos: self.pos, msg })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.peek_at(0)
+    }
+
+    /// Lone surrogates are returned as U+FFFD.
+    fn peek_at(&self, n: usize) -> Option<char> {
+        self.chars
+            .get(self.pos + n)
+            .map(|&c| std::char::from_u32(c).unwrap_or('\u{fffd}'))
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char, msg: &'static str) -> PResult<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            self.error(msg)
+        }
+    }
+
+    fn bump(&mut self) -> PResult<u32> {
+        match self.chars.get(self.pos) {
+            Some(&c) => {
+                self.pos += 1;
+                Ok(c)
+            }
+            None => self.error("unexpected end of pattern"),
+        }
+    }
+
+    fn parse_disjunction(&mut self) -> PResult<Disjunction> {
+        let mut alternatives = vec![self.parse_alternative()?];
+        while self.eat('|') {
+            alternatives.push(self.parse_alternative()?);
+        }
+
+        Ok(Disjunction { alternatives })
+    }
+
+    fn parse_alternative(&mut self) -> PResult<Alternative> {
+        let mut terms = vec![];
+
+        loop {
+            match self.peek() {
+                None | Some('|') | Some(')') => break,
+                _ => {}
+            }
+
+            terms.push(self.parse_term()?);
+        }
+
+        Ok(terms)
+    }
+
+    fn parse_term(&mut self) -> PResult<Term> {
+        match self.peek() {
+            Some('^') => {
+                self.pos += 1;
+                return Ok(Term::Assertion(Assertion::Start));
+            }
+            Some('$') => {
+                self.pos += 1;
+                return Ok(Term::Assertion(Assertion::End));
+            }
+            Some('\\') => match self.peek_at(1) {
+                Some('b') => {
+                    self.pos += 2;
+                    return Ok(Term::Assertion(Assertion::WordBoundary));
+                }
+                Some('B') => {
+                    self.pos += 2;
+                    return Ok(Term::Assertion(Assertion::NotWordBoundary));
+                }
+                _ => {}
+            },
+            _ => {}
+        }
+
+        let atom = self.parse_atom()?;
+        let quantifier = self.parse_quantifier()?;
+
+        if quantifier.is_some() && self.unicode {
+            if let Atom::Group(ref group) = atom {
+                match group.kind {
+                    GroupKind::Capturing(..) | GroupKind::NonCapturing => {}
+                    _ => return self.error("lookaround cannot be quantified"),
+                }
+            }
+        }
+
+        Ok(Term::Atom(atom, quantifier))
+    }
+
+    fn parse_quantifier(&mut self) -> PResult<Option<Quantifier>> {
+        let (min, max) = match self.peek() {
+            Some('*') => {
+                self.pos += 1;
+                (0, None)
+            }
+            Some('+') => {
+                self.pos += 1;
+                (1, None)
+            }
+            Some('?') => {
+                self.pos += 1;
+                (0, Some(1))
+            }
+            Some('{') => match self.try_parse_braced_quantifier() {
+                Some((min, max)) => (min, max),
+                None if self.unicode => return self.error("incomplete quantifier"),
+                // Annex B: `{` is a literal.
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        if let Some(max) = max {
+            if max < min {
+                return self.error("numbers out of order in quantifier");
+            }
+        }
+
+        let lazy = self.eat('?');
+        Ok(Some(Quantifier { min, max, lazy }))
+    }
+
+    /// Parses `{n}`, `{n,}` or `{n,m}`.
+    ///
+    /// Returns [None] without advancing if it's not a quantifier.
+    fn try_parse_braced_quantifier(&mut self) -> Option<(u32, Option<u32>)> {
+        let start = self.pos;
+        self.pos += 1;
+
+        let res = (|| {
+            let min = self.parse_decimal()?;
+            let max = if self.eat(',') {
+                if self.peek() == Some('}') {
+                    None
+                } else {
+                    Some(self.parse_decimal()?)
+                }
+            } else {
+                Some(min)
+            };
+
+            if self.eat('}') {
+                Some((min, max))
+            } else {
+                None
+            }
+        })();
+
+        if res.is_none() {
+            self.pos = start;
+        }
+        res
+    }
+
+    fn parse_decimal(&mut self) -> Option<u32> {
+        let mut value: Option<u32> = None;
+        while let Some(d) = self.peek().and_then(|c| c.to_digit(10)) {
+            self.pos += 1;
+            value = Some(value.unwrap_or(0).saturating_mul(10).saturating_add(d));
+        }
+        value
+    }
+
+    fn parse_atom(&mut self) -> PResult<Atom> {
+        let c = match self.peek() {
+            Some(c) => c,
+            None => return self.error("unexpected end of pattern"),
+        };
+
+        match c {
+            '.' => {
+                self.pos += 1;
+                Ok(Atom::Dot)
+            }
+            '(' => self.parse_group(),
+            '[' => self.parse_class().map(Atom::Class),
+            '\\' => {
+                self.pos += 1;
+                self.parse_atom_escape()
+            }
+            '*' | '+' | '?' => self.error("nothing to repeat"),
+            '{' => {
+                if self.unicode {
+                    return self.error("lone quantifier brackets");
+                }
+                if self.try_parse_braced_quantifier().is_some() {
+                    return self.error("nothing to repeat");
+                }
+                self.pos += 1;
+                Ok(Atom::Char(c as u32))
+            }
+            '}' | ']' if self.unicode => self.error("lone quantifier brackets"),
+            _ => Ok(Atom::Char(self.bump()?)),
+        }
+    }
+
+    fn parse_group(&mut self) -> PResult<Atom> {
+        self.expect('(', "expected `(`")?;
+
+        let kind = if self.eat('?') {
+            if self.eat(':') {
+                GroupKind::NonCapturing
+            } else if self.eat('=') {
+                GroupKind::Lookahead
+            } else if self.eat('!') {
+                GroupKind::NegativeLookahead
+            } else if self.eat('<') {
+                if self.eat('=') {
+                    GroupKind::Lookbehind
+                } else if self.eat('!') {
+                    GroupKind::NegativeLookbehind
+                } else {
+                    GroupKind::Capturing(Some(self.parse_group_name()?))
+                }
+            } else {
+                return self.error("invalid group");
+            }
+        } else {
+            GroupKind::Capturing(None)
+        };
+
+        let body = self.parse_disjunction()?;
+        self.expect(')', "unterminated group")?;
+
+        Ok(Atom::Group(box Group { kind, body }))
+    }
+
+    /// Parses `name>` of `(?<name>` or `\k<name>`.
+    fn parse_group_name(&mut self) -> PResult<String> {
+        let mut name = String::new();
+
+        loop {
+            let c = self.bump()?;
+            if c == '>' as u32 {
+                break;
+            }
+
+            match std::char::from_u32(c) {
+                Some(c) if c == '$' || c == '_' || c.is_alphanumeric() => name.push(c),
+                _ => return self.error("invalid capture group name"),
+            }
+        }
+
+        if name.is_empty() || name.starts_with(|c: char| c.is_numeric()) {
+            return self.error("invalid capture group name");
+        }
+
+        Ok(name)
+    }
+
+    /// Parses an escape after `\`.
+    fn parse_atom_escape(&mut self) -> PResult<Atom> {
+        let c = match self.peek() {
+            Some(c) => c,
+            None => return self.error("`\\` at end of pattern"),
+        };
+
+        match c {
+            '1'..='9' => {
+                let n = self.parse_decimal().unwrap();
+                Ok(Atom::Backreference(n))
+            }
+            'k' if self.unicode || self.has_named_groups => {
+                self.pos += 1;
+                self.expect('<', "invalid named reference")?;
+                Ok(Atom::NamedBackreference(self.parse_group_name()?))
+            }
+            _ => match self.parse_class_escape(false)? {
+                ClassAtom::Char(c) => Ok(Atom::Char(c)),
+                ClassAtom::Escape(e) => Ok(Atom::Escape(e)),
+            },
+        }
+    }
+
+    fn parse_class(&mut self) -> PResult<Class> {
+        self.expect('[', "expected `[`")?;
+        let negated = self.eat('^');
+        let mut items = vec![];
+
+        loop {
+            match self.peek() {
+                Some(']') => {
+                    self.pos += 1;
+                    break;
+                }
+                None => return self.error("unterminated character class"),
+                _ => {}
+            }
+
+            let first = self.parse_class_atom()?;
+
+            let is_range = self.peek() == Some('-')
+                && self.peek_at(1) != Some(']')
+                && self.pos + 1 < self.chars.len();
+            if !is_range {
+                items.push(first.into());
+                continue;
+            }
+
+            self.pos += 1;
+            let second = self.parse_class_atom()?;
+
+            match (first, second) {
+                (ClassAtom::Char(start), ClassAtom::Char(end)) => {
+                    if start > end {
+                        return self.error("range out of order in character class");
+                    }
+                    items.push(ClassItem::Range(start, end));
+                }
+                _ if self.unicode => return self.error("invalid character class"),
+                // Annex B: `[\d-a]` is `\d`, `-` and `a`.
+                (first, second) => {
+                    items.push(first.into());
+                    items.push(ClassItem::Char('-' as u32));
+                    items.push(second.into());
+                }
+            }
+        }
+
+        Ok(Class { negated, items })
+    }
+
+    fn parse_class_atom(&mut self) -> PResult<ClassAtom> {
+        let c = self.bump()?;
+        if c != '\\' as u32 {
+            return Ok(ClassAtom::Char(c));
+        }
+
+        match self.peek() {
+            Some('b') => {
+                self.pos += 1;
+                Ok(ClassAtom::Char(0x08))
+            }
+            Some('-') => {
+                self.pos += 1;
+                Ok(ClassAtom::Char('-' as u32))
+            }
+            Some('1'..='9') if self.unicode => self.error("invalid class escape"),
+            Some('k') if self.unicode => self.error("invalid class escape"),
+            _ => self.parse_class_escape(true),
+        }
+    }
+
+    /// Parses an escape after `\`, which is not a backreference.
+    fn parse_class_escape(&mut self, in_class: bool) -> PResult<ClassAtom> {
+        let start = self.pos;
+        let c = match std::char::from_u32(self.bump()?) {
+            Some(c) => c,
+            None => return Ok(ClassAtom::Char(self.chars[start])),
+        };
+
+        let escape = |e| Ok(ClassAtom::Escape(e));
+
+        let cp = match c {
+            'd' => return escape(ClassEscape::Digit { negated: false }),
+            'D' => return escape(ClassEscape::Digit { negated: true }),
+            's' => return escape(ClassEscape::Space { negated: false }),
+            'S' => return escape(ClassEscape::Space { negated: true }),
+            'w' => return escape(ClassEscape::Word { negated: false }),
+            'W' => return escape(ClassEscape::Word { negated: true }),
+            'p' | 'P' if self.unicode => {
+                let (name, value) = self.parse_property()?;
+                return escape(ClassEscape::Property {
+                    negated: c == 'P',
+                    name,
+                    value,
+                });
+            }
+
+            'f' => 0x0c,
+            'n' => 0x0a,
+            'r' => 0x0d,
+            't' => 0x09,
+            'v' => 0x0b,
+
+            'c' => match self.peek() {
+                Some(l) if l.is_ascii_alphabetic() => {
+                    self.pos += 1;
+                    l as u32 % 32
+                }
+                // Annex B
+                Some(l) if in_class && !self.unicode && (l.is_ascii_digit() || l == '_') => {
+                    self.pos += 1;
+                    l as u32 % 32
+                }
+                _ if self.unicode => return self.error("invalid unicode escape"),
+                // Annex B: `\c` is `\` and `c`.
+                _ => {
+                    self.pos -= 1;
+                    '\\' as u32
+                }
+            },
+
+            '0' if !self.peek().map(|c| c.is_ascii_digit()).unwrap_or(false) => 0,
+            '0'..='7' if !self.unicode => {
+                // Annex B: legacy octal escape.
+                let mut value = c.to_digit(8).unwrap();
+                for _ in 0..2 {
+                    match self.peek().and_then(|c| c.to_digit(8)) {
+                        Some(d) if value * 8 + d <= 0o377 => {
+                            self.pos += 1;
+                            value = value * 8 + d;
+                        }
+                        _ => break,
+                    }
+                }
+                value
+            }
+
+            'x' => match self.parse_hex(2) {
+                Some(v) => v,
+                None if self.unicode => return self.error("invalid escape"),
+                None => 'x' as u32,
+            },
+            'u' => match self.parse_unicode_escape()? {
+                Some(v) => v,
+                None if self.unicode => return self.error("invalid unicode escape"),
+                None => 'u' as u32,
+            },
+
+            '^' | '$' | '\\' | '.' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|'
+            | '/' => c as u32,
+            '-' if in_class => c as u32,
+
+            _ if self.unicode => return self.error("invalid escape"),
+            // Annex B: identity escape.
+            _ => c as u32,
+        };
+
+        Ok(ClassAtom::Char(cp))
+    }
+
+    /// Parses `{name=value}` or `{name}` of `\p{name=value}`.
+    fn parse_property(&mut self) -> PResult<(String, Option<String>)> {
+        self.expect('{', "invalid property name")?;
+
+        let mut name = String::new();
+        let mut value = None;
+        loop {
+            match self.peek() {
+                Some('}') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('=') if value.is_none() => {
+                    self.pos += 1;
+                    value = Some(String::new());
+                }
+                Some(c) if c.is_ascii_alphanumeric() || c == '_' => {
+                    self.pos += 1;
+                    match value {
+                        Some(ref mut value) => value.push(c),
+                        None => name.push(c),
+                    }
+                }
+                _ => return self.error("invalid property name"),
+            }
+        }
+
+        if name.is_empty() || value.as_ref().map(|v| v.is_empty()).unwrap_or(false) {
+            return self.error("invalid property name");
+        }
+
+        Ok((name, value))
+    }
+
+    /// Parses `count` hex digits. Returns [None] without advancing if there
+    /// are not enough digits.
+    fn parse_hex(&mut self, count: usize) -> Option<u32> {
+        let mut value = 0;
+        for i in 0..count {
+            value = value * 16 + self.peek_at(i)?.to_digit(16)?;
+        }
+
+        self.pos += count;
+        Some(value)
+    }
+
+    /// Parses an escape after `\u`.
+    fn parse_unicode_escape(&mut self) -> PResult<Option<u32>> {
+        if self.unicode && self.peek() == Some('{') {
+            let start = self.pos;
+            self.pos += 1;
+
+            let mut value: u32 = 0;
+            let mut digits = 0;
+            while let Some(d) = self.peek().and_then(|c| c.to_digit(16)) {
+                self.pos += 1;
+                digits += 1;
+                value = value.saturating_mul(16).saturating_add(d);
+            }
+
+            if digits == 0 || value > 0x10ffff || !self.eat('}') {
+                self.pos = start;
+                return self.error("invalid unicode escape");
+            }
+            return Ok(Some(value));
+        }
+
+        let lead = match self.parse_hex(4) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        // `😀` is a code point in unicode mode.
+        if self.unicode
+            && is_lead_surrogate(lead)
+            && self.peek() == Some('\\')
+            && self.peek_at(1) == Some('u')
+        {
+            let start = self.pos;
+            self.pos += 2;
+            match self.parse_hex(4) {
+                Some(trail) if is_trail_surrogate(trail) => {
+                    return Ok(Some((lead - 0xd800) * 0x400 + (trail - 0xdc00) + 0x10000))
+                }
+                _ => self.pos = start,
+            }
+        }
+
+        Ok(Some(lead))
+    }
+}
+
+impl From<ClassAtom> for ClassItem {
+    fn from(atom: ClassAtom) -> Self {
+        match atom {
+            ClassAtom::Char(c) => ClassItem::Char(c),
+            ClassAtom::Escape(e) => ClassItem::Escape(e),
+        }
+    }
+}
+
+pub(crate) fn is_lead_surrogate(c: u32) -> bool {
+    0xd800 <= c && c <= 0xdbff
+}
+
+pub(crate) fn is_trail_surrogate(c: u32) -> bool {
+    0xdc00 <= c && c <= 0xdfff
+}
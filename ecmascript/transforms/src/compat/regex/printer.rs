@@ -0,0 +1,229 @@
+use super::ast::*;
+use std::fmt::Write;
+
+/// Prints `d` as the pattern of a regex literal.
+///
+/// If `unicode` is false, code points above U+FFFF are printed as surrogate
+/// pairs.
+pub(crate) fn print(d: &Disjunction, unicode: bool) -> String {
+    let mut p = Printer {
+        buf: String::new(),
+        unicode,
+    };
+    p.disjunction(d);
+    p.buf
+}
+
+struct Printer {
+    buf: String,
+    unicode: bool,
+}
+
+impl Printer {
+    fn disjunction(&mut self, d: &Disjunction) {
+        for (i, alt) in d.alternatives.iter().enumerate() {
+            if i != 0 {
+                self.buf.push('|');
+            }
+
+            for (j, term) in alt.iter().enumerate() {
+                let next = alt.get(j + 1);
+                self.term(term, next);
+            }
+        }
+    }
+
+    fn term(&mut self, term: &Term, next: Option<&Term>) {
+        match term {
+            Term::Assertion(a) => self.buf.push_str(match a {
+                Assertion::Start => "^",
+                Assertion::End => "$",
+                Assertion::WordBoundary => "\\b",
+                Assertion::NotWordBoundary => "\\B",
+            }),
+            Term::Atom(atom, quantifier) => {
+                // `\1` followed by `2` should not be printed as `\12`, and a
+                // quantifier should apply to both halves of a surrogate pair.
+                let needs_group = match atom {
+                    Atom::Backreference(..) => {
+                        quantifier.is_none()
+                            && match next {
+                                Some(Term::Atom(Atom::Char(c), _)) => {
+                                    '0' as u32 <= *c && *c <= '9' as u32
+                                }
+                                _ => false,
+                            }
+                    }
+                    Atom::Char(c) => !self.unicode && *c > 0xffff && quantifier.is_some(),
+                    _ => false,
+                };
+
+                if needs_group {
+                    self.buf.push_str("(?:");
+                }
+                self.atom(atom);
+                if needs_group {
+                    self.buf.push(')');
+                }
+
+                if let Some(q) = quantifier {
+                    self.quantifier(q);
+                }
+            }
+        }
+    }
+
+    fn quantifier(&mut self, q: &Quantifier) {
+        match (q.min, q.max) {
+            (0, None) => self.buf.push('*'),
+            (1, None) => self.buf.push('+'),
+            (0, Some(1)) => self.buf.push('?'),
+            (min, None) => {
+                let _ = write!(self.buf, "{{{},}}", min);
+            }
+            (min, Some(max)) if min == max => {
+                let _ = write!(self.buf, "{{{}}}", min);
+            }
+            (min, Some(max)) => {
+                let _ = write!(self.buf, "{{{},{}}}", min, max);
+            }
+        }
+
+        if q.lazy {
+            self.buf.push('?');
+        }
+    }
+
+    fn atom(&mut self, atom: &Atom) {
+        match atom {
+            Atom::Char(c) => self.char(*c, false),
+            Atom::Dot => self.buf.push('.'),
+            Atom::Class(class) => self.class(class),
+            Atom::Escape(e) => self.escape(e),
+            Atom::Group(group) => {
+                self.buf.push('(');
+                match group.kind {
+                    GroupKind::Capturing(None) => {}
+                    GroupKind::Capturing(Some(ref name)) => {
+                        let _ = write!(self.buf, "?<{}>", name);
+                    }
+                    GroupKind::NonCapturing => self.buf.push_str("?:"),
+                    GroupKind::Lookahead => self.buf.push_str("?="),
+                    GroupKind::NegativeLookahead => self.buf.push_str("?!"),
+                    GroupKind::Lookbehind => self.buf.push_str("?<="),
+                    GroupKind::NegativeLookbehind => self.buf.push_str("?<!"),
+                }
+                self.disjunction(&group.body);
+                self.buf.push(')');
+            }
+            Atom::Backreference(n) => {
+                let _ = write!(self.buf, "\\{}", n);
+            }
+            Atom::NamedBackreference(name) => {
+                let _ = write!(self.buf, "\\k<{}>", name);
+            }
+        }
+    }
+
+    fn class(&mut self, class: &Class) {
+        self.buf.push('[');
+        if class.negated {
+            self.buf.push('^');
+        }
+
+        for item in &class.items {
+            match *item {
+                ClassItem::Char(c) => self.char(c, true),
+                ClassItem::Range(start, end) => {
+                    self.char(start, true);
+                    self.buf.push('-');
+                    self.char(end, true);
+                }
+                ClassItem::Escape(ref e) => self.escape(e),
+            }
+        }
+
+        self.buf.push(']');
+    }
+
+    fn escape(&mut self, e: &ClassEscape) {
+        match e {
+            ClassEscape::Digit { negated } => {
+                self.buf.push_str(if *negated { "\\D" } else { "\\d" })
+            }
+            ClassEscape::Space { negated } => {
+                self.buf.push_str(if *negated { "\\S" } else { "\\s" })
+            }
+            ClassEscape::Word { negated } => {
+                self.buf.push_str(if *negated { "\\W" } else { "\\w" })
+            }
+            ClassEscape::Property {
+                negated,
+                name,
+                value,
+            } => {
+                self.buf.push_str(if *negated { "\\P{" } else { "\\p{" });
+                self.buf.push_str(name);
+                if let Some(value) = value {
+                    self.buf.push('=');
+                    self.buf.push_str(value);
+                }
+                self.buf.push('}');
+            }
+        }
+    }
+
+    fn char(&mut self, c: u32, in_class: bool) {
+        let is_syntax = |c: char| {
+            if in_class {
+                match c {
+                    '\\' | ']' | '[' | '^' | '-' | '/' => true,
+                    _ => false,
+                }
+            } else {
+                match c {
+                    '^' | '$' | '\\' | '.' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{'
+                    | '}' | '|' | '/' => true,
+                    _ => false,
+                }
+            }
+        };
+
+        match c {
+            0x09 => self.buf.push_str("\\t"),
+            0x0a => self.buf.push_str("\\n"),
+            0x0b => self.buf.push_str("\\v"),
+            0x0c => self.buf.push_str("\\f"),
+            0x0d => self.buf.push_str("\\r"),
+            0x20..=0x7e => {
+                let c = c as u8 as char;
+                if is_syntax(c) {
+                    self.buf.push('\\');
+                }
+                self.buf.push(c);
+            }
+            0..=0xff => {
+                let _ = write!(self.buf, "\\x{:02X}", c);
+            }
+            // A surrogate followed by `\u` would be a surrogate pair in unicode mode.
+            0xd800..=0xdfff if self.unicode => {
+                let _ = write!(self.buf, "\\u{{{:X}}}", c);
+            }
+            0x100..=0xffff => {
+                let _ = write!(self.buf, "\\u{:04X}", c);
+            }
+            _ if self.unicode => {
+                let _ = write!(self.buf, "\\u{{{:X}}}", c);
+            }
+            _ => {
+                let c = c - 0x10000;
+                let _ = write!(
+                    self.buf,
+                    "\\u{:04X}\\u{:04X}",
+                    0xd800 + (c >> 10),
+                    0xdc00 + (c & 0x3ff)
+                );
+            }
+        }
+    }
+}
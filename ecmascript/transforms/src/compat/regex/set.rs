@@ -0,0 +1,122 @@
+use super::ast::ClassEscape;
+
+pub(crate) const MAX_CODE_POINT: u32 = 0x10ffff;
+
+/// A set of code points, stored as sorted and non-overlapping ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CodePointSet {
+    ranges: Vec<(u32, u32)>,
+}
+
+impl CodePointSet {
+    pub fn from_ranges<I>(ranges: I) -> Self
+    where
+        I: IntoIterator<Item = (u32, u32)>,
+    {
+        let mut set = CodePointSet {
+            ranges: ranges.into_iter().collect(),
+        };
+        set.normalize();
+        set
+    }
+
+    /// Inclusive ranges in ascending order.
+    pub fn ranges(&self) -> &[(u32, u32)] {
+        &self.ranges
+    }
+
+    pub fn add(&mut self, start: u32, end: u32) {
+        self.ranges.push((start, end));
+        self.normalize();
+    }
+
+    pub fn union(&mut self, other: &CodePointSet) {
+        self.ranges.extend_from_slice(&other.ranges);
+        self.normalize();
+    }
+
+    pub fn complement(&self) -> Self {
+        let mut ranges = vec![];
+        let mut next = 0;
+        for &(start, end) in &self.ranges {
+            if start > next {
+                ranges.push((next, start - 1));
+            }
+            next = end + 1;
+        }
+        if next <= MAX_CODE_POINT {
+            ranges.push((next, MAX_CODE_POINT));
+        }
+
+        CodePointSet { ranges }
+    }
+
+    fn normalize(&mut self) {
+        self.ranges.sort();
+
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(self.ranges.len());
+        for &(start, end) in &self.ranges {
+            match merged.last_mut() {
+                Some(last) if start <= last.1.saturating_add(1) => {
+                    last.1 = last.1.max(end);
+                }
+                _ => merged.push((start, end)),
+            }
+        }
+
+        self.ranges = merged;
+    }
+}
+
+/// Code points matched by `\d`, `\s` or `\w`.
+///
+/// Returns [None] for unknown unicode properties.
+pub(crate) fn escape_set(e: &ClassEscape) -> Option<CodePointSet> {
+    let (negated, set) = match *e {
+        ClassEscape::Digit { negated } => (
+            negated,
+            CodePointSet::from_ranges(vec![('0' as u32, '9' as u32)]),
+        ),
+        ClassEscape::Space { negated } => (negated, space()),
+        ClassEscape::Word { negated } => (
+            negated,
+            CodePointSet::from_ranges(vec![
+                ('0' as u32, '9' as u32),
+                ('A' as u32, 'Z' as u32),
+                ('_' as u32, '_' as u32),
+                ('a' as u32, 'z' as u32),
+            ]),
+        ),
+        ClassEscape::Property {
+            negated,
+            ref name,
+            ref value,
+        } => (
+            negated,
+            super::unicode::property(name, value.as_ref().map(|s| &**s))?,
+        ),
+    };
+
+    Some(if negated { set.complement() } else { set })
+}
+
+/// `WhiteSpace` and `LineTerminator`.
+fn space() -> CodePointSet {
+    CodePointSet::from_ranges(vec![
+        (0x09, 0x0d),
+        (0x20, 0x20),
+        (0xa0, 0xa0),
+        (0x1680, 0x1680),
+        (0x2000, 0x200a),
+        (0x2028, 0x2029),
+        (0x202f, 0x202f),
+        (0x205f, 0x205f),
+        (0x3000, 0x3000),
+        (0xfeff, 0xfeff),
+    ])
+}
+
+/// Code points matched by `.` without the `s` flag.
+pub(crate) fn dot() -> CodePointSet {
+    CodePointSet::from_ranges(vec![(0x0a, 0x0a), (0x0d, 0x0d), (0x2028, 0x2029)]).complement()
+}
@@ -0,0 +1,76 @@
+//! Unicode properties for `\p{...}`.
+//!
+//! `unicode_properties.json` can be regenerated using
+//! `scripts/unicode-property-data-gen.js`.
+
+use super::set::CodePointSet;
+use fxhash::FxHashMap;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Data {
+    aliases: Aliases,
+    general_category: FxHashMap<String, Vec<u32>>,
+    binary: FxHashMap<String, Vec<u32>>,
+    script: FxHashMap<String, Vec<u32>>,
+    script_extensions: FxHashMap<String, Vec<u32>>,
+}
+
+/// Maps aliases to canonical names.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Aliases {
+    general_category: FxHashMap<String, String>,
+    binary: FxHashMap<String, String>,
+    script: FxHashMap<String, String>,
+}
+
+static DATA: Lazy<Data> = Lazy::new(|| {
+    serde_json::from_str(include_str!("unicode_properties.json"))
+        .expect("failed to parse unicode property data")
+});
+
+/// Returns code points matched by `\p{name=value}` or `\p{name}`.
+pub(crate) fn property(name: &str, value: Option<&str>) -> Option<CodePointSet> {
+    let data = &*DATA;
+
+    let (table, aliases, value) = match (name, value) {
+        ("General_Category", Some(value)) | ("gc", Some(value)) => (
+            &data.general_category,
+            &data.aliases.general_category,
+            value,
+        ),
+        ("Script", Some(value)) | ("sc", Some(value)) => {
+            (&data.script, &data.aliases.script, value)
+        }
+        ("Script_Extensions", Some(value)) | ("scx", Some(value)) => {
+            (&data.script_extensions, &data.aliases.script, value)
+        }
+        (_, Some(..)) => return None,
+
+        // `\p{Lu}`
+        (name, None) => {
+            if let Some(ranges) = lookup(&data.binary, &data.aliases.binary, name) {
+                return Some(ranges);
+            }
+
+            (&data.general_category, &data.aliases.general_category, name)
+        }
+    };
+
+    lookup(table, aliases, value)
+}
+
+fn lookup(
+    table: &FxHashMap<String, Vec<u32>>,
+    aliases: &FxHashMap<String, String>,
+    name: &str,
+) -> Option<CodePointSet> {
+    let name = aliases.get(name).map(|s| &**s).unwrap_or(name);
+
+    table
+        .get(name)
+        .map(|ranges| CodePointSet::from_ranges(ranges.chunks(2).map(|r| (r[0], r[1]))))
+}
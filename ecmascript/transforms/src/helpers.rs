@@ -218,6 +218,7 @@ define_helpers!(Helpers {
         set_prototype_of,
         is_native_function
     ),
+    wrap_reg_exp: (wrap_native_super, inherits),
 
     class_private_field_destructure: (),
 });
@@ -496,7 +496,14 @@ macro_rules! track_ident {
                 let old = self.ident_type;
                 self.ident_type = IdentType::Ref;
 
-                let s = s.fold_children(self);
+                // Exported names are not references.
+                let s = match s {
+                    ExportSpecifier::Named(s) => ExportSpecifier::Named(ExportNamedSpecifier {
+                        orig: s.orig.fold_with(self),
+                        ..s
+                    }),
+                    _ => s.fold_children(self),
+                };
 
                 self.ident_type = old;
 
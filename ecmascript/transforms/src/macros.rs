@@ -294,6 +294,7 @@ macro_rules! noop_fold_type {
         noop_fold_type!($F, TsSignatureDecl);
         noop_fold_type!($F, TsThisType);
         noop_fold_type!($F, TsThisTypeOrIdent);
+        noop_fold_type!($F, TsTupleElement);
         noop_fold_type!($F, TsTupleType);
         noop_fold_type!($F, TsType);
         noop_fold_type!($F, TsTypeAliasDecl);
@@ -368,6 +369,7 @@ macro_rules! noop_visit_type {
         noop_visit_type!($F, TsSignatureDecl);
         noop_visit_type!($F, TsThisType);
         noop_visit_type!($F, TsThisTypeOrIdent);
+        noop_visit_type!($F, TsTupleElement);
         noop_visit_type!($F, TsTupleType);
         noop_visit_type!($F, TsType);
         noop_visit_type!($F, TsTypeAliasDecl);
@@ -3,4 +3,6 @@ pub mod util;
 pub mod amd;
 pub mod common_js;
 pub mod import_analysis;
+pub mod path;
+pub mod system_js;
 pub mod umd;
@@ -70,6 +70,18 @@ impl Fold<Module> for Amd {
                 ModuleItem::ModuleDecl(decl) => decl,
             };
 
+            // export = foo;
+            //  -> export default foo;
+            let decl = match decl {
+                ModuleDecl::TsExportAssignment(export) => {
+                    ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                        span: export.span,
+                        expr: export.expr,
+                    })
+                }
+                _ => decl,
+            };
+
             match decl {
                 ModuleDecl::Import(import) => self.scope.insert_import(import),
 
@@ -391,6 +403,38 @@ impl Fold<Module> for Amd {
                     }
                 }
 
+                ModuleDecl::TsImportEquals(TsImportEqualsDecl {
+                    is_export,
+                    id,
+                    module_ref: TsModuleRef::TsExternalModuleRef(TsExternalModuleRef { expr, .. }),
+                    ..
+                }) => {
+                    // import foo = require('foo');
+                    //  -> define(['foo'], function (foo) {})
+                    self.scope.insert_import_equals(id.clone(), expr.value);
+
+                    if is_export {
+                        has_export = true;
+                        if !self.config.config.strict && !emitted_esmodule {
+                            emitted_esmodule = true;
+                            stmts.push(define_es_module(exports_ident.clone()));
+                        }
+                        exports.push(id.sym.clone());
+                        initialized.insert(id.sym.clone());
+
+                        // exports.foo = foo;
+                        extra_stmts.push(
+                            AssignExpr {
+                                span: DUMMY_SP,
+                                left: PatOrExpr::Expr(box exports_ident.clone().member(id.clone())),
+                                op: op!("="),
+                                right: box Expr::Ident(id).fold_with(self),
+                            }
+                            .into_stmt(),
+                        );
+                    }
+                }
+
                 ModuleDecl::TsImportEquals(..)
                 | ModuleDecl::TsExportAssignment(..)
                 | ModuleDecl::TsNamespaceExport(..) => {}
@@ -423,6 +423,70 @@ impl Fold<Vec<ModuleItem>> for CommonJs {
                         _ => unreachable!(),
                     }
                 }
+
+                ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(TsImportEqualsDecl {
+                    span,
+                    is_export,
+                    id,
+                    module_ref: TsModuleRef::TsExternalModuleRef(TsExternalModuleRef { expr, .. }),
+                    ..
+                })) => {
+                    // import foo = require('foo');
+                    //  -> var foo = require('foo');
+                    extra_stmts.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(VarDecl {
+                        span,
+                        kind: VarDeclKind::Var,
+                        decls: vec![VarDeclarator {
+                            span: DUMMY_SP,
+                            name: Pat::Ident(id.clone()),
+                            init: Some(box make_require_call(self.root_mark, expr.value)),
+                            definite: false,
+                        }],
+                        declare: false,
+                    }))));
+
+                    if is_export {
+                        if !self.config.strict && !emitted_esmodule {
+                            emitted_esmodule = true;
+
+                            stmts.push(ModuleItem::Stmt(define_es_module(quote_ident!("exports"))));
+                        }
+                        exports.push(id.sym.clone());
+                        initialized.insert(id.sym.clone());
+
+                        // exports.foo = foo;
+                        extra_stmts.push(
+                            AssignExpr {
+                                span: DUMMY_SP,
+                                left: PatOrExpr::Expr(
+                                    box quote_ident!("exports").member(id.clone()),
+                                ),
+                                op: op!("="),
+                                right: box Expr::Ident(id),
+                            }
+                            .into_stmt()
+                            .into(),
+                        );
+                    }
+                }
+
+                ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(export)) => {
+                    // export = foo;
+                    //  -> module.exports = foo;
+                    extra_stmts.push(
+                        AssignExpr {
+                            span: export.span,
+                            left: PatOrExpr::Expr(
+                                box quote_ident!("module").member(quote_ident!("exports")),
+                            ),
+                            op: op!("="),
+                            right: export.expr.fold_with(self),
+                        }
+                        .into_stmt()
+                        .into(),
+                    );
+                }
+
                 _ => extra_stmts.push(item.fold_with(self)),
             }
         }
@@ -0,0 +1,447 @@
+//! Rewriting of module specifiers.
+//!
+//! Specifiers are resolved against the file system with a [Resolve]
+//! implementation, so that the output works with module loaders which do not
+//! search for extensions, like native es modules of node.js and browsers.
+
+use crate::{pass::Pass, util::resolve::Resolve};
+use anyhow::{bail, Error};
+use std::{
+    borrow::Cow,
+    path::{Component, Path, PathBuf},
+};
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Fold, FoldWith};
+use swc_ecma_ast::*;
+
+/// Extensions of files which are compiled to javascript.
+const SCRIPT_EXTENSIONS: &[&str] = &["js", "mjs", "cjs", "jsx", "ts", "tsx"];
+
+/// Rewrites specifiers of imports, exports and dynamic imports in the file at
+/// `base`.
+///
+/// Specifiers which cannot be resolved are not modified.
+pub fn import_rewriter<R>(base: PathBuf, resolver: R, config: Config) -> impl Pass
+where
+    R: Resolve,
+{
+    ImportRewriter {
+        base,
+        resolver,
+        config,
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Config {
+    /// If set, relative specifiers are resolved and the extension of script
+    /// files is replaced with it. e.g. `js` or `mjs`.
+    pub extension: Option<String>,
+}
+
+struct ImportRewriter<R: Resolve> {
+    base: PathBuf,
+    resolver: R,
+    config: Config,
+}
+
+impl<R: Resolve> ImportRewriter<R> {
+    fn rewrite(&self, src: Str) -> Str {
+        match self.resolve(&src.value) {
+            Some(value) => Str {
+                value,
+                has_escape: false,
+                ..src
+            },
+            None => src,
+        }
+    }
+
+    fn resolve(&self, specifier: &str) -> Option<JsWord> {
+        let is_relative = specifier.starts_with("./") || specifier.starts_with("../");
+        if is_relative && self.config.extension.is_none() {
+            return None;
+        }
+
+        let resolved = self.resolver.resolve(&self.base, specifier).ok()?;
+        let dir = self.base.parent().unwrap_or_else(|| Path::new(""));
+        let mut path = relative_path(dir, &resolved)?;
+
+        let is_script = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| SCRIPT_EXTENSIONS.contains(&ext))
+            .unwrap_or(false);
+        if is_script {
+            match self.config.extension {
+                Some(ref ext) => {
+                    path.set_extension(ext);
+                }
+                // Aliases are rewritten, but the extension is not added.
+                None if Path::new(specifier).extension().is_none() => {
+                    path.set_extension("");
+                }
+                None => {}
+            }
+        }
+
+        let mut s = path
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        if !s.starts_with("../") {
+            s.insert_str(0, "./");
+        }
+
+        if s == specifier {
+            return None;
+        }
+
+        Some(s.into())
+    }
+}
+
+impl<R: Resolve> Fold<ImportDecl> for ImportRewriter<R> {
+    fn fold(&mut self, i: ImportDecl) -> ImportDecl {
+        ImportDecl {
+            src: self.rewrite(i.src),
+            ..i
+        }
+    }
+}
+
+impl<R: Resolve> Fold<NamedExport> for ImportRewriter<R> {
+    fn fold(&mut self, e: NamedExport) -> NamedExport {
+        NamedExport {
+            src: e.src.map(|src| self.rewrite(src)),
+            ..e
+        }
+    }
+}
+
+impl<R: Resolve> Fold<ExportAll> for ImportRewriter<R> {
+    fn fold(&mut self, e: ExportAll) -> ExportAll {
+        ExportAll {
+            src: self.rewrite(e.src),
+            ..e
+        }
+    }
+}
+
+impl<R: Resolve> Fold<CallExpr> for ImportRewriter<R> {
+    fn fold(&mut self, e: CallExpr) -> CallExpr {
+        let mut e = e.fold_children(self);
+
+        // import('./foo')
+        let is_import = match e.callee {
+            ExprOrSuper::Expr(ref callee) => match **callee {
+                Expr::Ident(Ident {
+                    sym: js_word!("import"),
+                    ..
+                }) => true,
+                _ => false,
+            },
+            _ => false,
+        };
+        if !is_import {
+            return e;
+        }
+
+        if let Some(arg) = e.args.first_mut() {
+            if arg.spread.is_none() {
+                if let Expr::Lit(Lit::Str(ref mut src)) = *arg.expr {
+                    *src = self.rewrite(src.clone());
+                }
+            }
+        }
+
+        e
+    }
+}
+
+/// Returns the path of `to` relative to the directory `from`.
+fn relative_path(from: &Path, to: &Path) -> Option<PathBuf> {
+    let from = normalize(from);
+    let to = normalize(to);
+    if from.is_absolute() != to.is_absolute() {
+        return None;
+    }
+
+    let from: Vec<_> = from.components().collect();
+    let to: Vec<_> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+    if from[common..].iter().any(|c| *c == Component::ParentDir) {
+        return None;
+    }
+
+    let mut path = PathBuf::new();
+    for _ in common..from.len() {
+        path.push("..");
+    }
+    for c in &to[common..] {
+        path.push(c);
+    }
+
+    Some(path)
+}
+
+/// Removes `.` and `..` from `path` without accessing the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(..)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir) | Some(Component::Prefix(..)) => {}
+                _ => normalized.push(".."),
+            },
+            c => normalized.push(c),
+        }
+    }
+
+    normalized
+}
+
+/// Resolves relative specifiers like node.js.
+///
+/// `./foo` is resolved to `./foo`, `./foo.js`, `./foo.ts` or `./foo/index.js`
+/// (and so on), if the file exists. Specifiers of packages are not resolved.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NodeResolver;
+
+impl NodeResolver {
+    fn resolve_as_file(&self, path: &Path) -> Option<PathBuf> {
+        if path.is_file() {
+            return Some(path.to_path_buf());
+        }
+
+        SCRIPT_EXTENSIONS.iter().find_map(|ext| {
+            let mut file = path.as_os_str().to_os_string();
+            file.push(".");
+            file.push(ext);
+            let file = PathBuf::from(file);
+
+            if file.is_file() {
+                Some(file)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl Resolve for NodeResolver {
+    fn resolve(&self, base: &Path, import: &str) -> Result<PathBuf, Error> {
+        let path = Path::new(import);
+        let path = if path.is_absolute() {
+            Cow::Borrowed(path)
+        } else if import.starts_with("./") || import.starts_with("../") {
+            Cow::Owned(base.parent().unwrap_or_else(|| Path::new("")).join(path))
+        } else {
+            bail!("cannot resolve a package: {}", import)
+        };
+
+        if let Some(file) = self.resolve_as_file(&path) {
+            return Ok(file);
+        }
+        if let Some(file) = self.resolve_as_file(&path.join("index")) {
+            return Ok(file);
+        }
+
+        bail!("failed to resolve {} from {}", import, base.display())
+    }
+}
+
+/// Resolves aliases configured like `baseUrl` and `paths` of
+/// `tsconfig.json`, and delegates other specifiers to `inner`.
+#[derive(Debug, Clone)]
+pub struct TsConfigResolver<R: Resolve> {
+    inner: R,
+    base_url: PathBuf,
+    paths: Vec<(String, Vec<String>)>,
+}
+
+impl<R: Resolve> TsConfigResolver<R> {
+    /// `base_url` should be an absolute path, and patterns of `paths` may
+    /// contain one `*`.
+    pub fn new(inner: R, base_url: PathBuf, paths: Vec<(String, Vec<String>)>) -> Self {
+        TsConfigResolver {
+            inner,
+            base_url,
+            paths,
+        }
+    }
+
+    /// Returns the replacements of the pattern with the longest prefix which
+    /// matches `import`, with the text matched by `*`.
+    fn find_pattern<'a>(&'a self, import: &'a str) -> Option<(&'a [String], &'a str)> {
+        let mut found: Option<(usize, &[String], &str)> = None;
+
+        for (pattern, replacements) in &self.paths {
+            let (prefix, matched) = match pattern.find('*') {
+                Some(idx) => {
+                    let (prefix, suffix) = (&pattern[..idx], &pattern[idx + 1..]);
+                    if import.len() < prefix.len() + suffix.len()
+                        || !import.starts_with(prefix)
+                        || !import.ends_with(suffix)
+                    {
+                        continue;
+                    }
+                    (prefix, &import[prefix.len()..import.len() - suffix.len()])
+                }
+                None if pattern == import => (&**pattern, ""),
+                None => continue,
+            };
+
+            let is_longer = found.map(|(len, ..)| prefix.len() > len).unwrap_or(true);
+            if is_longer {
+                found = Some((prefix.len(), &replacements[..], matched));
+            }
+        }
+
+        found.map(|(_, replacements, matched)| (replacements, matched))
+    }
+}
+
+impl<R: Resolve> Resolve for TsConfigResolver<R> {
+    fn resolve(&self, base: &Path, import: &str) -> Result<PathBuf, Error> {
+        if import.starts_with("./") || import.starts_with("../") || Path::new(import).is_absolute()
+        {
+            return self.inner.resolve(base, import);
+        }
+
+        if let Some((replacements, matched)) = self.find_pattern(import) {
+            for replacement in replacements {
+                let path = self.base_url.join(replacement.replacen('*', matched, 1));
+
+                if let Ok(resolved) = self.inner.resolve(base, &path.to_string_lossy()) {
+                    return Ok(resolved);
+                }
+            }
+        }
+
+        // `baseUrl` works like a module directory.
+        let path = self.base_url.join(import);
+        match self.inner.resolve(base, &path.to_string_lossy()) {
+            Ok(resolved) => Ok(resolved),
+            Err(..) => self.inner.resolve(base, import),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn resolve(
+        files: &[&str],
+        resolver: impl Fn(&Path) -> Box<dyn Resolve>,
+        extension: Option<&str>,
+        specifier: &str,
+    ) -> Option<JsWord> {
+        let dir = tempfile::tempdir().unwrap();
+        for file in files {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        let rewriter = ImportRewriter {
+            base: dir.path().join("src/main.ts"),
+            resolver: resolver(dir.path()),
+            config: Config {
+                extension: extension.map(String::from),
+            },
+        };
+
+        rewriter.resolve(specifier)
+    }
+
+    fn node(_: &Path) -> Box<dyn Resolve> {
+        box NodeResolver
+    }
+
+    fn ts_config(dir: &Path) -> Box<dyn Resolve> {
+        box TsConfigResolver::new(
+            NodeResolver,
+            dir.join("src"),
+            vec![
+                ("@lib/*".into(), vec!["../lib/*".into()]),
+                ("@lib/special".into(), vec!["special/index.ts".into()]),
+                ("config".into(), vec!["config/prod".into()]),
+            ],
+        )
+    }
+
+    #[test]
+    fn relative() {
+        let files = &["src/foo.ts", "src/dir/index.tsx", "src/style.css"];
+
+        assert_eq!(
+            resolve(files, node, Some("js"), "./foo"),
+            Some("./foo.js".into())
+        );
+        assert_eq!(
+            resolve(files, node, Some("mjs"), "./foo.ts"),
+            Some("./foo.mjs".into())
+        );
+        assert_eq!(
+            resolve(files, node, Some("js"), "./dir"),
+            Some("./dir/index.js".into())
+        );
+        assert_eq!(resolve(files, node, Some("js"), "./style.css"), None);
+        assert_eq!(resolve(files, node, Some("js"), "./missing"), None);
+        assert_eq!(resolve(files, node, None, "./foo"), None);
+        assert_eq!(resolve(files, node, Some("js"), "react"), None);
+    }
+
+    #[test]
+    fn aliases() {
+        let files = &[
+            "lib/a.ts",
+            "src/special/index.ts",
+            "src/config/prod.js",
+            "src/utils.ts",
+        ];
+
+        assert_eq!(
+            resolve(files, ts_config, None, "@lib/a"),
+            Some("../lib/a".into())
+        );
+        assert_eq!(
+            resolve(files, ts_config, Some("js"), "@lib/a"),
+            Some("../lib/a.js".into())
+        );
+        assert_eq!(
+            resolve(files, ts_config, None, "@lib/special"),
+            Some("./special/index".into())
+        );
+        assert_eq!(
+            resolve(files, ts_config, None, "config"),
+            Some("./config/prod".into())
+        );
+        assert_eq!(
+            resolve(files, ts_config, None, "utils"),
+            Some("./utils".into())
+        );
+        assert_eq!(resolve(files, ts_config, None, "react"), None);
+    }
+
+    #[test]
+    fn relative_path_of() {
+        assert_eq!(
+            relative_path(Path::new("/a/b"), Path::new("/a/c/d.js")),
+            Some(PathBuf::from("../c/d.js"))
+        );
+        assert_eq!(
+            relative_path(Path::new("/a/./b/../b"), Path::new("/a/b/d.js")),
+            Some(PathBuf::from("d.js"))
+        );
+        assert_eq!(relative_path(Path::new("a"), Path::new("/a")), None);
+    }
+}
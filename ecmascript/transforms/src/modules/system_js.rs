@@ -0,0 +1,840 @@
+use super::util::{self, use_strict, ModulePass, Scope};
+use crate::{
+    pass::Pass,
+    util::{find_ids, ExprFactory},
+};
+use fxhash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use std::iter;
+use swc_atoms::{js_word, JsWord};
+use swc_common::{Fold, FoldWith, DUMMY_SP};
+use swc_ecma_ast::*;
+
+/// Compiles es modules to `System.register` calls.
+///
+/// Bindings of a dependency are read from its namespace object, which is
+/// updated by the setter of the dependency. Exported bindings are reported to
+/// the loader with `_export(name, value)` whenever they are assigned.
+pub fn system_js(config: Config) -> impl Pass {
+    SystemJs {
+        config: util::Config {
+            strict_mode: config.strict_mode,
+            ..Default::default()
+        },
+        module_id: config.module_id,
+        in_top_level: Default::default(),
+        scope: Default::default(),
+        export: private_ident!("_export"),
+        context: private_ident!("_context"),
+    }
+}
+
+struct SystemJs {
+    config: util::Config,
+    module_id: Option<String>,
+    in_top_level: bool,
+    scope: Scope,
+    /// `_export`
+    export: Ident,
+    /// `_context`
+    context: Ident,
+}
+
+noop_fold_type!(SystemJs);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    #[serde(default)]
+    pub module_id: Option<String>,
+
+    #[serde(default = "default_strict_mode")]
+    pub strict_mode: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            module_id: None,
+            strict_mode: default_strict_mode(),
+        }
+    }
+}
+
+const fn default_strict_mode() -> bool {
+    true
+}
+
+impl Fold<Module> for SystemJs {
+    fn fold(&mut self, module: Module) -> Module {
+        let mut items = module.body;
+        self.in_top_level = true;
+
+        // The directive is moved to the outer function.
+        let mut stmts = vec![];
+        let has_use_strict = util::has_use_strict(&items);
+        if has_use_strict {
+            items.remove(0);
+        }
+        if self.config.strict_mode || has_use_strict {
+            stmts.push(use_strict());
+        }
+
+        // Dependencies, in the order of the source.
+        for item in &items {
+            match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                    self.scope.insert_import(import.clone())
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(TsImportEqualsDecl {
+                    id,
+                    module_ref: TsModuleRef::TsExternalModuleRef(TsExternalModuleRef { expr, .. }),
+                    ..
+                })) => self
+                    .scope
+                    .insert_import_equals(id.clone(), expr.value.clone()),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportAll(ExportAll { src, .. }))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                    src: Some(src),
+                    ..
+                })) => {
+                    self.scope.import_to_export(src, true);
+                }
+                _ => {}
+            }
+        }
+
+        // Statements executed by the setter of each dependency.
+        let mut setter_stmts: FxHashMap<JsWord, Vec<Stmt>> = Default::default();
+
+        // Exports are registered before folding any statement, so that all
+        // assignments to an exported binding are reported.
+        for item in &items {
+            let decl = match item {
+                ModuleItem::ModuleDecl(decl) => decl,
+                _ => continue,
+            };
+
+            match decl {
+                ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => {
+                    let ids: Vec<Ident> = match decl {
+                        Decl::Class(ClassDecl { ident, .. }) | Decl::Fn(FnDecl { ident, .. }) => {
+                            vec![ident.clone()]
+                        }
+                        Decl::Var(var) => find_ids(&var.decls),
+                        _ => vec![],
+                    };
+
+                    for id in ids {
+                        self.add_export(&id, id.clone());
+                    }
+                }
+
+                ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                    decl:
+                        DefaultDecl::Class(ClassExpr {
+                            ident: Some(ident), ..
+                        }),
+                    ..
+                })
+                | ModuleDecl::ExportDefaultDecl(ExportDefaultDecl {
+                    decl:
+                        DefaultDecl::Fn(FnExpr {
+                            ident: Some(ident), ..
+                        }),
+                    ..
+                }) => self.add_export(ident, quote_ident!("default")),
+
+                ModuleDecl::ExportNamed(NamedExport {
+                    specifiers,
+                    src: None,
+                    ..
+                }) => {
+                    for s in specifiers {
+                        let (orig, exported) = match s {
+                            ExportSpecifier::Named(ExportNamedSpecifier {
+                                orig, exported, ..
+                            }) => (orig, exported.as_ref().unwrap_or(orig)),
+                            _ => continue,
+                        };
+
+                        // Imported bindings are exported by setters.
+                        let imported = self
+                            .scope
+                            .idents
+                            .get(&(orig.sym.clone(), orig.span.ctxt()))
+                            .cloned();
+                        match imported {
+                            Some((src, prop)) => {
+                                let value = box self.imported(&src, prop);
+                                setter_stmts.entry(src).or_default().push(
+                                    self.make_export_assign(&self.export, exported.clone(), value)
+                                        .into_stmt(),
+                                );
+                            }
+                            None => self.add_export(orig, exported.clone()),
+                        }
+                    }
+                }
+
+                ModuleDecl::ExportNamed(NamedExport {
+                    specifiers,
+                    src: Some(src),
+                    ..
+                }) => {
+                    for s in specifiers {
+                        let (orig, exported) = match s {
+                            ExportSpecifier::Named(ExportNamedSpecifier {
+                                orig, exported, ..
+                            }) => (orig, exported.as_ref().unwrap_or(orig)),
+                            _ => unreachable!(
+                                "export default from 'foo'; and export * as Foo from 'foo'; \
+                                 should be removed by previous pass"
+                            ),
+                        };
+
+                        let value = box self.imported(&src.value, orig.sym.clone());
+                        setter_stmts.entry(src.value.clone()).or_default().push(
+                            self.make_export_assign(&self.export, exported.clone(), value)
+                                .into_stmt(),
+                        );
+                    }
+                }
+
+                // export import foo = require('foo');
+                ModuleDecl::TsImportEquals(TsImportEqualsDecl {
+                    is_export: true,
+                    id,
+                    module_ref: TsModuleRef::TsExternalModuleRef(TsExternalModuleRef { expr, .. }),
+                    ..
+                }) => {
+                    let value = box self.imported(&expr.value, js_word!(""));
+                    setter_stmts.entry(expr.value.clone()).or_default().push(
+                        self.make_export_assign(&self.export, id.clone(), value)
+                            .into_stmt(),
+                    );
+                }
+
+                ModuleDecl::ExportAll(ExportAll { src, .. }) => {
+                    let stmts = self.export_all(&src.value);
+                    setter_stmts
+                        .entry(src.value.clone())
+                        .or_default()
+                        .extend(stmts);
+                }
+
+                _ => {}
+            }
+        }
+
+        // Declared in the outer function, so that hoisted functions and
+        // setters can access them.
+        let mut vars = vec![];
+        // Function declarations, which are hoisted out of `execute`.
+        let mut hoisted = vec![];
+        let mut execute = vec![];
+
+        for item in items {
+            let decl = match item {
+                ModuleItem::Stmt(stmt) => {
+                    self.fold_top_level_stmt(stmt, &mut vars, &mut hoisted, &mut execute);
+                    continue;
+                }
+                ModuleItem::ModuleDecl(decl) => decl,
+            };
+
+            // export = foo;
+            //  -> export default foo;
+            let decl = match decl {
+                ModuleDecl::TsExportAssignment(export) => {
+                    ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                        span: export.span,
+                        expr: export.expr,
+                    })
+                }
+                _ => decl,
+            };
+
+            match decl {
+                ModuleDecl::ExportDecl(ExportDecl { decl, .. }) => self.fold_top_level_stmt(
+                    Stmt::Decl(decl),
+                    &mut vars,
+                    &mut hoisted,
+                    &mut execute,
+                ),
+
+                ModuleDecl::ExportDefaultDecl(ExportDefaultDecl { decl, .. }) => match decl {
+                    DefaultDecl::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    }) => self.fold_top_level_stmt(
+                        Stmt::Decl(Decl::Class(ClassDecl {
+                            ident,
+                            class,
+                            declare: false,
+                        })),
+                        &mut vars,
+                        &mut hoisted,
+                        &mut execute,
+                    ),
+                    DefaultDecl::Class(class) => {
+                        let class = box Expr::Class(class.fold_with(self));
+                        execute.push(
+                            self.make_export_assign(&self.export, quote_ident!("default"), class)
+                                .into_stmt(),
+                        );
+                    }
+                    DefaultDecl::Fn(FnExpr { ident, function }) => {
+                        let ident = match ident {
+                            Some(ident) => ident,
+                            None => {
+                                let ident = private_ident!("_default");
+                                self.add_export(&ident, quote_ident!("default"));
+                                ident
+                            }
+                        };
+
+                        self.fold_top_level_stmt(
+                            Stmt::Decl(Decl::Fn(FnDecl {
+                                ident,
+                                function,
+                                declare: false,
+                            })),
+                            &mut vars,
+                            &mut hoisted,
+                            &mut execute,
+                        )
+                    }
+                    DefaultDecl::TsInterfaceDecl(..) => {}
+                },
+
+                ModuleDecl::ExportDefaultExpr(ExportDefaultExpr { expr, .. }) => {
+                    let expr = expr.fold_with(self);
+                    execute.push(
+                        self.make_export_assign(&self.export, quote_ident!("default"), expr)
+                            .into_stmt(),
+                    );
+                }
+
+                // Handled by setters.
+                ModuleDecl::Import(..)
+                | ModuleDecl::ExportNamed(..)
+                | ModuleDecl::ExportAll(..)
+                | ModuleDecl::TsImportEquals(..) => {}
+
+                ModuleDecl::TsExportAssignment(..) | ModuleDecl::TsNamespaceExport(..) => {}
+            }
+        }
+
+        // ====================
+        //  Handle imports
+        // ====================
+
+        let mut deps = vec![];
+        let mut setters = vec![];
+        let mut import_vars = vec![];
+
+        for (src, import) in self.scope.imports.drain(..) {
+            deps.push(Some(Lit::Str(quote_str!(src.clone())).as_arg()));
+
+            let mut params = vec![];
+            let mut body = vec![];
+            if let Some((sym, span)) = import {
+                let ident = Ident::new(sym, span);
+                let param = private_ident!("_m");
+
+                body.push(
+                    AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Pat(box Pat::Ident(ident.clone())),
+                        op: op!("="),
+                        right: box param.clone().into(),
+                    }
+                    .into_stmt(),
+                );
+                params.push(Param {
+                    span: DUMMY_SP,
+                    decorators: Default::default(),
+                    pat: Pat::Ident(param),
+                });
+                import_vars.push(ident);
+            }
+            body.extend(setter_stmts.remove(&src).unwrap_or_default());
+
+            setters.push(Some(fn_expr(params, body).as_arg()));
+        }
+
+        // ====================
+        //  Emit
+        // ====================
+
+        let mut declared = FxHashSet::default();
+        let decls: Vec<_> = import_vars
+            .into_iter()
+            .chain(vars)
+            .filter(|i| declared.insert((i.sym.clone(), i.span.ctxt())))
+            .map(|i| VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(i),
+                init: None,
+                definite: false,
+            })
+            .collect();
+        if !decls.is_empty() {
+            stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls,
+                declare: false,
+            })));
+        }
+
+        stmts.extend(hoisted);
+
+        // return { setters: [], execute: function () {} };
+        stmts.push(Stmt::Return(ReturnStmt {
+            span: DUMMY_SP,
+            arg: Some(box Expr::Object(ObjectLit {
+                span: DUMMY_SP,
+                props: vec![
+                    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("setters")),
+                        value: box Expr::Array(ArrayLit {
+                            span: DUMMY_SP,
+                            elems: setters,
+                        }),
+                    })),
+                    PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                        key: PropName::Ident(quote_ident!("execute")),
+                        value: box fn_expr(vec![], execute).into(),
+                    })),
+                ],
+            })),
+        }));
+
+        let params = vec![self.export.clone(), self.context.clone()]
+            .into_iter()
+            .map(|i| Param {
+                span: DUMMY_SP,
+                decorators: Default::default(),
+                pat: Pat::Ident(i),
+            })
+            .collect();
+
+        Module {
+            body: vec![CallExpr {
+                span: DUMMY_SP,
+                callee: member_expr!(DUMMY_SP, System.register).as_callee(),
+                args: self
+                    .module_id
+                    .clone()
+                    .map(|s| quote_str!(s).as_arg())
+                    .into_iter()
+                    .chain(iter::once(
+                        ArrayLit {
+                            span: DUMMY_SP,
+                            elems: deps,
+                        }
+                        .as_arg(),
+                    ))
+                    .chain(iter::once(fn_expr(params, stmts).as_arg()))
+                    .collect(),
+                type_args: Default::default(),
+            }
+            .into_stmt()
+            .into()],
+            ..module
+        }
+    }
+}
+
+impl SystemJs {
+    fn add_export(&mut self, local: &Ident, exported: Ident) {
+        self.scope
+            .exported_vars
+            .entry((local.sym.clone(), local.span.ctxt()))
+            .or_default()
+            .push((exported.sym, exported.span.ctxt()));
+    }
+
+    /// Reads `prop` of the namespace object of `src`.
+    fn imported(&self, src: &JsWord, prop: JsWord) -> Expr {
+        let (sym, span) = self
+            .scope
+            .imports
+            .get(src)
+            .cloned()
+            .flatten()
+            .expect("imported module should have a local name");
+        let ns = Ident::new(sym, span);
+
+        if prop == js_word!("") {
+            // import * as foo from 'foo';
+            Expr::Ident(ns)
+        } else {
+            ns.member(Ident::new(prop, DUMMY_SP))
+        }
+    }
+
+    /// Creates
+    ///
+    /// ```js
+    /// var _exportObj = {};
+    /// for (var _key in _foo) {
+    ///   if (_key !== "default" && _key !== "__esModule") _exportObj[_key] = _foo[_key];
+    /// }
+    /// _export(_exportObj);
+    /// ```
+    fn export_all(&self, src: &JsWord) -> Vec<Stmt> {
+        let ns = self.imported(src, js_word!(""));
+        let export_obj = private_ident!("_exportObj");
+        let key = private_ident!("_key");
+
+        vec![
+            Stmt::Decl(Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                decls: vec![VarDeclarator {
+                    span: DUMMY_SP,
+                    name: Pat::Ident(export_obj.clone()),
+                    init: Some(box Expr::Object(ObjectLit {
+                        span: DUMMY_SP,
+                        props: vec![],
+                    })),
+                    definite: false,
+                }],
+                declare: false,
+            })),
+            Stmt::ForIn(ForInStmt {
+                span: DUMMY_SP,
+                left: VarDeclOrPat::VarDecl(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Var,
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(key.clone()),
+                        init: None,
+                        definite: false,
+                    }],
+                    declare: false,
+                }),
+                right: box ns.clone(),
+                body: box Stmt::Block(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: vec![Stmt::If(IfStmt {
+                        span: DUMMY_SP,
+                        test: box key
+                            .clone()
+                            .make_bin(op!("!=="), Lit::Str(quote_str!("default")))
+                            .make_bin(
+                                op!("&&"),
+                                key.clone()
+                                    .make_bin(op!("!=="), Lit::Str(quote_str!("__esModule"))),
+                            ),
+                        cons: box AssignExpr {
+                            span: DUMMY_SP,
+                            left: PatOrExpr::Expr(
+                                box export_obj.clone().computed_member(key.clone()),
+                            ),
+                            op: op!("="),
+                            right: box ns.computed_member(key),
+                        }
+                        .into_stmt(),
+                        alt: None,
+                    })],
+                }),
+            }),
+            CallExpr {
+                span: DUMMY_SP,
+                callee: self.export.clone().as_callee(),
+                args: vec![export_obj.as_arg()],
+                type_args: Default::default(),
+            }
+            .into_stmt(),
+        ]
+    }
+
+    /// Splits a top-level statement into declarations of the outer function
+    /// and statements of `execute`.
+    fn fold_top_level_stmt(
+        &mut self,
+        stmt: Stmt,
+        vars: &mut Vec<Ident>,
+        hoisted: &mut Vec<Stmt>,
+        execute: &mut Vec<Stmt>,
+    ) {
+        match stmt {
+            Stmt::Decl(Decl::Fn(f)) => {
+                let f = f.fold_with(self);
+                let ident = f.ident.clone();
+                hoisted.push(Stmt::Decl(Decl::Fn(f)));
+
+                // Function declarations are exported before executing the module.
+                let exported = self
+                    .scope
+                    .exported_vars
+                    .get(&(ident.sym.clone(), ident.span.ctxt()))
+                    .cloned()
+                    .unwrap_or_default();
+                for (sym, ctxt) in exported {
+                    hoisted.push(
+                        self.make_export_assign(
+                            &self.export,
+                            Ident::new(sym, DUMMY_SP.with_ctxt(ctxt)),
+                            box Expr::Ident(ident.clone()),
+                        )
+                        .into_stmt(),
+                    );
+                }
+            }
+
+            // class Foo {}
+            //   -> Foo = class Foo {}
+            Stmt::Decl(Decl::Class(ClassDecl { ident, class, .. })) => {
+                vars.push(ident.clone());
+
+                let assign = Expr::Assign(AssignExpr {
+                    span: DUMMY_SP,
+                    left: PatOrExpr::Pat(box Pat::Ident(ident.clone())),
+                    op: op!("="),
+                    right: box Expr::Class(ClassExpr {
+                        ident: Some(ident),
+                        class,
+                    }),
+                });
+                execute.push(assign.fold_with(self).into_stmt());
+            }
+
+            // var foo = 1;
+            //   -> foo = 1;
+            Stmt::Decl(Decl::Var(var)) => {
+                vars.extend(find_ids::<_, Ident>(&var.decls));
+
+                for decl in var.decls {
+                    if let Some(init) = decl.init {
+                        let assign = Expr::Assign(AssignExpr {
+                            span: decl.span,
+                            left: PatOrExpr::Pat(box decl.name),
+                            op: op!("="),
+                            right: init,
+                        });
+                        execute.push(assign.fold_with(self).into_stmt());
+                    }
+                }
+            }
+
+            // if (a) { var foo = 1; }
+            //   -> if (a) { foo = 1; }
+            _ => {
+                let mut hoister = VarHoister { vars };
+                let stmt = stmt.fold_with(&mut hoister);
+                execute.push(stmt.fold_with(self))
+            }
+        }
+    }
+}
+
+/// Converts `var` declarations nested in a top-level statement into
+/// assignments, and collects the declared names.
+///
+/// Functions are not visited, as they have their own scope.
+struct VarHoister<'a> {
+    vars: &'a mut Vec<Ident>,
+}
+
+noop_fold_type!(VarHoister<'_>);
+
+impl VarHoister<'_> {
+    /// Returns `None` if `var` does not have an initializer.
+    fn var_to_expr(&mut self, var: VarDecl) -> Option<Box<Expr>> {
+        self.vars.extend(find_ids::<_, Ident>(&var.decls));
+
+        let mut exprs: Vec<_> = var
+            .decls
+            .into_iter()
+            .filter_map(|decl| {
+                let init = decl.init?.fold_with(self);
+                Some(box Expr::Assign(AssignExpr {
+                    span: decl.span,
+                    left: PatOrExpr::Pat(box decl.name),
+                    op: op!("="),
+                    right: init,
+                }))
+            })
+            .collect();
+
+        match exprs.len() {
+            0 => None,
+            1 => exprs.pop(),
+            _ => Some(box Expr::Seq(SeqExpr {
+                span: var.span,
+                exprs,
+            })),
+        }
+    }
+}
+
+impl Fold<Stmt> for VarHoister<'_> {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        match stmt {
+            Stmt::Decl(Decl::Var(var)) if var.kind == VarDeclKind::Var => {
+                let span = var.span;
+                match self.var_to_expr(var) {
+                    Some(expr) => Stmt::Expr(ExprStmt { span, expr }),
+                    None => Stmt::Empty(EmptyStmt { span }),
+                }
+            }
+            _ => stmt.fold_children(self),
+        }
+    }
+}
+
+impl Fold<ForStmt> for VarHoister<'_> {
+    fn fold(&mut self, s: ForStmt) -> ForStmt {
+        let init = match s.init {
+            Some(VarDeclOrExpr::VarDecl(var)) if var.kind == VarDeclKind::Var => {
+                self.var_to_expr(var).map(VarDeclOrExpr::Expr)
+            }
+            init => init.fold_with(self),
+        };
+
+        ForStmt {
+            init,
+            test: s.test.fold_with(self),
+            update: s.update.fold_with(self),
+            body: s.body.fold_with(self),
+            ..s
+        }
+    }
+}
+
+/// `for (var a in b)`
+///   -> `for (a in b)`
+impl Fold<VarDeclOrPat> for VarHoister<'_> {
+    fn fold(&mut self, v: VarDeclOrPat) -> VarDeclOrPat {
+        match v {
+            VarDeclOrPat::VarDecl(mut var)
+                if var.kind == VarDeclKind::Var
+                    && var.decls.len() == 1
+                    && var.decls[0].init.is_none() =>
+            {
+                self.vars.extend(find_ids::<_, Ident>(&var.decls));
+                VarDeclOrPat::Pat(var.decls.remove(0).name)
+            }
+            _ => v.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Function> for VarHoister<'_> {
+    fn fold(&mut self, f: Function) -> Function {
+        f
+    }
+}
+
+impl Fold<ArrowExpr> for VarHoister<'_> {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        f
+    }
+}
+
+fn fn_expr(params: Vec<Param>, stmts: Vec<Stmt>) -> FnExpr {
+    FnExpr {
+        ident: None,
+        function: Function {
+            span: DUMMY_SP,
+            is_async: false,
+            is_generator: false,
+            decorators: Default::default(),
+            params,
+            body: Some(BlockStmt {
+                span: DUMMY_SP,
+                stmts,
+            }),
+            return_type: Default::default(),
+            type_params: Default::default(),
+        },
+    }
+}
+
+impl Fold<Prop> for SystemJs {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(ident) => {
+                let top_level = self.in_top_level;
+                Scope::fold_shorthand_prop(self, top_level, ident)
+            }
+
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Expr> for SystemJs {
+    fn fold(&mut self, expr: Expr) -> Expr {
+        match expr {
+            // import('foo')
+            //   -> _context.import('foo')
+            Expr::Call(CallExpr {
+                span,
+                callee:
+                    ExprOrSuper::Expr(box Expr::Ident(Ident {
+                        sym: js_word!("import"),
+                        ..
+                    })),
+                args,
+                type_args,
+            }) => Expr::Call(CallExpr {
+                span,
+                callee: self
+                    .context
+                    .clone()
+                    .member(quote_ident!("import"))
+                    .as_callee(),
+                args: args.fold_with(self),
+                type_args,
+            }),
+
+            // import.meta
+            //   -> _context.meta
+            Expr::MetaProp(MetaPropExpr {
+                meta:
+                    Ident {
+                        sym: js_word!("import"),
+                        ..
+                    },
+                prop,
+            }) => self.context.clone().member(prop),
+
+            _ => {
+                let top_level = self.in_top_level;
+                Scope::fold_expr(self, self.export.clone(), top_level, expr)
+            }
+        }
+    }
+}
+
+impl ModulePass for SystemJs {
+    fn config(&self) -> &util::Config {
+        &self.config
+    }
+
+    fn scope(&self) -> &Scope {
+        &self.scope
+    }
+
+    fn scope_mut(&mut self) -> &mut Scope {
+        &mut self.scope
+    }
+
+    /// `_export("name", value)`
+    fn make_export_assign(&self, exports: &Ident, exported: Ident, value: Box<Expr>) -> Box<Expr> {
+        box Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: exports.clone().as_callee(),
+            args: vec![Lit::Str(quote_str!(exported.sym)).as_arg(), value.as_arg()],
+            type_args: Default::default(),
+        })
+    }
+}
+mark_as_nested!(SystemJs);
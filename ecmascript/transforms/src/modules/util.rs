@@ -15,6 +15,19 @@ pub(super) trait ModulePass {
     fn config(&self) -> &Config;
     fn scope(&self) -> &Scope;
     fn scope_mut(&mut self) -> &mut Scope;
+
+    /// Creates an expression which updates the exported binding `exported` to
+    /// `value`.
+    ///
+    /// Defaults to `exports.exported = value`.
+    fn make_export_assign(&self, exports: &Ident, exported: Ident, value: Box<Expr>) -> Box<Expr> {
+        box Expr::Assign(AssignExpr {
+            span: DUMMY_SP,
+            left: PatOrExpr::Expr(box exports.clone().member(exported)),
+            op: op!("="),
+            right: value,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -352,6 +365,23 @@ impl Scope {
         }
     }
 
+    /// `import foo = require('src');` binds `foo` to the module itself, like
+    /// `import * as foo from 'src';` but without an interop helper.
+    pub fn insert_import_equals(&mut self, local: Ident, src: JsWord) {
+        self.idents.insert(
+            (local.sym.clone(), local.span.ctxt()),
+            (src.clone(), "".into()),
+        );
+
+        self.imports
+            .entry(src)
+            .and_modify(|v| match *v {
+                Some(ref mut v) => v.0 = local.sym.clone(),
+                None => *v = Some((local.sym.clone(), local.span)),
+            })
+            .or_insert_with(|| Some((local.sym.clone(), local.span)));
+    }
+
     pub(super) fn fold_shorthand_prop(
         folder: &mut impl ModulePass,
         top_level: bool,
@@ -437,18 +467,16 @@ impl Scope {
 
         macro_rules! chain_assign {
             ($entry:expr, $e:expr) => {{
+                let exported = $entry.get().clone();
+                drop($entry);
+
                 let mut e = $e;
-                for i in $entry.get() {
-                    e = box Expr::Assign(AssignExpr {
-                        span: DUMMY_SP,
-                        left: PatOrExpr::Expr(
-                            box exports
-                                .clone()
-                                .member(Ident::new(i.0.clone(), DUMMY_SP.with_ctxt(i.1))),
-                        ),
-                        op: op!("="),
-                        right: e,
-                    });
+                for i in exported {
+                    e = folder.make_export_assign(
+                        &exports,
+                        Ident::new(i.0, DUMMY_SP.with_ctxt(i.1)),
+                        e,
+                    );
                 }
                 e
             }};
@@ -0,0 +1,383 @@
+use crate::{
+    optimization::simplify::{dead_branch_remover, expr_simplifier},
+    pass::Pass,
+};
+use std::mem;
+use swc_common::{chain, Fold, FoldWith, Visit, VisitWith, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_utils::StmtLike;
+
+/// Simplifies expressions and removes dead branches, and then
+///
+///  - joins consecutive expression statements into a sequence expression.
+///  - converts `if` statements into `&&`, `||` or a conditional expression.
+///  - merges consecutive `var` declarations.
+///  - shortens `true`, `false` and `undefined`.
+pub fn compressor() -> impl Pass {
+    chain!(
+        expr_simplifier(),
+        dead_branch_remover(),
+        Compressor {
+            undefined_declared: false,
+        }
+    )
+}
+
+struct Compressor {
+    /// If `true`, `undefined` is not replaced with `void 0`.
+    undefined_declared: bool,
+}
+
+noop_fold_type!(Compressor);
+
+impl Fold<Module> for Compressor {
+    fn fold(&mut self, m: Module) -> Module {
+        self.undefined_declared = is_undefined_declared(&m);
+        m.fold_children(self)
+    }
+}
+
+impl Fold<Script> for Compressor {
+    fn fold(&mut self, s: Script) -> Script {
+        self.undefined_declared = is_undefined_declared(&s);
+        s.fold_children(self)
+    }
+}
+
+impl<T> Fold<Vec<T>> for Compressor
+where
+    T: StmtLike + FoldWith<Self>,
+{
+    fn fold(&mut self, stmts: Vec<T>) -> Vec<T> {
+        let stmts = stmts.fold_children(self);
+
+        let mut buf = Vec::with_capacity(stmts.len());
+        // Pending expression statements.
+        let mut exprs = vec![];
+
+        for stmt in stmts {
+            let stmt = match stmt.try_into_stmt() {
+                Ok(stmt) => stmt,
+                Err(item) => {
+                    flush(&mut buf, &mut exprs);
+                    buf.push(item);
+                    continue;
+                }
+            };
+
+            match stmt {
+                // Directives should not be joined.
+                Stmt::Expr(ExprStmt {
+                    expr: box Expr::Lit(Lit::Str(..)),
+                    ..
+                }) => {
+                    flush(&mut buf, &mut exprs);
+                    buf.push(T::from_stmt(stmt));
+                }
+
+                Stmt::Expr(ExprStmt { expr, .. }) => exprs.push(expr),
+
+                Stmt::Return(ReturnStmt {
+                    span,
+                    arg: Some(arg),
+                }) if !exprs.is_empty() => {
+                    exprs.push(arg);
+                    buf.push(T::from_stmt(Stmt::Return(ReturnStmt {
+                        span,
+                        arg: Some(seq(&mut exprs)),
+                    })));
+                }
+
+                Stmt::Throw(ThrowStmt { span, arg }) if !exprs.is_empty() => {
+                    exprs.push(arg);
+                    buf.push(T::from_stmt(Stmt::Throw(ThrowStmt {
+                        span,
+                        arg: seq(&mut exprs),
+                    })));
+                }
+
+                Stmt::If(IfStmt {
+                    span,
+                    test,
+                    cons,
+                    alt,
+                }) if !exprs.is_empty() => {
+                    exprs.push(test);
+                    buf.push(T::from_stmt(Stmt::If(IfStmt {
+                        span,
+                        test: seq(&mut exprs),
+                        cons,
+                        alt,
+                    })));
+                }
+
+                Stmt::Decl(Decl::Var(var)) => {
+                    flush(&mut buf, &mut exprs);
+
+                    let var = match buf.pop().map(T::try_into_stmt) {
+                        Some(Ok(Stmt::Decl(Decl::Var(mut prev)))) if prev.kind == var.kind => {
+                            prev.decls.extend(var.decls);
+                            prev
+                        }
+                        Some(Ok(prev)) => {
+                            buf.push(T::from_stmt(prev));
+                            var
+                        }
+                        Some(Err(prev)) => {
+                            buf.push(prev);
+                            var
+                        }
+                        None => var,
+                    };
+                    buf.push(T::from_stmt(Stmt::Decl(Decl::Var(var))));
+                }
+
+                _ => {
+                    flush(&mut buf, &mut exprs);
+                    buf.push(T::from_stmt(stmt));
+                }
+            }
+        }
+        flush(&mut buf, &mut exprs);
+
+        buf
+    }
+}
+
+impl Fold<Stmt> for Compressor {
+    fn fold(&mut self, stmt: Stmt) -> Stmt {
+        let stmt = stmt.fold_children(self);
+
+        match stmt {
+            Stmt::If(IfStmt {
+                span,
+                test,
+                cons,
+                alt,
+            }) => {
+                let cons = unwrap_block(*cons);
+                let alt = alt.map(|alt| unwrap_block(*alt));
+
+                match (cons, alt) {
+                    // `if (a) b()` => `a && b()`
+                    (Stmt::Expr(cons), None) => {
+                        let (op, left) = match *test {
+                            Expr::Unary(UnaryExpr {
+                                op: op!("!"), arg, ..
+                            }) => (op!("||"), arg),
+                            test => (op!("&&"), box test),
+                        };
+
+                        Stmt::Expr(ExprStmt {
+                            span,
+                            expr: box Expr::Bin(BinExpr {
+                                span,
+                                op,
+                                left,
+                                right: cons.expr,
+                            }),
+                        })
+                    }
+
+                    // `if (a) b(); else c();` => `a ? b() : c()`
+                    (Stmt::Expr(cons), Some(Stmt::Expr(alt))) => Stmt::Expr(ExprStmt {
+                        span,
+                        expr: box Expr::Cond(CondExpr {
+                            span,
+                            test,
+                            cons: cons.expr,
+                            alt: alt.expr,
+                        }),
+                    }),
+
+                    // `if (a) return b; else return c;` => `return a ? b : c`
+                    (
+                        Stmt::Return(ReturnStmt {
+                            arg: Some(cons), ..
+                        }),
+                        Some(Stmt::Return(ReturnStmt { arg: Some(alt), .. })),
+                    ) => Stmt::Return(ReturnStmt {
+                        span,
+                        arg: Some(box Expr::Cond(CondExpr {
+                            span,
+                            test,
+                            cons,
+                            alt,
+                        })),
+                    }),
+
+                    (cons, alt) => Stmt::If(IfStmt {
+                        span,
+                        test,
+                        cons: box cons,
+                        alt: alt.map(Box::new),
+                    }),
+                }
+            }
+
+            _ => stmt,
+        }
+    }
+}
+
+impl Fold<Expr> for Compressor {
+    fn fold(&mut self, e: Expr) -> Expr {
+        let e = e.fold_children(self);
+
+        match e {
+            // `true` => `!0`
+            Expr::Lit(Lit::Bool(Bool { span, value })) => Expr::Unary(UnaryExpr {
+                span,
+                op: op!("!"),
+                arg: box Expr::Lit(Lit::Num(Number {
+                    span,
+                    value: if value { 0.0 } else { 1.0 },
+                })),
+            }),
+
+            Expr::Ident(Ident {
+                span,
+                sym: js_word!("undefined"),
+                ..
+            }) if !self.undefined_declared => Expr::Unary(UnaryExpr {
+                span,
+                op: op!("void"),
+                arg: box Expr::Lit(Lit::Num(Number { span, value: 0.0 })),
+            }),
+
+            _ => e,
+        }
+    }
+}
+
+impl Fold<MemberExpr> for Compressor {
+    fn fold(&mut self, mut e: MemberExpr) -> MemberExpr {
+        e.obj = e.obj.fold_with(self);
+        // `a.undefined` should not become `a.void 0`.
+        if e.computed {
+            e.prop = e.prop.fold_with(self);
+        }
+        e
+    }
+}
+
+/// Assignment targets are not shortened, as `void 0 = 1` is invalid.
+impl Fold<PatOrExpr> for Compressor {
+    fn fold(&mut self, n: PatOrExpr) -> PatOrExpr {
+        match n {
+            PatOrExpr::Expr(box Expr::Ident(..)) => n,
+            _ => n.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Pat> for Compressor {
+    fn fold(&mut self, p: Pat) -> Pat {
+        match p {
+            Pat::Expr(box Expr::Ident(..)) => p,
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<UpdateExpr> for Compressor {
+    fn fold(&mut self, e: UpdateExpr) -> UpdateExpr {
+        match *e.arg {
+            Expr::Ident(..) => e,
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+/// Unwraps a block statement if it only contains a statement which can be
+/// converted to an expression.
+fn unwrap_block(stmt: Stmt) -> Stmt {
+    match stmt {
+        Stmt::Block(BlockStmt { mut stmts, .. })
+            if stmts.len() == 1
+                && match stmts[0] {
+                    Stmt::Expr(..) | Stmt::Return(..) => true,
+                    _ => false,
+                } =>
+        {
+            stmts.remove(0)
+        }
+        _ => stmt,
+    }
+}
+
+fn seq(exprs: &mut Vec<Box<Expr>>) -> Box<Expr> {
+    if exprs.len() == 1 {
+        return exprs.remove(0);
+    }
+
+    box Expr::Seq(SeqExpr {
+        span: DUMMY_SP,
+        exprs: mem::replace(exprs, vec![]),
+    })
+}
+
+fn flush<T: StmtLike>(buf: &mut Vec<T>, exprs: &mut Vec<Box<Expr>>) {
+    if exprs.is_empty() {
+        return;
+    }
+
+    buf.push(T::from_stmt(Stmt::Expr(ExprStmt {
+        span: DUMMY_SP,
+        expr: seq(exprs),
+    })));
+}
+
+fn is_undefined_declared<T>(node: &T) -> bool
+where
+    T: VisitWith<UndefinedFinder>,
+{
+    let mut v = UndefinedFinder { found: false };
+    node.visit_with(&mut v);
+    v.found
+}
+
+/// Finds a binding named `undefined`.
+struct UndefinedFinder {
+    found: bool,
+}
+
+noop_visit_type!(UndefinedFinder);
+
+impl Visit<Pat> for UndefinedFinder {
+    fn visit(&mut self, p: &Pat) {
+        match p {
+            Pat::Ident(Ident {
+                sym: js_word!("undefined"),
+                ..
+            }) => self.found = true,
+            _ => p.visit_children(self),
+        }
+    }
+}
+
+impl Visit<FnDecl> for UndefinedFinder {
+    fn visit(&mut self, f: &FnDecl) {
+        self.found |= f.ident.sym == js_word!("undefined");
+        f.visit_children(self);
+    }
+}
+
+impl Visit<ClassDecl> for UndefinedFinder {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.found |= c.ident.sym == js_word!("undefined");
+        c.visit_children(self);
+    }
+}
+
+impl Visit<ImportDecl> for UndefinedFinder {
+    fn visit(&mut self, i: &ImportDecl) {
+        self.found |= i.specifiers.iter().any(|s| match s {
+            ImportSpecifier::Named(ImportNamedSpecifier { local, .. })
+            | ImportSpecifier::Default(ImportDefaultSpecifier { local, .. })
+            | ImportSpecifier::Namespace(ImportStarAsSpecifier { local, .. }) => {
+                local.sym == js_word!("undefined")
+            }
+        });
+    }
+}
@@ -0,0 +1,578 @@
+use crate::{pass::Pass, resolver_with_mark};
+use fxhash::{FxHashMap, FxHashSet};
+use indexmap::IndexMap;
+use std::cmp::Reverse;
+use swc_atoms::JsWord;
+use swc_common::{chain, Fold, FoldWith, Mark, Span, SyntaxContext, Visit, VisitWith};
+use swc_ecma_ast::*;
+use swc_ecma_utils::{find_ids, ident::IdentLike, Id};
+
+/// Renames bindings which are not declared in the top-level scope.
+///
+/// Syntax contexts are reset and marks are applied again using `resolver`, so
+/// names should be unique in each scope (i.e. [hygiene](crate::hygiene) should
+/// be invoked before this).
+///
+/// Nothing is renamed if `eval` or `with` is used.
+pub fn mangler(reserved: Vec<JsWord>) -> impl Pass {
+    let top_level_mark = Mark::fresh(Mark::root());
+
+    chain!(
+        CtxtRemover,
+        resolver_with_mark(top_level_mark),
+        Mangler {
+            top_level_ctxt: SyntaxContext::empty().apply_mark(top_level_mark),
+            reserved: reserved.into_iter().collect(),
+        }
+    )
+}
+
+struct CtxtRemover;
+
+noop_fold_type!(CtxtRemover);
+
+impl Fold<Span> for CtxtRemover {
+    fn fold(&mut self, s: Span) -> Span {
+        s.with_ctxt(SyntaxContext::empty())
+    }
+}
+
+struct Mangler {
+    top_level_ctxt: SyntaxContext,
+    reserved: FxHashSet<JsWord>,
+}
+
+noop_fold_type!(Mangler);
+
+impl Fold<Module> for Mangler {
+    fn fold(&mut self, m: Module) -> Module {
+        let names = self.names(&m);
+        m.fold_with(&mut Renamer { names: &names })
+    }
+}
+
+impl Fold<Script> for Mangler {
+    fn fold(&mut self, s: Script) -> Script {
+        let names = self.names(&s);
+        s.fold_with(&mut Renamer { names: &names })
+    }
+}
+
+impl Mangler {
+    /// Assigns a new name to each binding.
+    ///
+    /// A binding is only visible in a subtree of scopes, and two bindings can
+    /// share a name if their subtrees are disjoint.
+    fn names<T>(&self, node: &T) -> FxHashMap<Id, JsWord>
+    where
+        T: VisitWith<Analyzer>,
+    {
+        let mut a = Analyzer {
+            top_level_ctxt: self.top_level_ctxt,
+            scopes: vec![ScopeData {
+                parent: None,
+                depth: 0,
+                is_fn: true,
+            }],
+            cur: 0,
+            bindings: Default::default(),
+            unmangled: Default::default(),
+            has_eval: false,
+        };
+        node.visit_with(&mut a);
+
+        if a.has_eval {
+            return Default::default();
+        }
+
+        let mut bindings = vec![];
+        for (id, b) in a.bindings.iter() {
+            match b.decl {
+                Some(decl) => bindings.push((id, a.lca(b.region, decl), b.refs)),
+                // We can't know the scope of the binding.
+                None => {
+                    a.unmangled.insert(id.0.clone());
+                }
+            }
+        }
+        // Outer bindings first, and frequently used bindings get shorter names.
+        bindings.sort_by_key(|&(_, region, refs)| (a.scopes[region].depth, Reverse(refs)));
+
+        let mut used: FxHashMap<JsWord, Vec<ScopeId>> = Default::default();
+        let mut names = FxHashMap::default();
+        for (id, region, _) in bindings {
+            let mut n = 0;
+            let name = loop {
+                let name = base54(n);
+                n += 1;
+
+                if a.unmangled.contains(&name)
+                    || self.reserved.contains(&name)
+                    || is_reserved_name(&name)
+                {
+                    continue;
+                }
+                let conflicts = used.get(&name).map_or(false, |regions| {
+                    regions
+                        .iter()
+                        .any(|&r| a.is_ancestor(r, region) || a.is_ancestor(region, r))
+                });
+                if !conflicts {
+                    break name;
+                }
+            };
+
+            used.entry(name.clone()).or_default().push(region);
+            names.insert(id.clone(), name);
+        }
+
+        names
+    }
+}
+
+type ScopeId = usize;
+
+#[derive(Debug)]
+struct ScopeData {
+    parent: Option<ScopeId>,
+    depth: u32,
+    /// `true` for functions and the top-level scope.
+    is_fn: bool,
+}
+
+#[derive(Debug)]
+struct Binding {
+    /// Scope which declares the binding.
+    decl: Option<ScopeId>,
+    /// Least common ancestor of the scopes where the binding is used.
+    region: ScopeId,
+    refs: usize,
+}
+
+struct Analyzer {
+    top_level_ctxt: SyntaxContext,
+    scopes: Vec<ScopeData>,
+    cur: ScopeId,
+    bindings: IndexMap<Id, Binding>,
+    /// Names of top-level bindings and globals.
+    unmangled: FxHashSet<JsWord>,
+    has_eval: bool,
+}
+
+noop_visit_type!(Analyzer);
+
+impl Analyzer {
+    fn with_scope<F>(&mut self, is_fn: bool, op: F)
+    where
+        F: FnOnce(&mut Self),
+    {
+        let parent = self.cur;
+        self.scopes.push(ScopeData {
+            parent: Some(parent),
+            depth: self.scopes[parent].depth + 1,
+            is_fn,
+        });
+        self.cur = self.scopes.len() - 1;
+        op(self);
+        self.cur = parent;
+    }
+
+    fn fn_scope(&self) -> ScopeId {
+        let mut s = self.cur;
+        while !self.scopes[s].is_fn {
+            s = self.scopes[s].parent.unwrap();
+        }
+        s
+    }
+
+    fn is_mangled(&self, i: &Ident) -> bool {
+        let ctxt = i.span.ctxt();
+        ctxt != SyntaxContext::empty() && ctxt != self.top_level_ctxt
+    }
+
+    fn declare<T>(&mut self, pat: &T, scope: ScopeId)
+    where
+        T: for<'any> VisitWith<swc_ecma_utils::DestructuringFinder<'any, Ident>>,
+    {
+        let ids: Vec<Ident> = find_ids(pat);
+        for i in ids {
+            if !self.is_mangled(&i) {
+                continue;
+            }
+            let decl = match self.bindings.get(&i.to_id()).and_then(|b| b.decl) {
+                Some(decl) => self.lca(decl, scope),
+                None => scope,
+            };
+            let region = self.cur;
+            self.bindings
+                .entry(i.to_id())
+                .or_insert(Binding {
+                    decl: None,
+                    region,
+                    refs: 0,
+                })
+                .decl = Some(decl);
+        }
+    }
+
+    fn lca(&self, mut a: ScopeId, mut b: ScopeId) -> ScopeId {
+        while self.scopes[a].depth > self.scopes[b].depth {
+            a = self.scopes[a].parent.unwrap();
+        }
+        while self.scopes[b].depth > self.scopes[a].depth {
+            b = self.scopes[b].parent.unwrap();
+        }
+        while a != b {
+            a = self.scopes[a].parent.unwrap();
+            b = self.scopes[b].parent.unwrap();
+        }
+        a
+    }
+
+    fn is_ancestor(&self, ancestor: ScopeId, mut s: ScopeId) -> bool {
+        while self.scopes[s].depth > self.scopes[ancestor].depth {
+            s = self.scopes[s].parent.unwrap();
+        }
+        s == ancestor
+    }
+}
+
+impl Visit<Ident> for Analyzer {
+    fn visit(&mut self, i: &Ident) {
+        if !self.is_mangled(i) {
+            self.unmangled.insert(i.sym.clone());
+            return;
+        }
+
+        let cur = self.cur;
+        let region = match self.bindings.get(&i.to_id()) {
+            Some(b) => self.lca(b.region, cur),
+            None => cur,
+        };
+        let b = self.bindings.entry(i.to_id()).or_insert(Binding {
+            decl: None,
+            region,
+            refs: 0,
+        });
+        b.region = region;
+        b.refs += 1;
+    }
+}
+
+impl Visit<Function> for Analyzer {
+    fn visit(&mut self, f: &Function) {
+        self.with_scope(true, |a| {
+            let scope = a.cur;
+            a.declare(&f.params, scope);
+            f.visit_children(a);
+        })
+    }
+}
+
+impl Visit<ArrowExpr> for Analyzer {
+    fn visit(&mut self, f: &ArrowExpr) {
+        self.with_scope(true, |a| {
+            let scope = a.cur;
+            a.declare(&f.params, scope);
+            f.visit_children(a);
+        })
+    }
+}
+
+impl Visit<SetterProp> for Analyzer {
+    fn visit(&mut self, p: &SetterProp) {
+        p.key.visit_with(self);
+        self.with_scope(true, |a| {
+            let scope = a.cur;
+            a.declare(&p.param, scope);
+            p.param.visit_with(a);
+            p.body.visit_with(a);
+        })
+    }
+}
+
+impl Visit<Constructor> for Analyzer {
+    fn visit(&mut self, c: &Constructor) {
+        c.key.visit_with(self);
+        self.with_scope(true, |a| {
+            let scope = a.cur;
+            a.declare(&c.params, scope);
+            c.params.visit_with(a);
+            c.body.visit_with(a);
+        })
+    }
+}
+
+impl Visit<FnDecl> for Analyzer {
+    fn visit(&mut self, f: &FnDecl) {
+        let scope = self.fn_scope();
+        self.declare(&f.ident, scope);
+        f.visit_children(self);
+    }
+}
+
+impl Visit<FnExpr> for Analyzer {
+    fn visit(&mut self, f: &FnExpr) {
+        self.with_scope(false, |a| {
+            if let Some(ident) = &f.ident {
+                let scope = a.cur;
+                a.declare(ident, scope);
+            }
+            f.visit_children(a);
+        })
+    }
+}
+
+impl Visit<ClassDecl> for Analyzer {
+    fn visit(&mut self, c: &ClassDecl) {
+        let scope = self.cur;
+        self.declare(&c.ident, scope);
+        c.visit_children(self);
+    }
+}
+
+impl Visit<ClassExpr> for Analyzer {
+    fn visit(&mut self, c: &ClassExpr) {
+        self.with_scope(false, |a| {
+            if let Some(ident) = &c.ident {
+                let scope = a.cur;
+                a.declare(ident, scope);
+            }
+            c.visit_children(a);
+        })
+    }
+}
+
+impl Visit<VarDecl> for Analyzer {
+    fn visit(&mut self, v: &VarDecl) {
+        let scope = match v.kind {
+            VarDeclKind::Var => self.fn_scope(),
+            VarDeclKind::Let | VarDeclKind::Const => self.cur,
+        };
+        for d in &v.decls {
+            self.declare(&d.name, scope);
+        }
+        v.visit_children(self);
+    }
+}
+
+impl Visit<CatchClause> for Analyzer {
+    fn visit(&mut self, c: &CatchClause) {
+        self.with_scope(false, |a| {
+            if let Some(param) = &c.param {
+                let scope = a.cur;
+                a.declare(param, scope);
+            }
+            c.visit_children(a);
+        })
+    }
+}
+
+macro_rules! block_scope {
+    ($T:ty) => {
+        impl Visit<$T> for Analyzer {
+            fn visit(&mut self, n: &$T) {
+                self.with_scope(false, |a| n.visit_children(a))
+            }
+        }
+    };
+}
+
+block_scope!(BlockStmt);
+block_scope!(ForStmt);
+block_scope!(ForInStmt);
+block_scope!(ForOfStmt);
+
+impl Visit<CallExpr> for Analyzer {
+    fn visit(&mut self, e: &CallExpr) {
+        match e.callee {
+            ExprOrSuper::Expr(box Expr::Ident(Ident {
+                sym: js_word!("eval"),
+                ..
+            })) => self.has_eval = true,
+            _ => {}
+        }
+
+        e.visit_children(self);
+    }
+}
+
+impl Visit<WithStmt> for Analyzer {
+    fn visit(&mut self, s: &WithStmt) {
+        self.has_eval = true;
+        s.visit_children(self);
+    }
+}
+
+impl Visit<MemberExpr> for Analyzer {
+    fn visit(&mut self, e: &MemberExpr) {
+        e.obj.visit_with(self);
+        if e.computed {
+            e.prop.visit_with(self);
+        }
+    }
+}
+
+impl Visit<PropName> for Analyzer {
+    fn visit(&mut self, n: &PropName) {
+        if let PropName::Computed(e) = n {
+            e.visit_with(self);
+        }
+    }
+}
+
+impl Visit<LabeledStmt> for Analyzer {
+    fn visit(&mut self, s: &LabeledStmt) {
+        s.body.visit_with(self);
+    }
+}
+
+impl Visit<BreakStmt> for Analyzer {
+    fn visit(&mut self, _: &BreakStmt) {}
+}
+
+impl Visit<ContinueStmt> for Analyzer {
+    fn visit(&mut self, _: &ContinueStmt) {}
+}
+
+impl Visit<MetaPropExpr> for Analyzer {
+    fn visit(&mut self, _: &MetaPropExpr) {}
+}
+
+impl Visit<PrivateName> for Analyzer {
+    fn visit(&mut self, _: &PrivateName) {}
+}
+
+struct Renamer<'a> {
+    names: &'a FxHashMap<Id, JsWord>,
+}
+
+noop_fold_type!(Renamer<'_>);
+
+impl Renamer<'_> {
+    fn renamed(&self, i: &Ident) -> Option<Ident> {
+        self.names.get(&i.to_id()).map(|name| Ident {
+            sym: name.clone(),
+            ..i.clone()
+        })
+    }
+}
+
+impl Fold<Ident> for Renamer<'_> {
+    fn fold(&mut self, i: Ident) -> Ident {
+        self.renamed(&i).unwrap_or(i)
+    }
+}
+
+impl Fold<Prop> for Renamer<'_> {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(i) => match self.renamed(&i) {
+                Some(renamed) => Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(i),
+                    value: box Expr::Ident(renamed),
+                }),
+                None => Prop::Shorthand(i),
+            },
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<ObjectPatProp> for Renamer<'_> {
+    fn fold(&mut self, p: ObjectPatProp) -> ObjectPatProp {
+        match p {
+            ObjectPatProp::Assign(AssignPatProp { span, key, value }) => {
+                let value = value.fold_with(self);
+                let renamed = match self.renamed(&key) {
+                    Some(renamed) => renamed,
+                    None => return ObjectPatProp::Assign(AssignPatProp { span, key, value }),
+                };
+
+                ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(key),
+                    value: match value {
+                        Some(right) => box Pat::Assign(AssignPat {
+                            span,
+                            left: box Pat::Ident(renamed),
+                            right,
+                            type_ann: None,
+                        }),
+                        None => box Pat::Ident(renamed),
+                    },
+                })
+            }
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<MemberExpr> for Renamer<'_> {
+    fn fold(&mut self, mut e: MemberExpr) -> MemberExpr {
+        e.obj = e.obj.fold_with(self);
+        if e.computed {
+            e.prop = e.prop.fold_with(self);
+        }
+        e
+    }
+}
+
+impl Fold<PropName> for Renamer<'_> {
+    fn fold(&mut self, n: PropName) -> PropName {
+        match n {
+            PropName::Computed(e) => PropName::Computed(e.fold_with(self)),
+            _ => n,
+        }
+    }
+}
+
+impl Fold<LabeledStmt> for Renamer<'_> {
+    fn fold(&mut self, s: LabeledStmt) -> LabeledStmt {
+        LabeledStmt {
+            body: s.body.fold_with(self),
+            ..s
+        }
+    }
+}
+
+macro_rules! skip {
+    ($T:ty) => {
+        impl Fold<$T> for Renamer<'_> {
+            fn fold(&mut self, n: $T) -> $T {
+                n
+            }
+        }
+    };
+}
+
+skip!(BreakStmt);
+skip!(ContinueStmt);
+skip!(MetaPropExpr);
+skip!(PrivateName);
+
+/// Creates `n`-th name from `a`, ..., `_`, `aa`, `ba`, ...
+fn base54(mut n: usize) -> JsWord {
+    const FIRST: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_";
+    const REST: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_0123456789";
+
+    let mut s = String::new();
+    s.push(FIRST[n % FIRST.len()] as char);
+    n /= FIRST.len();
+    while n > 0 {
+        n -= 1;
+        s.push(REST[n % REST.len()] as char);
+        n /= REST.len();
+    }
+
+    s.into()
+}
+
+fn is_reserved_name(name: &JsWord) -> bool {
+    name.is_reserved_for_es3()
+        || match &**name {
+            "let" | "yield" | "await" | "arguments" | "eval" | "undefined" | "NaN" | "Infinity" => {
+                true
+            }
+            _ => false,
+        }
+}
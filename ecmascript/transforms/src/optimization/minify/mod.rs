@@ -0,0 +1,56 @@
+//! Minifier which renames local bindings and compresses statements.
+//!
+//! Names assigned by [hygiene] are reused, so this should be invoked after
+//! [hygiene] and before [fixer].
+//!
+//! [hygiene]: crate::hygiene
+//! [fixer]: crate::fixer
+pub use self::{compress::compressor, mangle::mangler};
+use crate::pass::{Optional, Pass};
+use serde::{Deserialize, Serialize};
+use swc_atoms::JsWord;
+use swc_common::chain;
+
+mod compress;
+mod mangle;
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct Config {
+    /// Renames local bindings to short names.
+    ///
+    /// Top-level bindings are not renamed.
+    #[serde(default = "default_true")]
+    pub mangle: bool,
+
+    /// Joins statements and shortens expressions.
+    #[serde(default = "default_true")]
+    pub compress: bool,
+
+    /// Names which are not used by [mangler].
+    #[serde(default)]
+    pub reserved: Vec<JsWord>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            mangle: true,
+            compress: true,
+            reserved: vec![],
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+pub fn minifier(config: Config) -> impl Pass {
+    chain!(
+        Optional::new(compressor(), config.compress),
+        Optional::new(mangler(config.reserved), config.mangle)
+    )
+}
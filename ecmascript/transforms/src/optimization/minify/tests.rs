@@ -0,0 +1,238 @@
+use super::*;
+use crate::{hygiene, resolver};
+
+fn mangle(reserved: Vec<JsWord>) -> impl Pass {
+    chain!(resolver(), hygiene(), mangler(reserved))
+}
+
+test!(
+    Default::default(),
+    |_| mangle(vec![]),
+    mangle_params,
+    "function foo(longName, other) {
+    var result = longName + other;
+    return result;
+}",
+    "function foo(a, b) {
+    var c = a + b;
+    return c;
+}"
+);
+
+test!(
+    Default::default(),
+    |_| mangle(vec![]),
+    mangle_reuse_in_sibling,
+    "function foo(x) {
+    return x;
+}
+function bar(y) {
+    return y;
+}",
+    "function foo(a) {
+    return a;
+}
+function bar(a) {
+    return a;
+}"
+);
+
+test!(
+    Default::default(),
+    |_| mangle(vec![]),
+    mangle_nested,
+    "function foo(x) {
+    return function(y) {
+        return x + y;
+    };
+}",
+    "function foo(a) {
+    return function(b) {
+        return a + b;
+    };
+}"
+);
+
+test!(
+    Default::default(),
+    |_| mangle(vec![]),
+    mangle_global,
+    "function foo(x) {
+    return a + x;
+}",
+    "function foo(b) {
+    return a + b;
+}"
+);
+
+test!(
+    Default::default(),
+    |_| mangle(vec!["a".into()]),
+    mangle_reserved,
+    "function foo(x) {
+    return x;
+}",
+    "function foo(b) {
+    return b;
+}"
+);
+
+test!(
+    Default::default(),
+    |_| mangle(vec![]),
+    mangle_top_level,
+    "var longName = 1;
+function foo() {
+    return longName;
+}",
+    "var longName = 1;
+function foo() {
+    return longName;
+}"
+);
+
+test!(
+    Default::default(),
+    |_| mangle(vec![]),
+    mangle_shorthand,
+    "function foo(x) {
+    var { y } = x;
+    return { x, y };
+}",
+    "function foo(a) {
+    var { y: b } = a;
+    return { x: a, y: b };
+}"
+);
+
+test!(
+    Default::default(),
+    |_| mangle(vec![]),
+    mangle_eval,
+    "function foo(x) {
+    eval('x');
+}",
+    "function foo(x) {
+    eval('x');
+}"
+);
+
+test!(
+    Default::default(),
+    |_| compressor(),
+    compress_seq,
+    "foo();
+bar();
+baz();",
+    "foo(), bar(), baz();"
+);
+
+test!(
+    Default::default(),
+    |_| compressor(),
+    compress_seq_return,
+    "function foo() {
+    bar();
+    return baz();
+}",
+    "function foo() {
+    return bar(), baz();
+}"
+);
+
+test!(
+    Default::default(),
+    |_| compressor(),
+    compress_directive,
+    "'use strict';
+foo();",
+    "'use strict';
+foo();"
+);
+
+test!(
+    Default::default(),
+    |_| compressor(),
+    compress_if,
+    "if (a) b();
+if (!a) {
+    b();
+}",
+    "a && b(), a || b();"
+);
+
+test!(
+    Default::default(),
+    |_| compressor(),
+    compress_if_else,
+    "if (a) b();
+else c();",
+    "a ? b() : c();"
+);
+
+test!(
+    Default::default(),
+    |_| compressor(),
+    compress_if_return,
+    "function foo(a) {
+    if (a) return 1;
+    else return 2;
+}",
+    "function foo(a) {
+    return a ? 1 : 2;
+}"
+);
+
+test!(
+    Default::default(),
+    |_| compressor(),
+    compress_vars,
+    "var a = 1;
+var b = 2;
+let c = 3;
+var d = 4;",
+    "var a = 1, b = 2;
+let c = 3;
+var d = 4;"
+);
+
+test!(
+    Default::default(),
+    |_| compressor(),
+    compress_literals,
+    "x = true;
+y = false;
+z = undefined;",
+    "x = !0, y = !1, z = void 0;"
+);
+
+test!(
+    Default::default(),
+    |_| compressor(),
+    compress_undefined_declared,
+    "function foo(undefined) {
+    return undefined;
+}",
+    "function foo(undefined) {
+    return undefined;
+}"
+);
+
+test!(
+    Default::default(),
+    |_| compressor(),
+    compress_undefined_member,
+    "a.undefined;
+a[undefined];",
+    "a.undefined, a[void 0];"
+);
+
+test!(
+    Default::default(),
+    |_| compressor(),
+    compress_undefined_assign,
+    "undefined = 1;
+undefined++;
+[undefined] = a;",
+    "undefined = 1, undefined++, [undefined] = a;"
+);
@@ -123,16 +123,8 @@ impl Fold<ExportDefaultDecl> for Dce<'_> {
     }
 }
 
-impl Fold<ExportAll> for Dce<'_> {
-    fn fold(&mut self, node: ExportAll) -> ExportAll {
-        if self.is_marked(node.span) {
-            return node;
-        }
-
-        unimplemented!("dce: `export * from 'foo'`")
-    }
-}
-
+// Names exported by `export * from 'foo'` are not known, so it's preserved.
+preserve!(ExportAll);
 preserve!(TsImportEqualsDecl);
 preserve!(TsExportAssignment);
 preserve!(TsNamespaceExportDecl);
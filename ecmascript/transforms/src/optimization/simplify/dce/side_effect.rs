@@ -272,6 +272,12 @@ impl Visit<NamedExport> for SideEffectVisitor<'_> {
     }
 }
 
+impl Visit<ExportAll> for SideEffectVisitor<'_> {
+    fn visit(&mut self, _: &ExportAll) {
+        self.found = true
+    }
+}
+
 impl Visit<ExportDefaultDecl> for SideEffectVisitor<'_> {
     fn visit(&mut self, _: &ExportDefaultDecl) {
         self.found = true;
@@ -265,7 +265,16 @@ impl ClassProperties {
             (vec![], vec![], vec![], vec![], None);
         let mut used_names = vec![];
         let mut used_key_names = vec![];
-        let mut statics = HashSet::default();
+        // Collected first, as static blocks may access static private fields
+        // declared after them.
+        let statics: HashSet<JsWord> = class
+            .body
+            .iter()
+            .filter_map(|member| match member {
+                ClassMember::PrivateProp(prop) if prop.is_static => Some(prop.key.id.sym.clone()),
+                _ => None,
+            })
+            .collect();
 
         for member in class.body {
             match member {
@@ -390,9 +399,6 @@ impl ClassProperties {
                 }
                 ClassMember::PrivateProp(prop) => {
                     let prop_span = prop.span();
-                    if prop.is_static {
-                        statics.insert(prop.key.id.sym.clone());
-                    }
 
                     let ident = Ident::new(
                         format!("_{}", prop.key.id.sym).into(),
@@ -471,6 +477,53 @@ impl ClassProperties {
                     })));
                 }
 
+                ClassMember::StaticBlock(block) => {
+                    // (function () { ... })();
+                    let body = block
+                        .body
+                        .fold_with(&mut SuperFieldAccessFolder {
+                            class_name: &ident,
+                            vars: &mut vars,
+                            constructor_this_mark: None,
+                            is_static: true,
+                            folding_constructor: false,
+                            in_injected_define_property_call: false,
+                            in_nested_scope: false,
+                            this_alias_mark: None,
+                        })
+                        .fold_with(&mut ThisInStaticFolder {
+                            ident: ident.clone(),
+                        });
+
+                    let iife = CallExpr {
+                        span: block.span,
+                        callee: Expr::Fn(FnExpr {
+                            ident: None,
+                            function: Function {
+                                span: DUMMY_SP,
+                                is_async: false,
+                                is_generator: false,
+                                params: vec![],
+                                body: Some(body),
+                                decorators: Default::default(),
+                                type_params: Default::default(),
+                                return_type: Default::default(),
+                            },
+                        })
+                        .as_callee(),
+                        args: vec![],
+                        type_args: Default::default(),
+                    };
+
+                    extra_stmts.push(iife.into_stmt().fold_with(&mut FieldAccessFolder {
+                        mark: self.mark,
+                        statics: &statics,
+                        vars: vec![],
+                        class_name: &ident,
+                        in_assign_pat: false,
+                    }));
+                }
+
                 ClassMember::Constructor(c) => constructor = Some(c),
             }
         }
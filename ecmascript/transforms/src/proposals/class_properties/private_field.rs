@@ -1,4 +1,4 @@
-use crate::util::{alias_ident_for, alias_if_required, prepend, ExprFactory};
+use crate::util::{alias_ident_for, alias_if_required, prepend, undefined, ExprFactory};
 use std::{collections::HashSet, iter, mem};
 use swc_atoms::JsWord;
 use swc_common::{Fold, FoldWith, Mark, Spanned, DUMMY_SP};
@@ -233,6 +233,45 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
 
                 let value = if op == op!("=") {
                     right.as_arg()
+                } else if op == op!("??=") {
+                    let left = box self.fold_private_get(left, Some(var)).0;
+
+                    // Private fields don't have setters, so it's fine to always assign.
+                    let value = private_ident!("_value");
+                    self.vars.push(VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(value.clone()),
+                        init: None,
+                        definite: false,
+                    });
+
+                    CondExpr {
+                        span: DUMMY_SP,
+                        test: box Expr::Bin(BinExpr {
+                            span: DUMMY_SP,
+                            left: box Expr::Bin(BinExpr {
+                                span: DUMMY_SP,
+                                left: box Expr::Assign(AssignExpr {
+                                    span: DUMMY_SP,
+                                    left: PatOrExpr::Pat(box Pat::Ident(value.clone())),
+                                    op: op!("="),
+                                    right: left,
+                                }),
+                                op: op!("!=="),
+                                right: box Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+                            }),
+                            op: op!("&&"),
+                            right: box Expr::Bin(BinExpr {
+                                span: DUMMY_SP,
+                                left: box Expr::Ident(value.clone()),
+                                op: op!("!=="),
+                                right: undefined(DUMMY_SP),
+                            }),
+                        }),
+                        cons: box Expr::Ident(value),
+                        alt: right,
+                    }
+                    .as_arg()
                 } else {
                     let left = box self.fold_private_get(left, Some(var)).0;
 
@@ -254,6 +293,9 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                             op!("&=") => op!("&"),
                             op!("^=") => op!("^"),
                             op!("**=") => op!("**"),
+                            op!("&&=") => op!("&&"),
+                            op!("||=") => op!("||"),
+                            op!("??=") => unreachable!(),
                         },
                         right,
                     }
@@ -332,6 +374,37 @@ impl<'a> Fold<Expr> for FieldAccessFolder<'a> {
                     .fold_children(self)
                 }
             }
+            // `#x in obj`
+            Expr::Bin(BinExpr {
+                span,
+                op: op!("in"),
+                left: box Expr::PrivateName(n),
+                right,
+            }) => {
+                let right = right.fold_with(self);
+
+                if self.statics.contains(&n.id.sym) {
+                    // Static fields only exist on the class itself.
+                    return Expr::Bin(BinExpr {
+                        span,
+                        op: op!("==="),
+                        left: right,
+                        right: box Expr::Ident(self.class_name.clone()),
+                    });
+                }
+
+                let ident = Ident::new(
+                    format!("_{}", n.id.sym).into(),
+                    n.id.span.apply_mark(self.mark),
+                );
+
+                Expr::Call(CallExpr {
+                    span,
+                    callee: ident.member(quote_ident!("has")).as_callee(),
+                    args: vec![right.as_arg()],
+                    type_args: Default::default(),
+                })
+            }
             Expr::Member(e) => self.fold_private_get(e, None).0,
             _ => e.fold_children(self),
         }
@@ -3,7 +3,7 @@ use crate::{
     util::{
         drop_span,
         options::{CM, SESSION},
-        ExprFactory, HANDLER,
+        prepend_stmts, undefined, ExprFactory, COMMENTS, HANDLER,
     },
 };
 use dashmap::DashMap;
@@ -12,16 +12,39 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::{iter, mem, sync::Arc};
 use swc_atoms::{js_word, JsWord};
-use swc_common::{iter::IdentifyLast, FileName, Fold, FoldWith, Spanned, DUMMY_SP};
+use swc_common::{iter::IdentifyLast, FileName, Fold, FoldWith, Span, Spanned, DUMMY_SP};
 use swc_ecma_ast::*;
 use swc_ecma_parser::{Parser, SourceFileInput, Syntax};
 
 #[cfg(test)]
 mod tests;
 
+/// How JSX is compiled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Runtime {
+    /// `React.createElement(type, props, ...children)`
+    Classic,
+    /// `jsx(type, props, key)`, imported from `react/jsx-runtime`.
+    Automatic,
+}
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Runtime::Classic
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Options {
+    #[serde(default)]
+    pub runtime: Runtime,
+
+    /// Module which the automatic runtime is imported from.
+    #[serde(default = "default_import_source")]
+    pub import_source: String,
+
     #[serde(default = "default_pragma")]
     pub pragma: String,
     #[serde(default = "default_pragma_frag")]
@@ -40,6 +63,8 @@ pub struct Options {
 impl Default for Options {
     fn default() -> Self {
         Options {
+            runtime: Default::default(),
+            import_source: default_import_source(),
             pragma: default_pragma(),
             pragma_frag: default_pragma_frag(),
             throw_if_namespace: default_throw_if_namespace(),
@@ -49,6 +74,10 @@ impl Default for Options {
     }
 }
 
+fn default_import_source() -> String {
+    "react".into()
+}
+
 fn default_pragma() -> String {
     "React.createElement".into()
 }
@@ -95,8 +124,17 @@ fn parse_option(name: &str, src: String) -> Box<Expr> {
 /// `@babel/plugin-transform-react-jsx`
 ///
 /// Turn JSX into React function calls
+///
+/// The runtime can be overridden per file with `@jsxRuntime` and
+/// `@jsxImportSource` pragma comments. In a script, the automatic runtime is
+/// loaded with `require`.
 pub fn jsx(options: Options) -> impl Pass {
     Jsx {
+        runtime: options.runtime,
+        import_source: options.import_source.into(),
+        development: options.development,
+        runtime_imports: vec![],
+        import_create_element: None,
         pragma: ExprOrSuper::Expr(parse_option("pragma", options.pragma)),
         pragma_frag: ExprOrSpread {
             spread: None,
@@ -108,6 +146,14 @@ pub fn jsx(options: Options) -> impl Pass {
 }
 
 struct Jsx {
+    runtime: Runtime,
+    import_source: JsWord,
+    development: bool,
+    /// Functions imported from `jsx-runtime` of `import_source`.
+    runtime_imports: Vec<(JsWord, Ident)>,
+    /// `createElement` imported from `import_source`.
+    import_create_element: Option<Ident>,
+
     pragma: ExprOrSuper,
     pragma_frag: ExprOrSpread,
     use_builtins: bool,
@@ -120,6 +166,19 @@ impl Jsx {
     fn jsx_frag_to_expr(&mut self, el: JSXFragment) -> Expr {
         let span = el.span();
 
+        if self.runtime == Runtime::Automatic {
+            let fragment = self.import_runtime("Fragment");
+            let children = self.jsx_children(el.children);
+            return self.automatic_call(
+                span,
+                box Expr::Ident(fragment),
+                vec![],
+                children,
+                None,
+                None,
+            );
+        }
+
         Expr::Call(CallExpr {
             span,
             callee: self.pragma.clone(),
@@ -142,9 +201,30 @@ impl Jsx {
 
         let name = self.jsx_name(el.opening.name);
 
+        let callee = match self.runtime {
+            Runtime::Classic => self.pragma.clone(),
+            Runtime::Automatic => {
+                // `key` can't be passed separately if it's overridden by a spread.
+                let key_after_spread = el
+                    .opening
+                    .attrs
+                    .iter()
+                    .skip_while(|attr| match attr {
+                        JSXAttrOrSpread::SpreadElement(..) => false,
+                        _ => true,
+                    })
+                    .any(is_key);
+                if !key_after_spread {
+                    return self.jsx_elem_to_automatic(span, name, el.opening.attrs, el.children);
+                }
+
+                self.import_create_element().as_callee()
+            }
+        };
+
         Expr::Call(CallExpr {
             span,
-            callee: self.pragma.clone(),
+            callee,
             args: iter::once(name.as_arg())
                 .chain(iter::once({
                     // Attributes
@@ -161,6 +241,221 @@ impl Jsx {
         })
     }
 
+    /// `jsx(type, props, key)`
+    fn jsx_elem_to_automatic(
+        &mut self,
+        span: Span,
+        name: Box<Expr>,
+        attrs: Vec<JSXAttrOrSpread>,
+        children: Vec<JSXElementChild>,
+    ) -> Expr {
+        let mut key = None;
+        let mut source = None;
+        let mut this = None;
+        let mut props = vec![];
+
+        for attr in attrs {
+            match attr {
+                JSXAttrOrSpread::JSXAttr(attr) => {
+                    let name = match attr.name {
+                        JSXAttrName::Ident(ref i) => Some(i.sym.clone()),
+                        _ => None,
+                    };
+                    let prop = attr_to_prop(attr).fold_with(self);
+                    match name.as_ref().map(|name| &**name) {
+                        Some("key") => key = Some(prop),
+                        Some("__source") if self.development => source = Some(prop),
+                        Some("__self") if self.development => this = Some(prop),
+                        _ => props.push(PropOrSpread::Prop(box prop)),
+                    }
+                }
+                JSXAttrOrSpread::SpreadElement(e) => props.push(PropOrSpread::Spread(e)),
+            }
+        }
+
+        let prop_value = |prop: Prop| match prop {
+            Prop::KeyValue(KeyValueProp { value, .. }) => value,
+            _ => unreachable!("attr_to_prop() returns a key-value property"),
+        };
+        let key = key.map(prop_value);
+        let dev_source = match (source, this) {
+            (None, None) => None,
+            (source, this) => Some((
+                source
+                    .map(prop_value)
+                    .unwrap_or_else(|| undefined(DUMMY_SP)),
+                this.map(prop_value).unwrap_or_else(|| undefined(DUMMY_SP)),
+            )),
+        };
+
+        let children = self.jsx_children(children);
+        self.automatic_call(span, name, props, children, key, dev_source)
+    }
+
+    /// Creates a call to `jsx`, `jsxs` or `jsxDEV`.
+    ///
+    /// `children` are merged into `props`.
+    fn automatic_call(
+        &mut self,
+        span: Span,
+        name: Box<Expr>,
+        mut props: Vec<PropOrSpread>,
+        mut children: Vec<Box<Expr>>,
+        key: Option<Box<Expr>>,
+        dev_source: Option<(Box<Expr>, Box<Expr>)>,
+    ) -> Expr {
+        let is_static = children.len() > 1;
+
+        let children = match children.len() {
+            0 => None,
+            1 => children.pop(),
+            _ => Some(box Expr::Array(ArrayLit {
+                span: DUMMY_SP,
+                elems: children.into_iter().map(|c| Some(c.as_arg())).collect(),
+            })),
+        };
+        if let Some(children) = children {
+            props.push(PropOrSpread::Prop(box Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(quote_ident!("children")),
+                value: children,
+            })));
+        }
+
+        let callee = if self.development {
+            self.import_runtime("jsxDEV")
+        } else if is_static {
+            self.import_runtime("jsxs")
+        } else {
+            self.import_runtime("jsx")
+        };
+
+        let mut args = vec![name.as_arg(), self.props_to_expr(props).as_arg()];
+        if self.development {
+            args.push(key.unwrap_or_else(|| undefined(DUMMY_SP)).as_arg());
+            args.push(
+                Lit::Bool(Bool {
+                    span: DUMMY_SP,
+                    value: is_static,
+                })
+                .as_arg(),
+            );
+            if let Some((source, this)) = dev_source {
+                args.push(source.as_arg());
+                args.push(this.as_arg());
+            }
+        } else if let Some(key) = key {
+            args.push(key.as_arg());
+        }
+
+        Expr::Call(CallExpr {
+            span,
+            callee: callee.as_callee(),
+            args,
+            type_args: None,
+        })
+    }
+
+    fn jsx_children(&mut self, children: Vec<JSXElementChild>) -> Vec<Box<Expr>> {
+        children
+            .into_iter()
+            .filter_map(|c| self.jsx_elem_child_to_expr(c))
+            .map(|c| c.expr)
+            .collect()
+    }
+
+    /// Returns the local name of `name`, which is imported from the runtime
+    /// module.
+    fn import_runtime(&mut self, name: &str) -> Ident {
+        if let Some((_, local)) = self.runtime_imports.iter().find(|(n, _)| *n == *name) {
+            return local.clone();
+        }
+
+        let local = private_ident!(format!("_{}", name));
+        self.runtime_imports.push((name.into(), local.clone()));
+        local
+    }
+
+    fn import_create_element(&mut self) -> Ident {
+        self.import_create_element
+            .get_or_insert_with(|| private_ident!("_createElement"))
+            .clone()
+    }
+
+    /// Applies `@jsxRuntime` and `@jsxImportSource` pragmas in leading
+    /// comments of the file.
+    fn parse_pragmas<T: Spanned>(&mut self, span: Span, body: &[T]) {
+        static RUNTIME: Lazy<Regex> = Lazy::new(|| Regex::new("@jsxRuntime\\s+(\\S+)").unwrap());
+        static IMPORT_SOURCE: Lazy<Regex> =
+            Lazy::new(|| Regex::new("@jsxImportSource\\s+(\\S+)").unwrap());
+
+        if !COMMENTS.is_set() {
+            return;
+        }
+
+        let mut positions = vec![span.lo()];
+        if let Some(item) = body.first() {
+            positions.push(item.span().lo());
+        }
+
+        COMMENTS.with(|comments| {
+            for pos in positions {
+                let cmts = match comments.leading_comments(pos) {
+                    Some(cmts) => cmts,
+                    None => continue,
+                };
+
+                for cmt in cmts.iter() {
+                    if let Some(runtime) = RUNTIME.captures(&cmt.text) {
+                        self.runtime = match &runtime[1] {
+                            "classic" => Runtime::Classic,
+                            "automatic" => Runtime::Automatic,
+                            _ => {
+                                HANDLER.with(|handler| {
+                                    handler
+                                        .struct_span_err(
+                                            cmt.span,
+                                            "Runtime must be either \"classic\" or \"automatic\"",
+                                        )
+                                        .emit()
+                                });
+                                continue;
+                            }
+                        };
+                    }
+
+                    if let Some(import_source) = IMPORT_SOURCE.captures(&cmt.text) {
+                        self.import_source = import_source[1].into();
+                    }
+                }
+            }
+        });
+    }
+
+    /// Returns the modules to import and the names imported from each
+    /// module, used by the automatic runtime.
+    fn take_imports(&mut self) -> Vec<(JsWord, Vec<(JsWord, Ident)>)> {
+        let mut imports = vec![];
+
+        let runtime_imports = mem::replace(&mut self.runtime_imports, vec![]);
+        if !runtime_imports.is_empty() {
+            let src = if self.development {
+                format!("{}/jsx-dev-runtime", self.import_source)
+            } else {
+                format!("{}/jsx-runtime", self.import_source)
+            };
+            imports.push((src.into(), runtime_imports));
+        }
+
+        if let Some(local) = self.import_create_element.take() {
+            imports.push((
+                self.import_source.clone(),
+                vec![("createElement".into(), local)],
+            ));
+        }
+
+        imports
+    }
+
     fn jsx_elem_child_to_expr(&mut self, c: JSXElementChild) -> Option<ExprOrSpread> {
         Some(match c {
             JSXElementChild::JSXText(text) => {
@@ -197,8 +492,23 @@ impl Jsx {
             return box Expr::Lit(Lit::Null(Null { span: DUMMY_SP }));
         }
 
-        let is_complex = attrs.iter().any(|a| match *a {
-            JSXAttrOrSpread::SpreadElement(..) => true,
+        let props = attrs
+            .into_iter()
+            .map(|attr| match attr {
+                JSXAttrOrSpread::JSXAttr(a) => {
+                    PropOrSpread::Prop(box attr_to_prop(a).fold_with(self))
+                }
+                JSXAttrOrSpread::SpreadElement(e) => PropOrSpread::Spread(e),
+            })
+            .collect();
+
+        self.props_to_expr(props)
+    }
+
+    /// Creates an object from `props`, using `_extends` for spreads.
+    fn props_to_expr(&mut self, props: Vec<PropOrSpread>) -> Box<Expr> {
+        let is_complex = props.iter().any(|p| match *p {
+            PropOrSpread::Spread(..) => true,
             _ => false,
         });
 
@@ -218,12 +528,10 @@ impl Jsx {
                     }
                 }};
             }
-            for attr in attrs {
-                match attr {
-                    JSXAttrOrSpread::JSXAttr(a) => {
-                        cur_obj_props.push(PropOrSpread::Prop(box attr_to_prop(a)))
-                    }
-                    JSXAttrOrSpread::SpreadElement(e) => {
+            for prop in props {
+                match prop {
+                    PropOrSpread::Prop(..) => cur_obj_props.push(prop),
+                    PropOrSpread::Spread(e) => {
                         check!();
                         args.push(e.expr.as_arg());
                     }
@@ -247,22 +555,59 @@ impl Jsx {
         } else {
             box Expr::Object(ObjectLit {
                 span: DUMMY_SP,
-                props: attrs
-                    .into_iter()
-                    .map(|a| match a {
-                        JSXAttrOrSpread::JSXAttr(a) => a,
-                        _ => unreachable!(),
-                    })
-                    .map(attr_to_prop)
-                    .map(|v| v.fold_with(self))
-                    .map(Box::new)
-                    .map(PropOrSpread::Prop)
-                    .collect(),
+                props,
             })
         }
     }
 }
 
+impl Fold<Module> for Jsx {
+    fn fold(&mut self, module: Module) -> Module {
+        let runtime = self.runtime;
+        let import_source = self.import_source.clone();
+        self.parse_pragmas(module.span, &module.body);
+
+        let mut module = module.fold_children(self);
+        if self.runtime == Runtime::Automatic {
+            let imports = self.take_imports();
+            prepend_stmts(
+                &mut module.body,
+                imports
+                    .into_iter()
+                    .map(|(src, names)| import_decl(src, names)),
+            );
+        }
+
+        self.runtime = runtime;
+        self.import_source = import_source;
+        module
+    }
+}
+
+/// Scripts cannot import, so the automatic runtime is loaded with `require`.
+impl Fold<Script> for Jsx {
+    fn fold(&mut self, script: Script) -> Script {
+        let runtime = self.runtime;
+        let import_source = self.import_source.clone();
+        self.parse_pragmas(script.span, &script.body);
+
+        let mut script = script.fold_children(self);
+        if self.runtime == Runtime::Automatic {
+            let imports = self.take_imports();
+            prepend_stmts(
+                &mut script.body,
+                imports
+                    .into_iter()
+                    .map(|(src, names)| require_decl(src, names)),
+            );
+        }
+
+        self.runtime = runtime;
+        self.import_source = import_source;
+        script
+    }
+}
+
 impl Fold<Expr> for Jsx {
     fn fold(&mut self, expr: Expr) -> Expr {
         let expr = expr.fold_children(self);
@@ -362,6 +707,77 @@ impl Jsx {
     }
 }
 
+fn is_key(attr: &JSXAttrOrSpread) -> bool {
+    match attr {
+        JSXAttrOrSpread::JSXAttr(JSXAttr {
+            name: JSXAttrName::Ident(i),
+            ..
+        }) => i.sym == *"key",
+        _ => false,
+    }
+}
+
+/// `import { jsx as _jsx } from 'react/jsx-runtime';`
+fn import_decl(src: JsWord, names: Vec<(JsWord, Ident)>) -> ModuleItem {
+    ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+        span: DUMMY_SP,
+        specifiers: names
+            .into_iter()
+            .map(|(name, local)| {
+                ImportSpecifier::Named(ImportNamedSpecifier {
+                    span: DUMMY_SP,
+                    local,
+                    imported: Some(Ident::new(name, DUMMY_SP)),
+                })
+            })
+            .collect(),
+        src: Str {
+            span: DUMMY_SP,
+            value: src,
+            has_escape: false,
+        },
+        type_only: false,
+    }))
+}
+
+/// `var { jsx: _jsx } = require('react/jsx-runtime');`
+fn require_decl(src: JsWord, names: Vec<(JsWord, Ident)>) -> Stmt {
+    Stmt::Decl(Decl::Var(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Object(ObjectPat {
+                span: DUMMY_SP,
+                props: names
+                    .into_iter()
+                    .map(|(name, local)| {
+                        ObjectPatProp::KeyValue(KeyValuePatProp {
+                            key: PropName::Ident(Ident::new(name, DUMMY_SP)),
+                            value: box Pat::Ident(local),
+                        })
+                    })
+                    .collect(),
+                optional: false,
+                type_ann: None,
+            }),
+            init: Some(box Expr::Call(CallExpr {
+                span: DUMMY_SP,
+                callee: quote_ident!("require").as_callee(),
+                args: vec![Lit::Str(Str {
+                    span: DUMMY_SP,
+                    value: src,
+                    has_escape: false,
+                })
+                .as_arg()],
+                type_args: Default::default(),
+            })),
+            definite: false,
+        }],
+        declare: false,
+    }))
+}
+
 fn attr_to_prop(a: JSXAttr) -> Prop {
     let key = to_prop_name(a.name);
     let value = a
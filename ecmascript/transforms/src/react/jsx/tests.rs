@@ -1145,3 +1145,136 @@ test!(
     "let page = React.createElement('p', null, 'Click ', React.createElement('em', null, 'New \
      melody'), ' listen to a randomly generated melody');"
 );
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(Options {
+        runtime: Runtime::Automatic,
+        ..Default::default()
+    }),
+    automatic_runtime,
+    r#"
+var x = <div className="a" key="k">foo</div>;
+var y = <><A /><B /></>;
+"#,
+    r#"
+import { jsx as _jsx, Fragment as _Fragment, jsxs as _jsxs } from "react/jsx-runtime";
+var x = _jsx("div", {
+    className: "a",
+    children: "foo"
+}, "k");
+var y = _jsxs(_Fragment, {
+    children: [_jsx(A, {}), _jsx(B, {})]
+});
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(Options {
+        runtime: Runtime::Automatic,
+        ..Default::default()
+    }),
+    automatic_runtime_key_after_spread,
+    r#"
+var x = <div key="a" {...props} />;
+var y = <div {...props} key="b">foo</div>;
+"#,
+    r#"
+import { jsx as _jsx } from "react/jsx-runtime";
+import { createElement as _createElement } from "react";
+var x = _jsx("div", _extends({}, props), "a");
+var y = _createElement("div", _extends({}, props, {
+    key: "b"
+}), "foo");
+"#
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(Options {
+        runtime: Runtime::Automatic,
+        development: true,
+        ..Default::default()
+    }),
+    automatic_runtime_development,
+    r#"
+var x = <div key="k">{a}{b}</div>;
+var y = <span />;
+"#,
+    r#"
+import { jsxDEV as _jsxDEV } from "react/jsx-dev-runtime";
+var x = _jsxDEV("div", {
+    children: [a, b]
+}, "k", true);
+var y = _jsxDEV("span", {}, void 0, false);
+"#
+);
+
+#[test]
+fn automatic_runtime_script() {
+    crate::tests::Tester::run(|tester| {
+        let syntax = ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+            jsx: true,
+            ..Default::default()
+        });
+        let script = tester.with_parser(
+            "input.js",
+            syntax,
+            "var x = <div {...b} key={a} />;\nvar y = <A />;",
+            |p| {
+                p.parse_script().map_err(|mut e| {
+                    e.emit();
+                })
+            },
+        )?;
+        let script = script.fold_with(&mut jsx(Options {
+            runtime: Runtime::Automatic,
+            ..Default::default()
+        }));
+
+        let actual = tester.print(&Module {
+            span: script.span,
+            body: script.body.into_iter().map(ModuleItem::Stmt).collect(),
+            shebang: None,
+        });
+        let expected = tester.parse_module(
+            "expected.js",
+            r#"var { jsx: _jsx } = require("react/jsx-runtime");
+var { createElement: _createElement } = require("react");
+var x = _createElement("div", _extends({}, b, {
+    key: a
+}));
+var y = _jsx(A, {});"#,
+        )?;
+        assert_eq!(actual, tester.print(&expected));
+
+        Ok(())
+    });
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::Es(::swc_ecma_parser::EsConfig {
+        jsx: true,
+        ..Default::default()
+    }),
+    |_| tr(Default::default()),
+    automatic_runtime_pragma,
+    r#"
+/** @jsxRuntime automatic @jsxImportSource preact */
+var x = <div />;
+"#,
+    r#"
+import { jsx as _jsx } from "preact/jsx-runtime";
+var x = _jsx("div", {});
+"#
+);
@@ -274,6 +274,24 @@ impl Fold<ClassMethod> for Resolver<'_> {
     }
 }
 
+impl Fold<StaticBlock> for Resolver<'_> {
+    fn fold(&mut self, b: StaticBlock) -> StaticBlock {
+        // `var`s in a static block are not hoisted out of it.
+        let child_mark = Mark::fresh(self.mark);
+
+        let mut child = Resolver::new(
+            child_mark,
+            Scope::new(ScopeKind::Fn, Some(&self.current)),
+            None,
+        );
+
+        StaticBlock {
+            body: b.body.fold_children(&mut child),
+            ..b
+        }
+    }
+}
+
 impl Fold<MethodProp> for Resolver<'_> {
     fn fold(&mut self, m: MethodProp) -> MethodProp {
         let key = m.key.fold_with(self);
@@ -547,6 +565,21 @@ impl Fold<Vec<ModuleItem>> for Resolver<'_> {
     }
 }
 
+impl Fold<ModuleDecl> for Resolver<'_> {
+    fn fold(&mut self, decl: ModuleDecl) -> ModuleDecl {
+        match decl {
+            // `TsImportEqualsDecl` is skipped by `noop_fold_type!`, but `id` is a
+            // binding just like the local of an import specifier.
+            ModuleDecl::TsImportEquals(import) => {
+                let id = self.fold_binding_ident(import.id);
+
+                ModuleDecl::TsImportEquals(TsImportEqualsDecl { id, ..import })
+            }
+            _ => decl.fold_children(self),
+        }
+    }
+}
+
 impl Fold<CatchClause> for Resolver<'_> {
     fn fold(&mut self, c: CatchClause) -> CatchClause {
         let child_mark = Mark::fresh(self.mark);
@@ -589,6 +622,12 @@ impl Fold<Function> for Hoister<'_, '_> {
     }
 }
 
+impl Fold<StaticBlock> for Hoister<'_, '_> {
+    fn fold(&mut self, node: StaticBlock) -> StaticBlock {
+        node
+    }
+}
+
 impl Fold<ArrowExpr> for Hoister<'_, '_> {
     fn fold(&mut self, node: ArrowExpr) -> ArrowExpr {
         node
@@ -624,6 +663,14 @@ impl Fold<Pat> for Hoister<'_, '_> {
     }
 }
 
+impl Fold<AssignPatProp> for Hoister<'_, '_> {
+    fn fold(&mut self, node: AssignPatProp) -> AssignPatProp {
+        let key = self.resolver.fold_binding_ident(node.key);
+
+        AssignPatProp { key, ..node }
+    }
+}
+
 impl Fold<PatOrExpr> for Hoister<'_, '_> {
     #[inline(always)]
     fn fold(&mut self, node: PatOrExpr) -> PatOrExpr {
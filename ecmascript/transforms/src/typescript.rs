@@ -2,19 +2,52 @@ use crate::{
     pass::Pass,
     util::{prepend_stmts, var::VarCollector, ExprFactory},
 };
-use fxhash::FxHashMap;
-use swc_atoms::js_word;
+use fxhash::{FxHashMap, FxHashSet};
+use std::mem;
+use swc_atoms::{js_word, JsWord};
 use swc_common::{util::move_map::MoveMap, Fold, FoldWith, Spanned, Visit, VisitWith, DUMMY_SP};
 use swc_ecma_ast::*;
-use swc_ecma_utils::{ident::IdentLike, Id};
+use swc_ecma_utils::{find_ids, ident::IdentLike, Id};
+
+pub use self::enums::EnumValues;
+
+mod enums;
 
 /// Strips type annotations out.
 pub fn strip() -> impl Pass {
-    Strip::default()
+    strip_with_config(Default::default())
+}
+
+pub fn strip_with_config(config: Config) -> impl Pass {
+    Strip {
+        config,
+        ..Default::default()
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Config {
+    /// If `true`, `import foo = require('foo')` and `export = foo` are
+    /// preserved so that the `common_js` pass can lower them to
+    /// `require('foo')` and `module.exports`.
+    ///
+    /// Otherwise, they are converted to `import * as foo from 'foo'` and
+    /// `export default foo`.
+    pub preserve_import_export_assign: bool,
+
+    /// `const enum`s exported by other modules, keyed by the import source
+    /// and then the exported name. Members of these enums are inlined.
+    ///
+    /// `ModuleTypeInfo::const_enums` of the type checker can be used to
+    /// build this.
+    pub imported_enums: FxHashMap<JsWord, FxHashMap<JsWord, EnumValues>>,
 }
 
 #[derive(Default)]
 struct Strip {
+    config: Config,
+    /// Values of `const enum`s, which are inlined.
+    enums: FxHashMap<Id, EnumValues>,
     non_top_level: bool,
     scope: Scope,
     phase: Phase,
@@ -84,12 +117,23 @@ impl Strip {
                 }
             }
 
-            Decl::TsEnum(TsEnumDecl { ref id, .. }) => {
-                // Currently swc cannot remove constant enums
-                store!(id.sym, id.span.ctxt(), true);
+            Decl::TsEnum(TsEnumDecl {
+                ref id, is_const, ..
+            }) => {
+                // Constant enums are inlined
+                if !is_const {
+                    store!(id.sym, id.span.ctxt(), true);
+                }
                 store!(id.sym, id.span.ctxt(), false);
             }
 
+            Decl::TsModule(ref m) if is_instantiated(m) => {
+                if let TsModuleName::Ident(ref id) = m.id {
+                    store!(id.sym, id.span.ctxt(), true);
+                    store!(id.sym, id.span.ctxt(), false);
+                }
+            }
+
             Decl::TsInterface(TsInterfaceDecl { ref id, .. })
             | Decl::TsModule(TsModuleDecl {
                 id: TsModuleName::Ident(ref id),
@@ -230,6 +274,7 @@ impl Fold<Vec<ModuleItem>> for Strip {
         self.phase = Phase::DropImports;
 
         // Second pass
+        let mut declared = declared_values(&items);
         let mut stmts = Vec::with_capacity(items.len());
         for item in items {
             self.was_side_effect_import = false;
@@ -251,6 +296,18 @@ impl Fold<Vec<ModuleItem>> for Strip {
                     }
                 }
 
+                // `const enum`s are inlined.
+                ModuleItem::Stmt(Stmt::Decl(Decl::TsEnum(TsEnumDecl {
+                    is_const: true, ..
+                })))
+                | ModuleItem::Stmt(Stmt::Decl(Decl::TsEnum(TsEnumDecl {
+                    declare: true, ..
+                })))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::TsEnum(TsEnumDecl { declare: true, .. }),
+                    ..
+                })) => continue,
+
                 ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
                     decl: Decl::TsEnum(e),
                     ..
@@ -294,6 +351,14 @@ impl Fold<Vec<ModuleItem>> for Strip {
                     self.handle_enum(e, &mut stmts)
                 }
 
+                ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(m))) => {
+                    self.handle_ts_module(m, false, &mut declared, &mut stmts)
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::TsModule(m),
+                    ..
+                })) => self.handle_ts_module(m, true, &mut declared, &mut stmts),
+
                 ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
                     expr: box Expr::Ident(ref i),
                     ..
@@ -318,14 +383,17 @@ impl Fold<Vec<ModuleItem>> for Strip {
                     ..
                 })))
                 | ModuleItem::Stmt(Stmt::Decl(Decl::TsInterface(..)))
-                | ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(..)))
                 | ModuleItem::Stmt(Stmt::Decl(Decl::TsTypeAlias(..)))
                 | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
                     decl: Decl::TsInterface(..),
                     ..
                 }))
                 | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
-                    decl: Decl::TsModule(..),
+                    decl: Decl::Var(VarDecl { declare: true, .. }),
+                    ..
+                }))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    decl: Decl::Class(ClassDecl { declare: true, .. }),
                     ..
                 }))
                 | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
@@ -355,26 +423,20 @@ impl Fold<Vec<ModuleItem>> for Strip {
                 | ModuleItem::ModuleDecl(ModuleDecl::TsNamespaceExport(..)) => continue,
 
                 ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import)) => {
-                    if !import.is_export {
+                    if import.declare || (!import.is_export && !self.is_value(&import.id)) {
                         continue;
                     }
 
-                    stmts.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
-                        span: DUMMY_SP,
-                        decl: Decl::Var(VarDecl {
-                            span: DUMMY_SP,
-                            kind: VarDeclKind::Var,
-                            decls: vec![VarDeclarator {
-                                span: DUMMY_SP,
-                                name: Pat::Ident(import.id),
-                                init: Some(box module_ref_to_expr(import.module_ref)),
-                                definite: false,
-                            }],
-                            declare: false,
-                        }),
-                    })));
+                    self.handle_import_equals(import, &mut stmts)
                 }
 
+                ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(export))
+                    if self.config.preserve_import_export_assign =>
+                {
+                    stmts.push(ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(
+                        export.fold_with(self),
+                    )))
+                }
                 ModuleItem::ModuleDecl(ModuleDecl::TsExportAssignment(export)) => {
                     stmts.push(ModuleItem::ModuleDecl(
                         ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
@@ -432,106 +494,283 @@ impl Strip {
         node
     }
 
+    fn collect_enums(&mut self, m: &Module) {
+        for item in &m.body {
+            let import = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => import,
+                _ => continue,
+            };
+            let enums = match self.config.imported_enums.get(&import.src.value) {
+                Some(enums) => enums,
+                None => continue,
+            };
+
+            for s in &import.specifiers {
+                if let ImportSpecifier::Named(ImportNamedSpecifier {
+                    local, imported, ..
+                }) = s
+                {
+                    let name = imported.as_ref().unwrap_or(local);
+                    if let Some(values) = enums.get(&name.sym) {
+                        self.enums.insert(local.to_id(), values.clone());
+                    }
+                }
+            }
+        }
+
+        let mut v = enums::ConstEnumCollector {
+            enums: mem::take(&mut self.enums),
+        };
+        m.visit_with(&mut v);
+        self.enums = v.enums;
+    }
+
+    /// Replaces a member of a `const enum` with its value.
+    fn inline_enum_member(&mut self, e: &MemberExpr) -> Option<Expr> {
+        let obj = match e.obj {
+            ExprOrSuper::Expr(box Expr::Ident(ref obj)) => obj,
+            _ => return None,
+        };
+        let value = enums::member_value(&self.enums, obj, &e.prop, e.computed)?.clone();
+
+        // Imports of enums are removed if all usages are inlined.
+        self.scope
+            .imported_idents
+            .entry(obj.to_id())
+            .and_modify(|v| v.has_type = true);
+
+        Some(enums::lit_to_expr(value))
+    }
+
+    /// Returns `true` if `i`, declared by `import i = ...`, is used as a value.
+    ///
+    /// Like `tsc`, unused aliases and aliases used only as types are elided.
+    fn is_value(&self, i: &Ident) -> bool {
+        self.scope
+            .imported_idents
+            .get(&i.to_id())
+            .map_or(false, |v| v.has_concrete)
+    }
+
+    fn handle_import_equals(&mut self, import: TsImportEqualsDecl, stmts: &mut Vec<ModuleItem>) {
+        let TsImportEqualsDecl {
+            span,
+            is_export,
+            id,
+            module_ref,
+            ..
+        } = import;
+
+        match module_ref {
+            // import A = B.C;
+            //  -> var A = B.C;
+            TsModuleRef::TsEntityName(name) => {
+                let decl = Decl::Var(VarDecl {
+                    span,
+                    kind: VarDeclKind::Var,
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(id),
+                        init: Some(box ts_entity_name_to_expr(name)),
+                        definite: false,
+                    }],
+                    declare: false,
+                });
+                stmts.push(if is_export {
+                    ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { span, decl }))
+                } else {
+                    Stmt::Decl(decl).into()
+                });
+            }
+
+            TsModuleRef::TsExternalModuleRef(module_ref) => {
+                if self.config.preserve_import_export_assign {
+                    stmts.push(ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(
+                        TsImportEqualsDecl {
+                            span,
+                            declare: false,
+                            is_export,
+                            id,
+                            module_ref: TsModuleRef::TsExternalModuleRef(module_ref),
+                        },
+                    )));
+                    return;
+                }
+
+                // import foo = require('foo');
+                //  -> import * as foo from 'foo';
+                stmts.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span,
+                    specifiers: vec![ImportSpecifier::Namespace(ImportStarAsSpecifier {
+                        span: id.span,
+                        local: id.clone(),
+                    })],
+                    src: module_ref.expr,
+                    type_only: false,
+                })));
+
+                if is_export {
+                    stmts.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                        NamedExport {
+                            span,
+                            specifiers: vec![ExportSpecifier::Named(ExportNamedSpecifier {
+                                span: id.span,
+                                orig: id,
+                                exported: None,
+                            })],
+                            src: None,
+                            type_only: false,
+                        },
+                    )));
+                }
+            }
+        }
+    }
+
     fn handle_enum(&mut self, e: TsEnumDecl, stmts: &mut Vec<ModuleItem>) {
+        let values = enums::compute_values(&e, &self.enums);
         let id = e.id;
-        stmts.push(
-            CallExpr {
-                span: DUMMY_SP,
-                callee: FnExpr {
-                    ident: None,
-                    function: Function {
+        let body = e
+            .members
+            .into_iter()
+            .enumerate()
+            .map(|(i, m)| {
+                let name = match m.id {
+                    TsEnumMemberId::Str(s) => s,
+                    TsEnumMemberId::Ident(i) => Str {
+                        span: i.span,
+                        value: i.sym,
+                        has_escape: false,
+                    },
+                };
+                let member = |name: Str| {
+                    box Expr::Member(MemberExpr {
                         span: DUMMY_SP,
-                        decorators: Default::default(),
-                        is_async: false,
-                        is_generator: false,
-                        type_params: Default::default(),
-                        params: vec![Param {
-                            span: id.span,
-                            decorators: vec![],
-                            pat: Pat::Ident(id.clone()),
-                        }],
-                        body: Some(BlockStmt {
-                            span: DUMMY_SP,
-                            stmts: e
-                                .members
-                                .into_iter()
-                                .enumerate()
-                                .map(|(i, m)| {
-                                    let value = match m.id {
-                                        TsEnumMemberId::Str(s) => s,
-                                        TsEnumMemberId::Ident(i) => Str {
-                                            span: i.span,
-                                            value: i.sym,
-                                            has_escape: false,
-                                        },
-                                    };
-                                    let prop = if let Some(_) = &m.init {
-                                        box Expr::Lit(Lit::Str(value.clone()))
-                                    } else {
-                                        box Expr::Assign(AssignExpr {
-                                            span: DUMMY_SP,
-                                            left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
-                                                span: DUMMY_SP,
-                                                obj: id.clone().as_obj(),
-                                                prop: box Expr::Lit(Lit::Str(value.clone())),
-                                                computed: true,
-                                            })),
-                                            op: op!("="),
-                                            right: box Expr::Lit(Lit::Num(Number {
-                                                span: DUMMY_SP,
-                                                value: i as _,
-                                            })),
-                                        })
-                                    };
-
-                                    // Foo[Foo["a"] = 0] = "a";
-                                    AssignExpr {
-                                        span: DUMMY_SP,
-                                        left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
-                                            obj: id.clone().as_obj(),
-                                            span: DUMMY_SP,
-                                            computed: true,
-
-                                            // Foo["a"] = 0
-                                            prop,
-                                        })),
-                                        op: op!("="),
-                                        right: m.init.unwrap_or_else(|| {
-                                            box Expr::Lit(Lit::Str(Str {
-                                                span: DUMMY_SP,
-                                                value: value.value,
-                                                has_escape: false,
-                                            }))
-                                        }),
-                                    }
-                                    .into_stmt()
-                                })
-                                .collect(),
+                        obj: id.clone().as_obj(),
+                        prop: box Expr::Lit(Lit::Str(name)),
+                        computed: true,
+                    })
+                };
+
+                // Computed values are used as initializers may refer to other
+                // members.
+                let (init, is_str) = match values.get(&name.value) {
+                    Some(value) => (
+                        box enums::lit_to_expr(value.clone()),
+                        match value {
+                            Lit::Str(..) => true,
+                            _ => false,
+                        },
+                    ),
+                    None => (
+                        m.init.unwrap_or_else(|| {
+                            box Expr::Lit(Lit::Num(Number {
+                                span: DUMMY_SP,
+                                value: i as _,
+                            }))
                         }),
-                        return_type: Default::default(),
-                    },
+                        false,
+                    ),
+                };
+
+                if is_str {
+                    // Foo["a"] = "b";
+                    return AssignExpr {
+                        span: DUMMY_SP,
+                        left: PatOrExpr::Expr(member(name)),
+                        op: op!("="),
+                        right: init,
+                    }
+                    .into_stmt();
                 }
-                .as_callee(),
-                args: vec![BinExpr {
+
+                // Foo[Foo["a"] = 0] = "a";
+                AssignExpr {
                     span: DUMMY_SP,
-                    left: box Expr::Ident(id.clone()),
-                    op: op!("||"),
-                    right: box Expr::Assign(AssignExpr {
+                    left: PatOrExpr::Expr(box Expr::Member(MemberExpr {
+                        obj: id.clone().as_obj(),
                         span: DUMMY_SP,
-                        left: PatOrExpr::Pat(Pat::Ident(id.clone()).into()),
-                        op: op!("="),
-                        right: box Expr::Object(ObjectLit {
+                        computed: true,
+
+                        // Foo["a"] = 0
+                        prop: box Expr::Assign(AssignExpr {
                             span: DUMMY_SP,
-                            props: vec![],
+                            left: PatOrExpr::Expr(member(name.clone())),
+                            op: op!("="),
+                            right: init,
                         }),
-                    }),
+                    })),
+                    op: op!("="),
+                    right: box Expr::Lit(Lit::Str(Str {
+                        span: DUMMY_SP,
+                        value: name.value,
+                        has_escape: false,
+                    })),
                 }
-                .as_arg()],
-                type_args: Default::default(),
-            }
-            .into_stmt()
-            .into(),
-        )
+                .into_stmt()
+            })
+            .collect();
+
+        stmts.push(ns_iife(id, body).into())
+    }
+
+    /// Lowers a namespace.
+    ///
+    /// ```js
+    /// var Foo;
+    /// (function (Foo) {
+    ///     Foo.x = 1;
+    /// })(Foo || (Foo = {}));
+    /// ```
+    ///
+    /// `var Foo` is omitted if `Foo` is already declared by a class, a
+    /// function, an enum or a previous namespace. `declared` also stores
+    /// members exported by previous namespaces, as they are accessible from
+    /// merged namespaces.
+    fn handle_ts_module(
+        &mut self,
+        m: TsModuleDecl,
+        is_export: bool,
+        declared: &mut FxHashMap<Id, FxHashSet<JsWord>>,
+        stmts: &mut Vec<ModuleItem>,
+    ) {
+        if !is_instantiated(&m) {
+            return;
+        }
+        let span = m.span;
+        let id = match m.id {
+            TsModuleName::Ident(id) => id,
+            TsModuleName::Str(..) => return,
+        };
+        let body = match m.body {
+            Some(TsNamespaceBody::TsModuleBlock(block)) => block.body,
+            // Nested namespaces are converted to blocks while folding.
+            _ => unreachable!("namespace with body other than TsModuleBlock"),
+        };
+
+        let exported = declared.entry(id.to_id()).or_insert_with(|| {
+            let var = Decl::Var(VarDecl {
+                span: DUMMY_SP,
+                kind: VarDeclKind::Var,
+                declare: false,
+                decls: vec![VarDeclarator {
+                    span,
+                    name: Pat::Ident(id.clone()),
+                    definite: false,
+                    init: None,
+                }],
+            });
+            stmts.push(if is_export {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { span, decl: var }))
+            } else {
+                Stmt::Decl(var).into()
+            });
+
+            Default::default()
+        });
+
+        let body = lower_ns_body(&id, body, exported);
+        stmts.push(ns_iife(id, body).into());
     }
 }
 
@@ -583,6 +822,26 @@ impl Fold<ImportDecl> for Strip {
     }
 }
 
+impl Fold<TsImportEqualsDecl> for Strip {
+    fn fold(&mut self, import: TsImportEqualsDecl) -> TsImportEqualsDecl {
+        match self.phase {
+            Phase::Analysis => {
+                if !import.is_export {
+                    self.scope
+                        .imported_idents
+                        .insert(import.id.to_id(), Default::default());
+                }
+
+                TsImportEqualsDecl {
+                    module_ref: import.module_ref.fold_with(self),
+                    ..import
+                }
+            }
+            Phase::DropImports => import,
+        }
+    }
+}
+
 impl Fold<Ident> for Strip {
     fn fold(&mut self, i: Ident) -> Ident {
         self.scope
@@ -667,12 +926,40 @@ impl Fold<Decl> for Strip {
     }
 }
 
+impl Fold<TsNamespaceBody> for Strip {
+    fn fold(&mut self, body: TsNamespaceBody) -> TsNamespaceBody {
+        match body {
+            // `namespace A.B {}` => `namespace A { export namespace B {} }`
+            TsNamespaceBody::TsNamespaceDecl(decl) => {
+                TsNamespaceBody::TsModuleBlock(TsModuleBlock {
+                    span: decl.span,
+                    body: vec![ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                        span: decl.span,
+                        decl: Decl::TsModule(TsModuleDecl {
+                            span: decl.span,
+                            declare: decl.declare,
+                            global: decl.global,
+                            id: TsModuleName::Ident(decl.id),
+                            body: Some(*decl.body),
+                        }),
+                    }))],
+                })
+                .fold_with(self)
+            }
+            TsNamespaceBody::TsModuleBlock(..) => body.fold_children(self),
+        }
+    }
+}
+
 impl Fold<Stmt> for Strip {
     fn fold(&mut self, stmt: Stmt) -> Stmt {
         let stmt = stmt.fold_children(self);
 
         match stmt {
             Stmt::Decl(decl) => match decl {
+                // Lowered by `handle_ts_module`
+                Decl::TsModule(ref m) if is_instantiated(m) => Stmt::Decl(decl),
+
                 Decl::TsInterface(..)
                 | Decl::TsModule(..)
                 | Decl::TsTypeAlias(..)
@@ -737,6 +1024,12 @@ impl Fold<Expr> for Strip {
             _ => validate!(expr),
         };
 
+        if let Expr::Member(ref e) = expr {
+            if let Some(value) = self.inline_enum_member(e) {
+                return value;
+            }
+        }
+
         let expr = match expr {
             Expr::Member(MemberExpr {
                 span,
@@ -760,14 +1053,9 @@ impl Fold<Module> for Strip {
     fn fold(&mut self, node: Module) -> Module {
         let node = validate!(node);
 
-        validate!(node.fold_children(self))
-    }
-}
+        self.collect_enums(&node);
 
-fn module_ref_to_expr(r: TsModuleRef) -> Expr {
-    match r {
-        TsModuleRef::TsEntityName(name) => ts_entity_name_to_expr(name),
-        _ => unimplemented!("export import A = B where B != TsEntityName\nB: {:?}", r),
+        validate!(node.fold_children(self))
     }
 }
 
@@ -783,3 +1071,391 @@ fn ts_entity_name_to_expr(n: TsEntityName) -> Expr {
         .into(),
     }
 }
+
+/// Returns `false` for namespaces which are not emitted by `tsc`, like
+/// `declare namespace Foo {}` or a namespace which only contains types.
+fn is_instantiated(m: &TsModuleDecl) -> bool {
+    fn is_body_instantiated(body: &TsNamespaceBody) -> bool {
+        match body {
+            TsNamespaceBody::TsModuleBlock(block) => block.body.iter().any(|item| match item {
+                ModuleItem::Stmt(Stmt::Decl(decl))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => {
+                    match decl {
+                        Decl::TsInterface(..) | Decl::TsTypeAlias(..) => false,
+                        Decl::TsModule(m) => is_instantiated(m),
+                        Decl::TsEnum(e) => !e.declare,
+                        Decl::Class(c) => !c.declare,
+                        Decl::Fn(f) => !f.declare && f.function.body.is_some(),
+                        Decl::Var(v) => !v.declare,
+                    }
+                }
+                ModuleItem::Stmt(Stmt::Empty(..)) => false,
+                ModuleItem::ModuleDecl(ModuleDecl::TsImportEquals(import)) => import.is_export,
+                ModuleItem::ModuleDecl(..) => false,
+                ModuleItem::Stmt(..) => true,
+            }),
+            TsNamespaceBody::TsNamespaceDecl(decl) => {
+                !decl.declare && is_body_instantiated(&decl.body)
+            }
+        }
+    }
+
+    match m {
+        TsModuleDecl {
+            declare: false,
+            global: false,
+            id: TsModuleName::Ident(..),
+            body: Some(body),
+            ..
+        } => is_body_instantiated(body),
+        _ => false,
+    }
+}
+
+/// Collects classes, functions and enums, which can be merged with a
+/// namespace.
+fn declared_values(items: &[ModuleItem]) -> FxHashMap<Id, FxHashSet<JsWord>> {
+    items
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::Stmt(Stmt::Decl(decl))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => match decl
+            {
+                Decl::Class(ClassDecl {
+                    ident,
+                    declare: false,
+                    ..
+                })
+                | Decl::Fn(FnDecl {
+                    ident,
+                    declare: false,
+                    function: Function { body: Some(..), .. },
+                })
+                | Decl::TsEnum(TsEnumDecl { id: ident, .. }) => {
+                    Some((ident.to_id(), Default::default()))
+                }
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// `(function (Foo) { ... })(Foo || (Foo = {}));`
+fn ns_iife(id: Ident, body: Vec<Stmt>) -> Stmt {
+    CallExpr {
+        span: DUMMY_SP,
+        callee: FnExpr {
+            ident: None,
+            function: Function {
+                span: DUMMY_SP,
+                decorators: Default::default(),
+                is_async: false,
+                is_generator: false,
+                type_params: Default::default(),
+                params: vec![Param {
+                    span: id.span,
+                    decorators: vec![],
+                    pat: Pat::Ident(id.clone()),
+                }],
+                body: Some(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: body,
+                }),
+                return_type: Default::default(),
+            },
+        }
+        .as_callee(),
+        args: vec![BinExpr {
+            span: DUMMY_SP,
+            left: box Expr::Ident(id.clone()),
+            op: op!("||"),
+            right: box Expr::Assign(AssignExpr {
+                span: DUMMY_SP,
+                left: PatOrExpr::Pat(Pat::Ident(id).into()),
+                op: op!("="),
+                right: box Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![],
+                }),
+            }),
+        }
+        .as_arg()],
+        type_args: Default::default(),
+    }
+    .into_stmt()
+}
+
+/// Converts items of a namespace into statements of the namespace function.
+///
+/// Exported functions and classes are assigned to the namespace object after
+/// their declarations, and exported variables are replaced with properties of
+/// the namespace object. Members in `exported`, which are exported by previous
+/// declarations of the namespace, are also replaced, and members exported by
+/// `items` are added to it.
+fn lower_ns_body(
+    ns: &Ident,
+    items: Vec<ModuleItem>,
+    exported: &mut FxHashSet<JsWord>,
+) -> Vec<Stmt> {
+    let assign = |name: Ident, value: Box<Expr>| {
+        AssignExpr {
+            span: DUMMY_SP,
+            left: PatOrExpr::Expr(box ns.clone().member(name)),
+            op: op!("="),
+            right: value,
+        }
+        .into_stmt()
+    };
+
+    let mut exported_vars = FxHashSet::default();
+    let mut local_names = FxHashSet::default();
+    let mut stmts = Vec::with_capacity(items.len());
+
+    for item in items {
+        let decl = match item {
+            ModuleItem::Stmt(stmt) => {
+                stmts.push(stmt);
+                continue;
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => decl,
+            // Other module declarations are not allowed in a namespace.
+            ModuleItem::ModuleDecl(..) => continue,
+        };
+
+        match decl {
+            Decl::Fn(FnDecl { ref ident, .. }) | Decl::Class(ClassDecl { ref ident, .. }) => {
+                let ident = ident.clone();
+                local_names.insert(ident.sym.clone());
+                stmts.push(Stmt::Decl(decl));
+                stmts.push(assign(ident.clone(), box Expr::Ident(ident)));
+            }
+
+            Decl::Var(VarDecl {
+                span,
+                kind,
+                declare,
+                decls,
+            }) => {
+                for decl in decls {
+                    match decl.name {
+                        Pat::Ident(name) => {
+                            exported_vars.insert(name.sym.clone());
+                            if let Some(init) = decl.init {
+                                stmts.push(assign(name, init));
+                            }
+                        }
+                        // References to bindings of a pattern are not rewritten.
+                        _ => {
+                            let names: Vec<Ident> = find_ids(&decl.name);
+                            stmts.push(Stmt::Decl(Decl::Var(VarDecl {
+                                span,
+                                kind,
+                                declare,
+                                decls: vec![decl],
+                            })));
+                            stmts.extend(
+                                names
+                                    .into_iter()
+                                    .map(|name| assign(name.clone(), box Expr::Ident(name))),
+                            );
+                        }
+                    }
+                }
+            }
+
+            _ => stmts.push(Stmt::Decl(decl)),
+        }
+    }
+
+    let names: FxHashSet<_> = exported
+        .iter()
+        .chain(exported_vars.iter())
+        .filter(|name| !local_names.contains(*name))
+        .cloned()
+        .collect();
+    exported.extend(exported_vars);
+    exported.extend(local_names);
+
+    if names.is_empty() {
+        return stmts;
+    }
+
+    stmts.fold_with(&mut NamespaceMemberRewriter {
+        ns: ns.clone(),
+        names,
+    })
+}
+
+/// Replaces references to exported variables of a namespace with
+/// `Foo.name`.
+///
+/// As `strip` runs before `resolver`, shadowing is detected using names.
+struct NamespaceMemberRewriter {
+    ns: Ident,
+    /// Names of exported variables which are not shadowed.
+    names: FxHashSet<JsWord>,
+}
+
+noop_fold_type!(NamespaceMemberRewriter);
+
+impl NamespaceMemberRewriter {
+    fn member(&self, i: Ident) -> Expr {
+        self.ns.clone().member(i)
+    }
+
+    fn fold_scope<T>(&mut self, bindings: Vec<JsWord>, node: T) -> T
+    where
+        T: FoldWith<Self>,
+    {
+        let shadowed: Vec<_> = bindings
+            .into_iter()
+            .filter(|name| self.names.remove(name))
+            .collect();
+        let node = node.fold_children(self);
+        self.names.extend(shadowed);
+        node
+    }
+}
+
+impl Fold<Function> for NamespaceMemberRewriter {
+    fn fold(&mut self, f: Function) -> Function {
+        let mut bindings = find_ids::<_, Id>(&f.params);
+        bindings.extend(find_bindings(&f.body));
+
+        self.fold_scope(bindings.into_iter().map(|id| id.0).collect(), f)
+    }
+}
+
+impl Fold<ArrowExpr> for NamespaceMemberRewriter {
+    fn fold(&mut self, f: ArrowExpr) -> ArrowExpr {
+        let mut bindings = find_ids::<_, Id>(&f.params);
+        if let BlockStmtOrExpr::BlockStmt(ref body) = f.body {
+            bindings.extend(find_bindings(body));
+        }
+
+        self.fold_scope(bindings.into_iter().map(|id| id.0).collect(), f)
+    }
+}
+
+impl Fold<BlockStmt> for NamespaceMemberRewriter {
+    fn fold(&mut self, block: BlockStmt) -> BlockStmt {
+        let bindings = find_bindings(&block);
+
+        self.fold_scope(bindings.into_iter().map(|id| id.0).collect(), block)
+    }
+}
+
+impl Fold<CatchClause> for NamespaceMemberRewriter {
+    fn fold(&mut self, c: CatchClause) -> CatchClause {
+        let bindings = find_ids::<_, Id>(&c.param);
+
+        self.fold_scope(bindings.into_iter().map(|id| id.0).collect(), c)
+    }
+}
+
+impl Fold<Expr> for NamespaceMemberRewriter {
+    fn fold(&mut self, e: Expr) -> Expr {
+        match e {
+            Expr::Ident(i) if self.names.contains(&i.sym) => self.member(i),
+            Expr::Member(MemberExpr {
+                span,
+                obj,
+                prop,
+                computed,
+            }) => Expr::Member(MemberExpr {
+                span,
+                obj: obj.fold_with(self),
+                prop: if computed { prop.fold_with(self) } else { prop },
+                computed,
+            }),
+            _ => e.fold_children(self),
+        }
+    }
+}
+
+impl Fold<Prop> for NamespaceMemberRewriter {
+    fn fold(&mut self, p: Prop) -> Prop {
+        match p {
+            Prop::Shorthand(i) if self.names.contains(&i.sym) => Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(i.clone()),
+                value: box self.member(i),
+            }),
+            _ => p.fold_children(self),
+        }
+    }
+}
+
+impl Fold<PropName> for NamespaceMemberRewriter {
+    fn fold(&mut self, n: PropName) -> PropName {
+        match n {
+            PropName::Computed(..) => n.fold_children(self),
+            _ => n,
+        }
+    }
+}
+
+impl Fold<ClassProp> for NamespaceMemberRewriter {
+    fn fold(&mut self, p: ClassProp) -> ClassProp {
+        ClassProp {
+            key: if p.computed {
+                p.key.fold_with(self)
+            } else {
+                p.key
+            },
+            value: p.value.fold_with(self),
+            decorators: p.decorators.fold_with(self),
+            ..p
+        }
+    }
+}
+
+impl Fold<PatOrExpr> for NamespaceMemberRewriter {
+    fn fold(&mut self, n: PatOrExpr) -> PatOrExpr {
+        match n {
+            PatOrExpr::Pat(box Pat::Ident(i)) if self.names.contains(&i.sym) => {
+                PatOrExpr::Expr(box self.member(i))
+            }
+            _ => n.fold_children(self),
+        }
+    }
+}
+
+/// Finds bindings declared in a function body or a block, without entering
+/// nested functions.
+fn find_bindings<T>(node: &T) -> Vec<Id>
+where
+    T: VisitWith<BindingFinder>,
+{
+    let mut v = BindingFinder { found: vec![] };
+    node.visit_with(&mut v);
+    v.found
+}
+
+struct BindingFinder {
+    found: Vec<Id>,
+}
+
+noop_visit_type!(BindingFinder);
+
+impl Visit<Expr> for BindingFinder {
+    fn visit(&mut self, _: &Expr) {}
+}
+
+impl Visit<VarDeclarator> for BindingFinder {
+    fn visit(&mut self, d: &VarDeclarator) {
+        self.found.extend(find_ids::<_, Id>(&d.name));
+    }
+}
+
+impl Visit<FnDecl> for BindingFinder {
+    fn visit(&mut self, f: &FnDecl) {
+        self.found.push(f.ident.to_id());
+    }
+}
+
+impl Visit<ClassDecl> for BindingFinder {
+    fn visit(&mut self, c: &ClassDecl) {
+        self.found.push(c.ident.to_id());
+    }
+}
@@ -0,0 +1,309 @@
+use fxhash::FxHashMap;
+use swc_atoms::JsWord;
+use swc_common::{Visit, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_utils::{ident::IdentLike, Id};
+
+/// Values of enum members, keyed by member names.
+///
+/// Values are [Lit::Num] or [Lit::Str].
+pub type EnumValues = FxHashMap<JsWord, Lit>;
+
+/// Computes values of members of `e` at compile time.
+///
+/// Members which cannot be computed, like `a = foo()`, are not included.
+/// `enums` is used to resolve references to other enums, like `a = Foo.b`.
+pub(super) fn compute_values(e: &TsEnumDecl, enums: &FxHashMap<Id, EnumValues>) -> EnumValues {
+    let mut values = EnumValues::default();
+    // Value of a member without an initializer.
+    let mut next = Some(0.0);
+
+    for m in &e.members {
+        let value = match m.init {
+            Some(ref init) => Evaluator {
+                id: &e.id,
+                values: &values,
+                enums,
+            }
+            .eval(init),
+            None => next.map(|value| {
+                Lit::Num(Number {
+                    span: DUMMY_SP,
+                    value,
+                })
+            }),
+        };
+
+        next = match value {
+            Some(Lit::Num(Number { value, .. })) => Some(value + 1.0),
+            _ => None,
+        };
+
+        if let Some(value) = value {
+            values.insert(member_name(&m.id), value);
+        }
+    }
+
+    values
+}
+
+pub(super) fn member_name(id: &TsEnumMemberId) -> JsWord {
+    match id {
+        TsEnumMemberId::Ident(i) => i.sym.clone(),
+        TsEnumMemberId::Str(s) => s.value.clone(),
+    }
+}
+
+/// Returns the value of `obj.prop` if `obj` is an enum in `enums`.
+pub(super) fn member_value<'a>(
+    enums: &'a FxHashMap<Id, EnumValues>,
+    obj: &Ident,
+    prop: &Expr,
+    computed: bool,
+) -> Option<&'a Lit> {
+    enums.get(&obj.to_id())?.get(prop_name(prop, computed)?)
+}
+
+fn prop_name(prop: &Expr, computed: bool) -> Option<&JsWord> {
+    match (prop, computed) {
+        (Expr::Ident(prop), false) => Some(&prop.sym),
+        (Expr::Lit(Lit::Str(prop)), true) => Some(&prop.value),
+        _ => None,
+    }
+}
+
+/// Converts a value of an enum member to an expression.
+pub(super) fn lit_to_expr(lit: Lit) -> Expr {
+    match lit {
+        // -1 is not a literal.
+        Lit::Num(Number { span, value }) if value.is_sign_negative() && value != 0.0 => {
+            Expr::Unary(UnaryExpr {
+                span,
+                op: op!(unary, "-"),
+                arg: box Expr::Lit(Lit::Num(Number {
+                    span,
+                    value: -value,
+                })),
+            })
+        }
+        _ => Expr::Lit(lit),
+    }
+}
+
+struct Evaluator<'a> {
+    /// Name of the enum being computed.
+    id: &'a Ident,
+    /// Earlier members of the enum.
+    values: &'a EnumValues,
+    enums: &'a FxHashMap<Id, EnumValues>,
+}
+
+impl Evaluator<'_> {
+    fn eval(&self, e: &Expr) -> Option<Lit> {
+        match e {
+            Expr::Lit(lit) => match lit {
+                Lit::Num(..) | Lit::Str(..) => Some(lit.clone()),
+                _ => None,
+            },
+
+            Expr::Tpl(Tpl { exprs, quasis, .. }) if exprs.is_empty() => {
+                quasis[0].cooked.clone().map(Lit::Str)
+            }
+
+            Expr::Paren(ParenExpr { expr, .. }) => self.eval(expr),
+
+            Expr::Ident(i) => self.values.get(&i.sym).cloned(),
+
+            Expr::Member(MemberExpr {
+                obj: ExprOrSuper::Expr(obj),
+                prop,
+                computed,
+                ..
+            }) => {
+                let obj = match &**obj {
+                    Expr::Ident(obj) => obj,
+                    _ => return None,
+                };
+
+                // Earlier members of the enum itself.
+                if obj.sym == self.id.sym {
+                    let value = prop_name(prop, *computed).and_then(|name| self.values.get(name));
+                    if let Some(value) = value {
+                        return Some(value.clone());
+                    }
+                }
+
+                member_value(self.enums, obj, prop, *computed).cloned()
+            }
+
+            Expr::Unary(UnaryExpr { op, arg, .. }) => {
+                let v = match self.eval(arg)? {
+                    Lit::Num(Number { value, .. }) => value,
+                    _ => return None,
+                };
+
+                Some(num(match *op {
+                    op!(unary, "+") => v,
+                    op!(unary, "-") => -v,
+                    op!("~") => !to_int32(v) as f64,
+                    _ => return None,
+                }))
+            }
+
+            Expr::Bin(BinExpr {
+                op, left, right, ..
+            }) => {
+                let l = self.eval(left)?;
+                let r = self.eval(right)?;
+
+                match (l, r) {
+                    (Lit::Num(Number { value: l, .. }), Lit::Num(Number { value: r, .. })) => {
+                        Some(num(match *op {
+                            op!(bin, "+") => l + r,
+                            op!(bin, "-") => l - r,
+                            op!("*") => l * r,
+                            op!("/") => l / r,
+                            op!("%") => l % r,
+                            op!("**") => l.powf(r),
+                            op!("&") => (to_int32(l) & to_int32(r)) as f64,
+                            op!("|") => (to_int32(l) | to_int32(r)) as f64,
+                            op!("^") => (to_int32(l) ^ to_int32(r)) as f64,
+                            op!("<<") => to_int32(l).wrapping_shl(to_int32(r) as u32 & 31) as f64,
+                            op!(">>") => to_int32(l).wrapping_shr(to_int32(r) as u32 & 31) as f64,
+                            op!(">>>") => {
+                                (to_int32(l) as u32).wrapping_shr(to_int32(r) as u32 & 31) as f64
+                            }
+                            _ => return None,
+                        }))
+                    }
+
+                    (l, r) if *op == op!(bin, "+") => Some(Lit::Str(Str {
+                        span: DUMMY_SP,
+                        value: format!("{}{}", to_string(&l)?, to_string(&r)?).into(),
+                        has_escape: false,
+                    })),
+
+                    _ => None,
+                }
+            }
+
+            _ => None,
+        }
+    }
+}
+
+fn num(value: f64) -> Lit {
+    Lit::Num(Number {
+        span: DUMMY_SP,
+        value,
+    })
+}
+
+fn to_string(lit: &Lit) -> Option<String> {
+    match lit {
+        Lit::Str(s) => Some(s.value.to_string()),
+        Lit::Num(n) => Some(num_to_string(n.value)),
+        _ => None,
+    }
+}
+
+/// `Number::toString` of the spec.
+///
+/// Rust's `Display` differs for large or small numbers, e.g. `1e21` is printed
+/// as `1000000000000000000000` instead of `1e+21`.
+fn num_to_string(v: f64) -> String {
+    if v.is_nan() {
+        return "NaN".into();
+    }
+    if v == 0.0 {
+        return "0".into();
+    }
+    if v < 0.0 {
+        return format!("-{}", num_to_string(-v));
+    }
+    if v.is_infinite() {
+        return "Infinity".into();
+    }
+
+    // `LowerExp` prints the shortest digits which round-trip, like the spec
+    // requires.
+    let exp_form = format!("{:e}", v);
+    let (mantissa, exp) = exp_form.split_at(exp_form.find('e').unwrap());
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    // Position of the decimal point, `n` of the spec.
+    let n = exp[1..].parse::<i32>().unwrap() + 1;
+
+    if k <= n && n <= 21 {
+        format!("{}{}", digits, "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        let (int, frac) = digits.split_at(n as usize);
+        format!("{}.{}", int, frac)
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{}", "0".repeat(-n as usize), digits)
+    } else {
+        let exp = n - 1;
+        let sign = if exp < 0 { '-' } else { '+' };
+        let (first, rest) = digits.split_at(1);
+        if rest.is_empty() {
+            format!("{}e{}{}", first, sign, exp.abs())
+        } else {
+            format!("{}.{}e{}{}", first, rest, sign, exp.abs())
+        }
+    }
+}
+
+/// `ToInt32` of the spec.
+fn to_int32(v: f64) -> i32 {
+    if !v.is_finite() {
+        return 0;
+    }
+
+    v.trunc().rem_euclid(4294967296.0) as u32 as i32
+}
+
+/// Collects values of `const enum`s.
+pub(super) struct ConstEnumCollector {
+    pub enums: FxHashMap<Id, EnumValues>,
+}
+
+impl Visit<TsEnumDecl> for ConstEnumCollector {
+    fn visit(&mut self, e: &TsEnumDecl) {
+        if !e.is_const {
+            return;
+        }
+
+        let values = compute_values(e, &self.enums);
+        self.enums.entry(e.id.to_id()).or_default().extend(values);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::num_to_string;
+
+    #[test]
+    fn number_to_string() {
+        let cases = [
+            (0.0, "0"),
+            (-0.0, "0"),
+            (1.0, "1"),
+            (-1.5, "-1.5"),
+            (123.456, "123.456"),
+            (0.1, "0.1"),
+            (0.000001, "0.000001"),
+            (0.0000001, "1e-7"),
+            (1.5e-10, "1.5e-10"),
+            (1e20, "100000000000000000000"),
+            (1e21, "1e+21"),
+            (1.2345e25, "1.2345e+25"),
+            (std::f64::NAN, "NaN"),
+            (std::f64::INFINITY, "Infinity"),
+            (std::f64::NEG_INFINITY, "-Infinity"),
+        ];
+
+        for &(v, expected) in cases.iter() {
+            assert_eq!(num_to_string(v), expected, "{}", v);
+        }
+    }
+}
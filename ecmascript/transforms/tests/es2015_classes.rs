@@ -6109,3 +6109,64 @@ with (env) {
 
 "#
 );
+
+// static_super_set
+test!(
+    syntax(),
+    |_| tr(),
+    static_super_set,
+    r#"
+class Obj extends Base {
+  static set() {
+    return super.test = 3;
+  }
+}
+"#,
+    r#"
+let Obj =
+/*#__PURE__*/
+function (Base) {
+  'use strict';
+  _inherits(Obj, Base);
+
+  function Obj() {
+    _classCallCheck(this, Obj);
+
+    return _possibleConstructorReturn(this, _getPrototypeOf(Obj).apply(this, arguments));
+  }
+
+  _createClass(Obj, null, [{
+    key: "set",
+    value: function set() {
+      return _set(_getPrototypeOf(Obj), "test", 3, this, true);
+    }
+  }]);
+
+  return Obj;
+}(Base);
+"#
+);
+
+// static_super_set_exec
+test_exec!(
+    syntax(),
+    |_| tr(),
+    static_super_set_exec,
+    r#"
+class Base {
+  static set test(v) {
+    Base.value = v;
+  }
+}
+
+class Obj extends Base {
+  static set() {
+    super.test = 3;
+  }
+}
+
+Obj.set();
+expect(Base.value).toBe(3);
+expect(Object.prototype.hasOwnProperty.call(Obj, 'test')).toBe(false);
+"#
+);
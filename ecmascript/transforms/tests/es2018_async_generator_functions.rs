@@ -0,0 +1,212 @@
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+use swc_common::{chain, Fold};
+use swc_ecma_ast::Module;
+use swc_ecma_parser::Syntax;
+use swc_ecma_transforms::compat::{es2017::async_to_generator, es2018::async_generator_functions};
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Syntax::default()
+}
+
+fn tr() -> impl Fold<Module> {
+    async_generator_functions()
+}
+
+test!(
+    syntax(),
+    |_| tr(),
+    fn_decl,
+    r#"
+async function* agf(a, b = 1) {
+  const x = await a;
+  yield x;
+}
+"#,
+    r#"
+function agf(_x) {
+  return _wrapAsyncGenerator(function* (a, b = 1) {
+    const x = yield _awaitAsyncGenerator(a);
+    yield x;
+  }).apply(this, arguments);
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    yield_star,
+    r#"
+async function* agf() {
+  yield* other();
+}
+"#,
+    r#"
+function agf() {
+  return _wrapAsyncGenerator(function* () {
+    yield* _asyncGeneratorDelegate(_asyncIterator(other()), _awaitAsyncGenerator);
+  }).apply(this, arguments);
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    nested_fn,
+    r#"
+const obj = {
+  async *foo() {
+    const f = async () => await 1;
+    yield f;
+  }
+};
+"#,
+    r#"
+const obj = {
+  foo() {
+    return _wrapAsyncGenerator(function* () {
+      const f = async () => await 1;
+      yield f;
+    }).apply(this, arguments);
+  }
+};
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    for_await,
+    r#"
+async function f() {
+  for await (const x of y) {
+    g(x);
+  }
+}
+"#,
+    r#"
+async function f() {
+  var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+  try {
+    for (var _iterator = _asyncIterator(y), _step, _value; _step = await _iterator.next(),
+        _iteratorNormalCompletion = _step.done, _value = await _step.value,
+        !_iteratorNormalCompletion; _iteratorNormalCompletion = true) {
+      const x = _value;
+      {
+        g(x);
+      }
+    }
+  } catch (err) {
+    _didIteratorError = true;
+    _iteratorError = err;
+  } finally {
+    try {
+      if (!_iteratorNormalCompletion && _iterator.return != null) {
+        await _iterator.return();
+      }
+    } finally {
+      if (_didIteratorError) {
+        throw _iteratorError;
+      }
+    }
+  }
+}
+"#
+);
+
+test!(
+    syntax(),
+    |_| tr(),
+    for_await_labeled,
+    r#"
+async function f() {
+  outer: for await (x of y) {
+    continue outer;
+  }
+}
+"#,
+    r#"
+async function f() {
+  var _iteratorNormalCompletion = true, _didIteratorError = false, _iteratorError;
+  try {
+    outer: for (var _iterator = _asyncIterator(y), _step, _value; _step = await _iterator.next(),
+        _iteratorNormalCompletion = _step.done, _value = await _step.value,
+        !_iteratorNormalCompletion; _iteratorNormalCompletion = true) {
+      x = _value;
+      {
+        continue outer;
+      }
+    }
+  } catch (err) {
+    _didIteratorError = true;
+    _iteratorError = err;
+  } finally {
+    try {
+      if (!_iteratorNormalCompletion && _iterator.return != null) {
+        await _iterator.return();
+      }
+    } finally {
+      if (_didIteratorError) {
+        throw _iteratorError;
+      }
+    }
+  }
+}
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(tr(), async_to_generator()),
+    exec_generator,
+    r#"
+async function* gen(a) {
+  yield a;
+  const b = await Promise.resolve(2);
+  yield b;
+  yield* [3, 4];
+}
+
+const values = [];
+return (async () => {
+  for await (const v of gen(1)) {
+    values.push(v);
+  }
+  expect(values).toEqual([1, 2, 3, 4]);
+  expect(gen.length).toBe(1);
+})();
+"#
+);
+
+test_exec!(
+    syntax(),
+    |_| chain!(tr(), async_to_generator()),
+    exec_return,
+    r#"
+let closed = false;
+async function* gen() {
+  try {
+    yield 1;
+    yield 2;
+  } finally {
+    closed = true;
+  }
+}
+
+return (async () => {
+  for await (const v of gen()) {
+    expect(v).toBe(1);
+    break;
+  }
+  expect(closed).toBe(true);
+})();
+"#
+);
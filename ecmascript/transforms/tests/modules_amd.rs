@@ -11,7 +11,7 @@ use swc_ecma_transforms::{
         amd::{amd, Config},
         util,
     },
-    resolver,
+    resolver, typescript,
 };
 
 #[macro_use]
@@ -1176,3 +1176,57 @@ export const foo = function () {
 });
 "
 );
+
+fn ts_tr() -> impl Fold<Module> {
+    chain!(
+        typescript::strip_with_config(typescript::Config {
+            preserve_import_export_assign: true,
+            ..Default::default()
+        }),
+        resolver(),
+        amd(Default::default())
+    )
+}
+
+test!(
+    Syntax::Typescript(Default::default()),
+    |_| ts_tr(),
+    ts_import_equals,
+    "import fs = require('fs');
+fs.readFileSync('foo');",
+    "define(['fs'], function(fs) {
+    'use strict';
+    fs.readFileSync('foo');
+});"
+);
+
+test!(
+    Syntax::Typescript(Default::default()),
+    |_| ts_tr(),
+    ts_export_import_equals,
+    "export import fs = require('fs');",
+    "define(['exports', 'fs'], function(_exports, fs) {
+    'use strict';
+    Object.defineProperty(_exports, '__esModule', {
+        value: true
+    });
+    _exports.fs = void 0;
+    _exports.fs = fs;
+});"
+);
+
+test!(
+    Syntax::Typescript(Default::default()),
+    |_| ts_tr(),
+    ts_export_assignment,
+    "export = foo;",
+    "define(['exports'], function(_exports) {
+    'use strict';
+    Object.defineProperty(_exports, '__esModule', {
+        value: true
+    });
+    _exports.default = void 0;
+    var _default = foo;
+    _exports.default = _default;
+});"
+);
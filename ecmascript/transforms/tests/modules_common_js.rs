@@ -4155,3 +4155,53 @@ const resources = [
       }
   ];"
 );
+
+fn ts_tr() -> impl Fold<Module> {
+    let mark = Mark::fresh(Mark::root());
+
+    chain!(
+        typescript::strip_with_config(typescript::Config {
+            preserve_import_export_assign: true,
+            ..Default::default()
+        }),
+        resolver_with_mark(mark),
+        common_js(mark, Default::default())
+    )
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |_| ts_tr(),
+    ts_import_equals,
+    "import fs = require('fs');
+fs.readFileSync('foo');",
+    "'use strict';
+var fs = require('fs');
+fs.readFileSync('foo');"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |_| ts_tr(),
+    ts_export_import_equals,
+    "export import fs = require('fs');",
+    "'use strict';
+Object.defineProperty(exports, '__esModule', {
+    value: true
+});
+exports.fs = void 0;
+var fs = require('fs');
+exports.fs = fs;"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |_| ts_tr(),
+    ts_export_assignment,
+    "function foo() {}
+export = foo;",
+    "'use strict';
+function foo() {
+}
+module.exports = foo;"
+);
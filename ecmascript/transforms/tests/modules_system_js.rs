@@ -0,0 +1,311 @@
+#![feature(box_syntax)]
+#![feature(test)]
+#![feature(box_patterns)]
+#![feature(specialization)]
+
+use swc_common::{chain, Fold};
+use swc_ecma_ast::Module;
+use swc_ecma_parser::{EsConfig, Syntax};
+use swc_ecma_transforms::{
+    modules::system_js::{system_js, Config},
+    resolver, typescript,
+};
+
+#[macro_use]
+mod common;
+
+fn syntax() -> Syntax {
+    Syntax::Es(EsConfig {
+        dynamic_import: true,
+        import_meta: true,
+        ..Default::default()
+    })
+}
+
+fn tr(config: Config) -> impl Fold<Module> {
+    chain!(resolver(), system_js(config))
+}
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    imports,
+    "import foo, { bar } from 'dep';
+import 'side-effect';
+
+foo(bar);",
+    "System.register(['dep', 'side-effect'], function(_export, _context) {
+    'use strict';
+    var _dep;
+    return {
+        setters: [function(_m) {
+            _dep = _m;
+        }, function() {}],
+        execute: function() {
+            _dep.default(_dep.bar);
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    live_bindings,
+    "export var x = 1;
+export let count = 0;
+export function inc() {
+    count++;
+    x = 2;
+}",
+    "System.register([], function(_export, _context) {
+    'use strict';
+    var x, count;
+    function inc() {
+        _export('count', count = +count + 1);
+        _export('x', x = 2);
+    }
+    _export('inc', inc);
+    return {
+        setters: [],
+        execute: function() {
+            _export('x', x = 1);
+            _export('count', count = 0);
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    nested_var,
+    "if (c) {
+    var y = 1;
+}
+for (var i = 0; i < 2; i++) {
+    var z;
+}
+for (var k in o) {}
+export function get() {
+    var local = 1;
+    return [y, i, z, k, local];
+}
+export { y };",
+    "System.register([], function(_export, _context) {
+    'use strict';
+    var y, i, z, k;
+    function get() {
+        var local = 1;
+        return [y, i, z, k, local];
+    }
+    _export('get', get);
+    return {
+        setters: [],
+        execute: function() {
+            if (c) {
+                _export('y', y = 1);
+            }
+            for(i = 0; i < 2; i++){
+                ;
+            }
+            for(k in o){
+            }
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_named,
+    "var a = 1;
+function b() {}
+export { a, a as c, b as d };",
+    "System.register([], function(_export, _context) {
+    'use strict';
+    var a;
+    function b() {}
+    _export('d', b);
+    return {
+        setters: [],
+        execute: function() {
+            _export('c', _export('a', a = 1));
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_default,
+    "export default class A {}
+A = null;",
+    "System.register([], function(_export, _context) {
+    'use strict';
+    var A;
+    return {
+        setters: [],
+        execute: function() {
+            _export('default', A = class A {});
+            _export('default', A = null);
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_default_expr,
+    "export default 1 + 2;",
+    "System.register([], function(_export, _context) {
+    'use strict';
+    return {
+        setters: [],
+        execute: function() {
+            _export('default', 1 + 2);
+        }
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    export_default_anonymous_fn,
+    "export default function () {}",
+    "System.register([], function(_export, _context) {
+    'use strict';
+    function _default() {}
+    _export('default', _default);
+    return {
+        setters: [],
+        execute: function() {}
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Default::default()),
+    re_export,
+    "export { a as b } from 'other';
+export * from 'all';
+import * as ns from 'ns';
+export { ns };",
+    "System.register(['other', 'all', 'ns'], function(_export, _context) {
+    'use strict';
+    var _other, _all, ns;
+    return {
+        setters: [function(_m) {
+            _other = _m;
+            _export('b', _other.a);
+        }, function(_m) {
+            _all = _m;
+            var _exportObj = {};
+            for(var _key in _all){
+                if (_key !== 'default' && _key !== '__esModule') _exportObj[_key] = _all[_key];
+            }
+            _export(_exportObj);
+        }, function(_m) {
+            ns = _m;
+            _export('ns', ns);
+        }],
+        execute: function() {}
+    };
+});"
+);
+
+test!(
+    syntax(),
+    |_| tr(Config {
+        module_id: Some("foo".into()),
+        ..Default::default()
+    }),
+    context,
+    "console.log(import.meta.url, this);
+import('./a').then(function () {});",
+    "System.register('foo', [], function(_export, _context) {
+    'use strict';
+    return {
+        setters: [],
+        execute: function() {
+            console.log(_context.meta.url, void 0);
+            _context.import('./a').then(function() {});
+        }
+    };
+});"
+);
+
+fn ts_tr() -> impl Fold<Module> {
+    chain!(
+        typescript::strip_with_config(typescript::Config {
+            preserve_import_export_assign: true,
+            ..Default::default()
+        }),
+        resolver(),
+        system_js(Default::default())
+    )
+}
+
+test!(
+    Syntax::Typescript(Default::default()),
+    |_| ts_tr(),
+    ts_import_equals,
+    "import fs = require('fs');
+fs.readFileSync('foo');",
+    "System.register(['fs'], function(_export, _context) {
+    'use strict';
+    var fs;
+    return {
+        setters: [
+            function(_m) {
+                fs = _m;
+            }
+        ],
+        execute: function() {
+            fs.readFileSync('foo');
+        }
+    };
+});"
+);
+
+test!(
+    Syntax::Typescript(Default::default()),
+    |_| ts_tr(),
+    ts_export_import_equals,
+    "export import fs = require('fs');",
+    "System.register(['fs'], function(_export, _context) {
+    'use strict';
+    var fs;
+    return {
+        setters: [
+            function(_m) {
+                fs = _m;
+                _export('fs', fs);
+            }
+        ],
+        execute: function() {
+        }
+    };
+});"
+);
+
+test!(
+    Syntax::Typescript(Default::default()),
+    |_| ts_tr(),
+    ts_export_assignment,
+    "export = foo;",
+    "System.register([], function(_export, _context) {
+    'use strict';
+    return {
+        setters: [],
+        execute: function() {
+            _export('default', foo);
+        }
+    };
+});"
+);
@@ -11,7 +11,7 @@ use swc_ecma_transforms::{
         util,
     },
     pass::Pass,
-    resolver_with_mark,
+    resolver_with_mark, typescript,
 };
 
 #[macro_use]
@@ -1878,3 +1878,68 @@ export const foo = function () {
     _exports.foo = foo;
 });"
 );
+
+fn ts_tr(tester: &mut Tester<'_>) -> impl Pass {
+    let mark = Mark::fresh(Mark::root());
+    chain!(
+        typescript::strip_with_config(typescript::Config {
+            preserve_import_export_assign: true,
+            ..Default::default()
+        }),
+        resolver_with_mark(mark),
+        umd(tester.cm.clone(), mark, Default::default())
+    )
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |tester| ts_tr(tester),
+    ts_import_equals,
+    "import fs = require('fs');
+fs.readFileSync('foo');",
+    "(function(global, factory) {
+    if (typeof define === 'function' && define.amd) {
+        define(['fs'], factory);
+    } else if (typeof exports !== 'undefined') {
+        factory(require('fs'));
+    } else {
+        var mod = {
+            exports: {
+            }
+        };
+        factory(global.fs);
+        global.input = mod.exports;
+    }
+})(this, function(fs) {
+    'use strict';
+    fs.readFileSync('foo');
+});"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |tester| ts_tr(tester),
+    ts_export_import_equals,
+    "export import fs = require('fs');",
+    "(function(global, factory) {
+    if (typeof define === 'function' && define.amd) {
+        define(['exports', 'fs'], factory);
+    } else if (typeof exports !== 'undefined') {
+        factory(exports, require('fs'));
+    } else {
+        var mod = {
+            exports: {
+            }
+        };
+        factory(mod.exports, global.fs);
+        global.input = mod.exports;
+    }
+})(this, function(_exports, fs) {
+    'use strict';
+    Object.defineProperty(_exports, '__esModule', {
+        value: true
+    });
+    _exports.fs = void 0;
+    _exports.fs = fs;
+});"
+);
@@ -164,6 +164,15 @@ fn export_default_expr_used() {
     used(&["default"], "export default 5;", "export default 5;");
 }
 
+#[test]
+fn export_all_used() {
+    used(
+        &["foo"],
+        "export * from 'a'; export const bar = 1;",
+        "export * from 'a';",
+    );
+}
+
 noop!(
     issue_760_1,
     "var ref;
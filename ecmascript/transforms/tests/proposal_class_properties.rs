@@ -29,6 +29,8 @@ fn syntax() -> Syntax {
     Syntax::Es(EsConfig {
         class_private_props: true,
         class_props: true,
+        static_blocks: true,
+        private_in_object: true,
         ..Default::default()
     })
 }
@@ -4856,3 +4858,109 @@ expect(foo.y).toBe('bar');
 
 "#
 );
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), class_properties()),
+    static_block,
+    "class Foo {
+  static foo = 1;
+  static {
+    var bar = this.foo + 1;
+    this.bar = bar;
+  }
+}",
+    "class Foo {
+}
+_defineProperty(Foo, 'foo', 1);
+(function() {
+    var bar = Foo.foo + 1;
+    Foo.bar = bar;
+})();"
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), class_properties()),
+    static_block_private,
+    "class Foo {
+  static {
+    this.x = Foo.#x;
+  }
+  static #x = 1;
+}",
+    "class Foo {
+}
+(function() {
+    Foo.x = _classStaticPrivateFieldSpecGet(Foo, Foo, _x);
+})();
+var _x = {
+    writable: true,
+    value: 1
+};"
+);
+
+test_exec!(
+    syntax(),
+    |_| class_properties(),
+    static_block_exec,
+    "class Foo {
+  static foo = 1;
+  static #bar = 2;
+  static {
+    this.baz = this.foo + Foo.#bar;
+  }
+}
+
+expect(Foo.baz).toBe(3);"
+);
+
+test!(
+    syntax(),
+    |_| chain!(resolver(), class_properties()),
+    private_in_object,
+    "class Foo {
+  #x = 1;
+  static #y = 2;
+  static check(obj) {
+    return #x in obj && #y in obj;
+  }
+}",
+    "class Foo {
+    static check(obj) {
+        return _x.has(obj) && obj === Foo;
+    }
+    constructor(){
+        _x.set(this, {
+            writable: true,
+            value: 1
+        });
+    }
+}
+var _x = new WeakMap();
+var _y = {
+    writable: true,
+    value: 2
+};"
+);
+
+test_exec!(
+    syntax(),
+    |_| class_properties(),
+    private_in_object_exec,
+    "class Foo {
+  #x;
+  static #y;
+  static hasX(obj) {
+    return #x in obj;
+  }
+  static hasY(obj) {
+    return #y in obj;
+  }
+}
+
+expect(Foo.hasX(new Foo())).toBe(true);
+expect(Foo.hasX({})).toBe(false);
+expect(Foo.hasY(Foo)).toBe(true);
+expect(Foo.hasY(new Foo())).toBe(false);"
+);
@@ -3,8 +3,12 @@
 #![feature(box_patterns)]
 #![feature(specialization)]
 
-use swc_common::chain;
-use swc_ecma_transforms::{resolver, typescript::strip};
+use swc_common::{chain, Fold, DUMMY_SP};
+use swc_ecma_ast::*;
+use swc_ecma_transforms::{
+    resolver,
+    typescript::{self, strip},
+};
 
 #[macro_use]
 mod common;
@@ -583,3 +587,252 @@ to!(
     }
 }"
 );
+
+to!(
+    namespace_simple,
+    "namespace Foo {
+    export const x = 1;
+    const y = x + 1;
+    export function f() {
+        return y;
+    }
+}",
+    "var Foo;
+(function(Foo) {
+    Foo.x = 1;
+    const y = Foo.x + 1;
+    function f() {
+        return y;
+    }
+    Foo.f = f;
+})(Foo || (Foo = {}));"
+);
+
+to!(
+    namespace_export,
+    "export namespace Foo {
+    export class A {}
+}",
+    "export var Foo;
+(function(Foo) {
+    class A {
+    }
+    Foo.A = A;
+})(Foo || (Foo = {}));"
+);
+
+to!(
+    namespace_merge,
+    "namespace Foo {
+    export let x = 1;
+}
+namespace Foo {
+    export function f() {
+        return x;
+    }
+}",
+    "var Foo;
+(function(Foo) {
+    Foo.x = 1;
+})(Foo || (Foo = {}));
+(function(Foo) {
+    function f() {
+        return Foo.x;
+    }
+    Foo.f = f;
+})(Foo || (Foo = {}));"
+);
+
+to!(
+    namespace_merge_class,
+    "class Foo {}
+namespace Foo {
+    export const x = 1;
+}",
+    "class Foo {
+}
+(function(Foo) {
+    Foo.x = 1;
+})(Foo || (Foo = {}));"
+);
+
+to!(
+    namespace_nested,
+    "namespace A.B.C {
+    export const x = 1;
+}",
+    "var A;
+(function(A) {
+    (function(B) {
+        (function(C) {
+            C.x = 1;
+        })(B.C || (B.C = {}));
+    })(A.B || (A.B = {}));
+})(A || (A = {}));"
+);
+
+to!(
+    namespace_shadowed,
+    "namespace Foo {
+    export const x = 1;
+    function f(x) {
+        return x;
+    }
+    function g() {
+        return x;
+    }
+}",
+    "var Foo;
+(function(Foo) {
+    Foo.x = 1;
+    function f(x) {
+        return x;
+    }
+    function g() {
+        return Foo.x;
+    }
+})(Foo || (Foo = {}));"
+);
+
+to!(
+    namespace_declare,
+    "declare namespace Foo {
+    export const x: number;
+}
+namespace Bar {
+    export interface A {}
+    export type B = string;
+}",
+    ""
+);
+
+to!(
+    import_equals_require,
+    "import fs = require('fs');
+fs.readFileSync('foo');",
+    "import * as fs from 'fs';
+fs.readFileSync('foo');"
+);
+
+to!(
+    export_import_equals_require,
+    "export import fs = require('fs');",
+    "import * as fs from 'fs';
+export { fs };"
+);
+
+to!(
+    import_equals_entity,
+    "import A = B.C;
+A();",
+    "var A = B.C;
+A();"
+);
+
+to!(
+    import_equals_type_only,
+    "import fs = require('fs');
+import A = B.C;
+let x: A = fs.readFileSync('foo');",
+    "import * as fs from 'fs';
+let x = fs.readFileSync('foo');"
+);
+
+to!(
+    const_enum_inline,
+    "const enum Foo {
+    A = 1,
+    B = A << 2,
+    C,
+    D = 'd' + 'e',
+}
+console.log(Foo.A, Foo.B, Foo['C'], Foo.D);",
+    "console.log(1, 4, 5, 'de');"
+);
+
+to!(
+    const_enum_number_to_string,
+    "const enum Foo {
+    A = 'a' + 1e21,
+    B = 'b' + 1e-7,
+    C = 'c' + 0.000001,
+    D = 'd' + 1.5,
+    E = 'e' + 100,
+}
+console.log(Foo.A, Foo.B, Foo.C, Foo.D, Foo.E);",
+    "console.log('a1e+21', 'b1e-7', 'c0.000001', 'd1.5', 'e100');"
+);
+
+to!(
+    const_enum_export,
+    "export const enum Foo {
+    A = -1,
+}
+foo(Foo.A);",
+    "export var Foo;
+(function(Foo) {
+    Foo[Foo['A'] = -1] = 'A';
+})(Foo || (Foo = {}));
+foo(-1);"
+);
+
+to!(
+    enum_computed_init,
+    "enum Foo {
+    A = 1 << 1,
+    B,
+    C = A | B,
+}",
+    "var Foo;
+(function(Foo) {
+    Foo[Foo['A'] = 2] = 'A';
+    Foo[Foo['B'] = 3] = 'B';
+    Foo[Foo['C'] = 3] = 'C';
+})(Foo || (Foo = {}));"
+);
+
+to!(declare_enum, "declare enum Foo { A }", "");
+
+fn strip_with_imported_enums() -> impl Fold<Module> {
+    let mut values = typescript::EnumValues::default();
+    values.insert(
+        "A".into(),
+        Lit::Num(Number {
+            span: DUMMY_SP,
+            value: 1.0,
+        }),
+    );
+
+    let mut config = typescript::Config::default();
+    config
+        .imported_enums
+        .entry("./foo".into())
+        .or_default()
+        .insert("Foo".into(), values);
+
+    typescript::strip_with_config(config)
+}
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |_| strip_with_imported_enums(),
+    const_enum_imported,
+    "import { Foo, bar } from './foo';
+bar(Foo.A);",
+    "import { bar } from './foo';
+bar(1);"
+);
+
+test!(
+    ::swc_ecma_parser::Syntax::Typescript(Default::default()),
+    |_| strip(),
+    ts4_types,
+    "import { A, B, C } from './types';
+type Range = [start: number, end?: number, ...rest: A[]];
+type Concat<T extends unknown[], U extends unknown[]> = [...T, ...U];
+type Handler = `on${Capitalize<B>}`;
+let ctor: abstract new () => C;
+function f(range: [start: number, end: number]) {}",
+    "let ctor;
+function f(range) {}"
+);
@@ -29,6 +29,7 @@ define!({
         ClassProp(ClassProp),
         PrivateProp(PrivateProp),
         TsIndexSignature(TsIndexSignature),
+        StaticBlock(StaticBlock),
     }
 
     pub struct ClassProp {
@@ -87,6 +88,10 @@ define!({
         pub accessibility: Option<Accessibility>,
         pub is_optional: bool,
     }
+    pub struct StaticBlock {
+        pub span: Span,
+        pub body: BlockStmt,
+    }
     pub struct Decorator {
         pub span: Span,
         pub expr: Box<Expr>,
@@ -612,6 +617,9 @@ define!({
         BitXorAssign,
         BitAndAssign,
         ExpAssign,
+        AndAssign,
+        OrAssign,
+        NullishAssign,
     }
     pub enum UpdateOp {
         PlusPlus,
@@ -1000,6 +1008,7 @@ define!({
         pub params: Vec<TsFnParam>,
         pub type_params: Option<TsTypeParamDecl>,
         pub type_ann: TsTypeAnn,
+        pub is_abstract: bool,
     }
     pub struct TsTypeRef {
         pub span: Span,
@@ -1040,7 +1049,12 @@ define!({
     }
     pub struct TsTupleType {
         pub span: Span,
-        pub elem_types: Vec<Box<TsType>>,
+        pub elem_types: Vec<TsTupleElement>,
+    }
+    pub struct TsTupleElement {
+        pub span: Span,
+        pub label: Option<Pat>,
+        pub ty: TsType,
     }
     pub struct TsOptionalType {
         pub span: Span,
@@ -1113,7 +1127,12 @@ define!({
         Number(Number),
         Str(Str),
         Bool(Bool),
-        Tpl(Tpl),
+        Tpl(TsTplLitType),
+    }
+    pub struct TsTplLitType {
+        pub span: Span,
+        pub types: Vec<Box<TsType>>,
+        pub quasis: Vec<TplElement>,
     }
     pub struct TsInterfaceDecl {
         pub span: Span,
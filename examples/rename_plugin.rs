@@ -0,0 +1,32 @@
+//! A native plugin which renames identifiers.
+//!
+//! Options map old names to new names, like `{ "foo": "bar" }`.
+#![feature(specialization)]
+
+use anyhow::{Context, Error};
+use std::collections::HashMap;
+use swc_common::Fold;
+use swc_ecmascript::ast::Ident;
+use swc_plugin::define_plugin;
+
+struct Rename {
+    names: HashMap<String, String>,
+}
+
+impl Fold<Ident> for Rename {
+    fn fold(&mut self, i: Ident) -> Ident {
+        match self.names.get(&*i.sym) {
+            Some(name) => Ident {
+                sym: name.clone().into(),
+                ..i
+            },
+            None => i,
+        }
+    }
+}
+
+define_plugin!(|options: serde_json::Value| -> Result<_, Error> {
+    Ok(Rename {
+        names: serde_json::from_value(options).context("invalid options")?,
+    })
+});
@@ -20,20 +20,32 @@ use swc::{
     common::{self, errors::Handler, FileName, FilePathMapping, SourceFile, SourceMap},
     config::{Options, ParseOptions, SourceMapsConfig},
     ecmascript::ast::Program,
-    Compiler, TransformOutput,
+    Compiler, ErrorOutput, TransformOutput,
 };
 
-fn init(_cx: MethodContext<JsUndefined>) -> NeonResult<ArcCompiler> {
+fn init(mut cx: MethodContext<JsUndefined>) -> NeonResult<ArcCompiler> {
     let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
 
-    let handler = Handler::with_tty_emitter(
-        common::errors::ColorConfig::Always,
-        true,
-        false,
-        Some(cm.clone()),
-    );
+    let json_diagnostics = match cx.argument_opt(0) {
+        Some(v) => v
+            .downcast::<JsBoolean>()
+            .map(|v| v.value())
+            .unwrap_or(false),
+        None => false,
+    };
 
-    let c = Compiler::new(cm.clone(), handler);
+    let c = if json_diagnostics {
+        Compiler::with_json_diagnostics(cm)
+    } else {
+        let handler = Handler::with_tty_emitter(
+            common::errors::ColorConfig::Always,
+            true,
+            false,
+            Some(cm.clone()),
+        );
+
+        Compiler::new(cm, handler)
+    };
 
     Ok(Arc::new(c))
 }
@@ -55,36 +67,56 @@ struct TransformTask {
     options: Options,
 }
 
+/// Runs `op`, attaching diagnostics reported while running it to the error.
+fn capture_error<T, F>(c: &Compiler, op: F) -> Result<T, ErrorOutput>
+where
+    F: FnOnce() -> Result<T, Error>,
+{
+    let (res, diagnostics) = c.capture_diagnostics(op);
+    res.map_err(|err| ErrorOutput::new(&err, diagnostics))
+}
+
+/// Throws an error with diagnostics of `output` attached as `diagnostics`.
+fn throw_error<'a>(cx: &mut impl Context<'a>, output: ErrorOutput) -> JsResult<'a, JsValue> {
+    let error = cx.error(&output.message)?;
+    let diagnostics = neon_serde::to_value(cx, &output.diagnostics)?;
+    error.set(cx, "diagnostics", diagnostics)?;
+
+    cx.throw(error)
+}
+
 fn complete_output<'a>(
     mut cx: impl Context<'a>,
-    result: Result<TransformOutput, Error>,
+    result: Result<TransformOutput, ErrorOutput>,
 ) -> JsResult<'a, JsValue> {
     match result {
         Ok(output) => Ok(neon_serde::to_value(&mut cx, &output)?),
-        Err(err) => cx.throw_error(format!("{:?}", err)),
+        Err(err) => throw_error(&mut cx, err),
     }
 }
 
 impl Task for TransformTask {
     type Output = TransformOutput;
-    type Error = Error;
+    type Error = ErrorOutput;
     type JsEvent = JsValue;
 
     fn perform(&self) -> Result<Self::Output, Self::Error> {
-        self.c.run(|| match self.input {
-            Input::Program(ref s) => {
-                let program: Program =
-                    serde_json::from_str(&s).expect("failed to deserialize Program");
-                // TODO: Source map
-                self.c.process_js(program, None, &self.options)
-            }
-
-            Input::File(ref path) => {
-                let fm = self.c.cm.load_file(path).context("failed to read module")?;
-                self.c.process_js_file(fm, &self.options)
-            }
-
-            Input::Source(ref s) => self.c.process_js_file(s.clone(), &self.options),
+        self.c.with_diagnostics(|| {
+            self.c.run(|| match self.input {
+                Input::Program(ref s) => {
+                    let program: Program =
+                        serde_json::from_str(&s).expect("failed to deserialize Program");
+                    // TODO: Source map
+                    self.c.process_js(program, None, &self.options)
+                }
+
+                Input::File(ref path) => {
+                    let fm = self.c.cm.load_file(path).context("failed to read module")?;
+                    self.c.process_js_file(fm, &self.options)
+                }
+
+                Input::Source(ref s) => self.c.process_js_file(s.clone(), &self.options),
+            })
         })
     }
 
@@ -136,10 +168,14 @@ where
         }
     };
 
+    let c;
     let this = cx.this();
-    let output = {
+    {
         let guard = cx.lock();
-        let c = this.borrow(&guard);
+        let compiler = this.borrow(&guard);
+        c = compiler.clone();
+    }
+    let output = c.with_diagnostics(|| {
         c.run(|| {
             if is_module.value() {
                 let program: Program =
@@ -151,7 +187,7 @@ where
                 c.process_js_file(fm, &options)
             }
         })
-    };
+    });
 
     complete_output(cx, output)
 }
@@ -228,34 +264,36 @@ struct ParseFileTask {
 
 fn complete_parse<'a>(
     mut cx: impl Context<'a>,
-    result: Result<Program, Error>,
+    result: Result<Program, ErrorOutput>,
     c: &Compiler,
 ) -> JsResult<'a, JsValue> {
     c.run(|| match result {
         Ok(program) => Ok(cx
             .string(serde_json::to_string(&program).expect("failed to serialize Program"))
             .upcast()),
-        Err(err) => cx.throw_error(format!("{:?}", err)),
+        Err(err) => throw_error(&mut cx, err),
     })
 }
 
 impl Task for ParseTask {
     type Output = Program;
-    type Error = Error;
+    type Error = ErrorOutput;
     type JsEvent = JsValue;
 
     fn perform(&self) -> Result<Self::Output, Self::Error> {
-        self.c.run(|| {
-            self.c
-                .parse_js(
-                    self.fm.clone(),
-                    self.options.target,
-                    self.options.syntax,
-                    self.options.is_module,
-                    self.options.comments,
-                    &Default::default(),
-                )
-                .map(|v| v.0)
+        capture_error(&self.c, || {
+            self.c.run(|| {
+                self.c
+                    .parse_js(
+                        self.fm.clone(),
+                        self.options.target,
+                        self.options.syntax,
+                        self.options.is_module,
+                        self.options.comments,
+                        &Default::default(),
+                    )
+                    .map(|v| v.0)
+            })
         })
     }
 
@@ -270,27 +308,29 @@ impl Task for ParseTask {
 
 impl Task for ParseFileTask {
     type Output = Program;
-    type Error = Error;
+    type Error = ErrorOutput;
     type JsEvent = JsValue;
 
     fn perform(&self) -> Result<Self::Output, Self::Error> {
-        self.c.run(|| {
-            let fm = self
-                .c
-                .cm
-                .load_file(&self.path)
-                .context("failed to read module")?;
-
-            self.c
-                .parse_js(
-                    fm,
-                    self.options.target,
-                    self.options.syntax,
-                    self.options.is_module,
-                    self.options.comments,
-                    &Default::default(),
-                )
-                .map(|v| v.0)
+        capture_error(&self.c, || {
+            self.c.run(|| {
+                let fm = self
+                    .c
+                    .cm
+                    .load_file(&self.path)
+                    .context("failed to read module")?;
+
+                self.c
+                    .parse_js(
+                        fm,
+                        self.options.target,
+                        self.options.syntax,
+                        self.options.is_module,
+                        self.options.comments,
+                        &Default::default(),
+                    )
+                    .map(|v| v.0)
+            })
         })
     }
 
@@ -340,7 +380,7 @@ fn parse_sync(mut cx: MethodContext<JsCompiler>) -> JsResult<JsValue> {
         let options_arg = cx.argument::<JsValue>(1)?;
         let options: ParseOptions = neon_serde::from_value(&mut cx, options_arg)?;
 
-        let program = {
+        let program = capture_error(&c, || {
             let fm = c.cm.new_source_file(FileName::Anon, src.value());
             c.parse_js(
                 fm,
@@ -351,7 +391,7 @@ fn parse_sync(mut cx: MethodContext<JsCompiler>) -> JsResult<JsValue> {
                 &Default::default(),
             )
             .map(|v| v.0)
-        };
+        });
 
         complete_parse(cx, program, &c)
     })
@@ -370,7 +410,7 @@ fn parse_file_sync(mut cx: MethodContext<JsCompiler>) -> JsResult<JsValue> {
         let options_arg = cx.argument::<JsValue>(1)?;
         let options: ParseOptions = neon_serde::from_value(&mut cx, options_arg)?;
 
-        let program = {
+        let program = capture_error(&c, || {
             let fm =
                 c.cm.load_file(Path::new(&path.value()))
                     .expect("failed to read program file");
@@ -384,7 +424,7 @@ fn parse_file_sync(mut cx: MethodContext<JsCompiler>) -> JsResult<JsValue> {
                 &Default::default(),
             )
             .map(|v| v.0)
-        };
+        });
 
         complete_parse(cx, program, &c)
     })
@@ -422,10 +462,10 @@ struct PrintTask {
 
 impl Task for PrintTask {
     type Output = TransformOutput;
-    type Error = Error;
+    type Error = ErrorOutput;
     type JsEvent = JsValue;
     fn perform(&self) -> Result<Self::Output, Self::Error> {
-        self.c.run(|| {
+        self.c.with_diagnostics(|| {
             let comments = Default::default();
 
             self.c.print(
@@ -436,12 +476,12 @@ impl Task for PrintTask {
                     .clone()
                     .unwrap_or(SourceMapsConfig::Bool(false)),
                 None,
+                &self.options.source_map_options(),
                 self.options
                     .config
                     .clone()
                     .unwrap_or_default()
-                    .minify
-                    .unwrap_or(false),
+                    .codegen_config(),
             )
         })
     }
@@ -497,7 +537,7 @@ fn print_sync(mut cx: MethodContext<JsCompiler>) -> JsResult<JsValue> {
         let options = cx.argument::<JsValue>(1)?;
         let options: Options = neon_serde::from_value(&mut cx, options)?;
 
-        let result = {
+        let result = c.with_diagnostics(|| {
             let comments = Default::default();
             c.print(
                 &program,
@@ -507,9 +547,10 @@ fn print_sync(mut cx: MethodContext<JsCompiler>) -> JsResult<JsValue> {
                     .clone()
                     .unwrap_or(SourceMapsConfig::Bool(false)),
                 None,
-                options.config.unwrap_or_default().minify.unwrap_or(false),
+                &options.source_map_options(),
+                options.config.unwrap_or_default().codegen_config(),
             )
-        };
+        });
         complete_output(cx, result)
     })
 }
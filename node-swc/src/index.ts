@@ -1,4 +1,5 @@
 import {
+  CompilerOptions,
   Plugin,
   ParseOptions,
   Module,
@@ -28,8 +29,8 @@ export function plugins(ps: Plugin[]): Plugin {
 }
 
 export class Compiler extends wrapNativeSuper(native.Compiler) {
-  public constructor() {
-    super();
+  public constructor(options?: CompilerOptions) {
+    super(!!(options && options.jsonDiagnostics));
   }
 
   parse(
@@ -186,6 +186,13 @@ export interface Options extends Config {
    */
   sourceRoot?: string;
 
+  /**
+   * Embed input files in `sourcesContent` of the generated source map.
+   *
+   * Defaults to `true`.
+   */
+  inlineSourcesContent?: boolean;
+
   plugin?: Plugin;
 
   isModule?: boolean;
@@ -261,6 +268,102 @@ export interface JscConfig {
    * Defaults to `es3` (which enableds **all** pass).
    */
   target?: JscTarget;
+
+  /**
+   * Native plugins built with `swc_plugin`, which are invoked before all other
+   * passes.
+   */
+  plugins?: NativePluginConfig[];
+
+  /**
+   * Mangles and compresses the output.
+   */
+  minify?: MinifyConfig;
+
+  /**
+   * Directory used to resolve non-relative imports and `paths`, relative to
+   * `cwd`.
+   */
+  baseUrl?: string;
+
+  /**
+   * Aliases for import paths, like `paths` of `tsconfig.json`.
+   */
+  paths?: { [from: string]: string[] };
+
+  /**
+   * Extension of emitted files, e.g. `"js"` or `"mjs"`. Imports of script
+   * files are rewritten to use it.
+   */
+  importExtension?: string;
+
+  /**
+   * Options for the code generator.
+   */
+  output?: OutputConfig;
+}
+
+export interface OutputConfig {
+  /**
+   * Preferred quote of string literals. Defaults to `single`.
+   */
+  quote?: "single" | "double";
+  /**
+   * Number of spaces used for each level of indentation. Defaults to `4`.
+   */
+  indentWidth?: number;
+  /**
+   * Indents with tabs instead of spaces. Defaults to `false`.
+   */
+  useTabs?: boolean;
+  /**
+   * Escapes non-ascii characters in strings, template literals and
+   * identifiers. Defaults to `false`.
+   */
+  asciiOnly?: boolean;
+  /**
+   * Comments kept in the output.
+   *
+   *  - `all`: Keeps all comments.
+   *  - `none`: Removes all comments.
+   *  - `some`: Keeps license comments, which start with `!` or contain
+   *    `@license` or `@preserve`.
+   *  - Other strings are used as a regex which matches comments to keep.
+   *
+   * Defaults to `some` if minified, and `all` otherwise.
+   */
+  comments?: "all" | "none" | "some" | string;
+  /**
+   * Moves license comments to `license` of the output, instead of printing
+   * them.
+   */
+  extractComments?: boolean;
+}
+
+export interface MinifyConfig {
+  /**
+   * Renames local bindings. Defaults to `true`.
+   */
+  mangle?: boolean;
+  /**
+   * Joins statements and shortens expressions. Defaults to `true`.
+   */
+  compress?: boolean;
+  /**
+   * Names which are not used for renamed bindings.
+   */
+  reserved?: string[];
+}
+
+export interface NativePluginConfig {
+  /**
+   * Path to the dynamic library.
+   */
+  path: string;
+  /**
+   * Passed to the plugin.
+   */
+  options?: any;
 }
 
 export type JscTarget =
@@ -270,7 +373,9 @@ export type JscTarget =
   | "es2016"
   | "es2017"
   | "es2018"
-  | "es2019";
+  | "es2019"
+  | "es2020"
+  | "es2021";
 
 export type ParserConfig = TsParserConfig | EsParserConfig;
 export interface TsParserConfig {
@@ -331,6 +436,24 @@ export interface EsParserConfig {
    * Defaults to `false`
    */
   nullishCoalescing?: boolean;
+  /**
+   * Enables `&&=`, `||=` and `??=`.
+   *
+   * Defaults to `false`
+   */
+  logicalAssignment?: boolean;
+  /**
+   * Enables `static { ... }` in classes.
+   *
+   * Defaults to `false`
+   */
+  staticBlocks?: boolean;
+  /**
+   * Enables `#x in obj`.
+   *
+   * Defaults to `false`
+   */
+  privateInObject?: boolean;
 }
 
 /**
@@ -351,6 +474,20 @@ export interface TransformConfig {
 }
 
 export interface ReactConfig {
+  /**
+   * `classic` emits `React.createElement` calls, while `automatic` imports
+   * `jsx` functions from `react/jsx-runtime`.
+   *
+   * Defaults to `classic`.
+   */
+  runtime?: "classic" | "automatic";
+  /**
+   * Module which `jsx` functions are imported from, when `runtime` is
+   * `automatic`.
+   *
+   * Defaults to `react`.
+   */
+  importSource?: string;
   /**
    * Replace the function used when compiling JSX expressions.
    *
@@ -422,7 +559,11 @@ export interface GlobalPassOption {
   envs?: string[];
 }
 
-export type ModuleConfig = CommonJsConfig | UmdConfig | AmdConfig;
+export type ModuleConfig =
+  | CommonJsConfig
+  | UmdConfig
+  | AmdConfig
+  | SystemJsConfig;
 
 export interface BaseModuleConfig {
   /**
@@ -506,6 +647,18 @@ export interface AmdConfig extends BaseModuleConfig {
   moduleId: string;
 }
 
+export interface SystemJsConfig {
+  type: "systemjs";
+  moduleId?: string;
+
+  /**
+   * Emits 'use strict' directive.
+   *
+   * Defaults to `true`.
+   */
+  strictMode?: boolean;
+}
+
 export interface Output {
   /**
    * Transformed code
@@ -515,6 +668,92 @@ export interface Output {
    * Sourcemap (**not** base64 encoded)
    */
   map?: string;
+  /**
+   * License comments, if `jsc.output.extractComments` is enabled.
+   * Typically written to `<output>.LICENSE.txt`.
+   */
+  license?: string;
+  /**
+   * Warnings reported while processing the input, if the compiler is created
+   * with `jsonDiagnostics`.
+   */
+  diagnostics?: Diagnostic[];
+}
+
+export interface CompilerOptions {
+  /**
+   * Attach diagnostics to outputs and errors as `diagnostics` instead of
+   * printing them to stderr.
+   *
+   * Defaults to `false`.
+   */
+  jsonDiagnostics?: boolean;
+}
+
+/**
+ * Errors thrown by a compiler created with `jsonDiagnostics` have
+ * `diagnostics` reported while processing the input.
+ */
+export interface SwcError extends Error {
+  diagnostics: Diagnostic[];
+}
+
+export interface Diagnostic {
+  level: "bug" | "error" | "warning" | "note" | "help" | "failure-note";
+  code?: DiagnosticCode;
+  message: string;
+  spans: DiagnosticSpan[];
+  /**
+   * Notes and helps attached to this diagnostic.
+   */
+  children: Diagnostic[];
+  suggestions: DiagnosticSuggestion[];
+  /**
+   * Human readable form of this diagnostic.
+   */
+  rendered?: string;
+}
+
+export interface DiagnosticCode {
+  code: string;
+  kind: "error" | "lint";
+}
+
+export interface DiagnosticSpan {
+  fileName: string;
+  byteStart: number;
+  byteEnd: number;
+  /**
+   * 1-based.
+   */
+  lineStart: number;
+  /**
+   * 1-based.
+   */
+  lineEnd: number;
+  /**
+   * 1-based.
+   */
+  columnStart: number;
+  /**
+   * 1-based.
+   */
+  columnEnd: number;
+  isPrimary: boolean;
+  label?: string;
+}
+
+export interface DiagnosticSuggestion {
+  message: string;
+  applicability:
+    | "machine-applicable"
+    | "has-placeholders"
+    | "maybe-incorrect"
+    | "unspecified";
+  /**
+   * Each substitution is an alternative fix.
+   */
+  substitutions: { span: DiagnosticSpan; snippet: string }[][];
 }
 
 export interface MatchPattern { }
@@ -561,7 +800,8 @@ export type ClassMember =
   | PrivateMethod
   | ClassProperty
   | PrivateProperty
-  | TsIndexSignature;
+  | TsIndexSignature
+  | StaticBlock;
 
 export interface ClassPropertyBase extends Node, HasSpan, HasDecorator {
   value?: Expression;
@@ -641,6 +881,12 @@ export interface PrivateMethod extends ClassMethodBase {
   key: PrivateName;
 }
 
+export interface StaticBlock extends Node, HasSpan {
+  type: "StaticBlock";
+
+  body: BlockStatement;
+}
+
 export interface Decorator extends Node, HasSpan {
   type: "Decorator";
 
@@ -1809,6 +2055,7 @@ export interface TsConstructorType extends Node, HasSpan {
 
   typeParams: TsTypeParameterDeclaration;
   typeAnnotation: TsTypeAnnotation;
+  abstract: boolean;
 }
 
 export interface TsTypeReference extends Node, HasSpan {
@@ -1861,7 +2108,14 @@ export interface TsArrayType extends Node, HasSpan {
 export interface TsTupleType extends Node, HasSpan {
   type: "TsTupleType";
 
-  elemTypes: TsType[];
+  elemTypes: TsTupleElement[];
+}
+
+export interface TsTupleElement extends Node, HasSpan {
+  type: "TsTupleElement";
+
+  label?: Identifier | RestElement;
+  ty: TsType;
 }
 
 export interface TsOptionalType extends Node, HasSpan {
@@ -1944,7 +2198,14 @@ export interface TsLiteralType extends Node, HasSpan {
   literal: TsLiteral;
 }
 
-export type TsLiteral = NumericLiteral | StringLiteral | BooleanLiteral | TemplateLiteral;
+export type TsLiteral = NumericLiteral | StringLiteral | BooleanLiteral | TsTemplateLiteralType;
+
+export interface TsTemplateLiteralType extends Node, HasSpan {
+  type: "TemplateLiteral";
+
+  types: TsType[];
+  quasis: TemplateElement[];
+}
 
 // // ================
 // // TypeScript declarations
@@ -0,0 +1,188 @@
+//! Abi of native plugins.
+//!
+//! A plugin is a `cdylib` crate which exports functions generated by
+//! [define_plugin]. swc loads plugins named in `jsc.plugins` of `.swcrc` and
+//! runs them before all other passes.
+//!
+//! Programs are passed to plugins using [codec], so a plugin should be built
+//! against the same version of `swc_ecma_ast` as swc. [ABI_VERSION] is the
+//! version of this crate, which depends on an exact version of
+//! `swc_ecma_ast`, and swc refuses to load a plugin built for another version.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use swc_plugin::define_plugin;
+//!
+//! define_plugin!(|options: serde_json::Value| -> Result<_, anyhow::Error> {
+//!     Ok(my_pass(serde_json::from_value(options)?))
+//! });
+//! ```
+
+use anyhow::{Context, Error};
+use std::{
+    mem,
+    panic::{catch_unwind, AssertUnwindSafe},
+    slice,
+};
+use swc_common::{FoldWith, Globals, GLOBALS};
+use swc_ecma_ast::Program;
+use swc_ecma_transforms::pass::Pass;
+
+pub mod codec;
+
+/// Version of the abi and the ast.
+pub const ABI_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const ABI_VERSION_SYMBOL: &[u8] = b"swc_plugin_abi_version\0";
+pub const PROCESS_SYMBOL: &[u8] = b"swc_plugin_process\0";
+pub const FREE_SYMBOL: &[u8] = b"swc_plugin_free\0";
+
+/// `extern "C" fn swc_plugin_abi_version(len) -> *const u8`
+///
+/// Returns [ABI_VERSION] the plugin is built with, and stores its length in
+/// `len`.
+pub type AbiVersionFn = unsafe extern "C" fn(len: *mut usize) -> *const u8;
+
+/// `extern "C" fn swc_plugin_process(program, program_len, options,
+/// options_len, out) -> bool`
+///
+/// `program` is a [Program] encoded using [codec], and `options` is `options`
+/// of the plugin config serialized as json. On success, the transformed
+/// program is stored in `out` and `true` is returned. Otherwise, an error
+/// message is stored in `out`.
+pub type ProcessFn = unsafe extern "C" fn(
+    program: *const u8,
+    program_len: usize,
+    options: *const u8,
+    options_len: usize,
+    out: *mut RawBuffer,
+) -> bool;
+
+/// `extern "C" fn swc_plugin_free(buf: RawBuffer)`
+///
+/// Buffers created by a plugin should be freed by the plugin, as the plugin
+/// may use another allocator.
+pub type FreeFn = unsafe extern "C" fn(buf: RawBuffer);
+
+/// A `Vec<u8>` passed across the abi boundary.
+#[repr(C)]
+#[derive(Debug)]
+pub struct RawBuffer {
+    pub ptr: *mut u8,
+    pub len: usize,
+    pub cap: usize,
+}
+
+impl RawBuffer {
+    pub const fn empty() -> Self {
+        RawBuffer {
+            ptr: 0 as *mut u8,
+            len: 0,
+            cap: 0,
+        }
+    }
+
+    pub fn from_vec(mut v: Vec<u8>) -> Self {
+        let buf = RawBuffer {
+            ptr: v.as_mut_ptr(),
+            len: v.len(),
+            cap: v.capacity(),
+        };
+        mem::forget(v);
+        buf
+    }
+
+    /// # Safety
+    ///
+    /// `self` should be created by [RawBuffer::from_vec] of the same binary.
+    pub unsafe fn into_vec(self) -> Vec<u8> {
+        Vec::from_raw_parts(self.ptr, self.len, self.cap)
+    }
+
+    /// # Safety
+    ///
+    /// `self` should be created by [RawBuffer::from_vec] and should not be
+    /// freed yet.
+    pub unsafe fn as_slice(&self) -> &[u8] {
+        if self.ptr.is_null() {
+            return &[];
+        }
+        slice::from_raw_parts(self.ptr, self.len)
+    }
+}
+
+/// Defines functions required to load a crate as a plugin.
+///
+/// `$create` is called with `options` of the plugin config, and it should
+/// return `Result<impl Pass, anyhow::Error>`.
+#[macro_export]
+macro_rules! define_plugin {
+    ($create:expr) => {
+        #[no_mangle]
+        pub unsafe extern "C" fn swc_plugin_abi_version(len: *mut usize) -> *const u8 {
+            *len = $crate::ABI_VERSION.len();
+            $crate::ABI_VERSION.as_ptr()
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn swc_plugin_process(
+            program: *const u8,
+            program_len: usize,
+            options: *const u8,
+            options_len: usize,
+            out: *mut $crate::RawBuffer,
+        ) -> bool {
+            $crate::__process(program, program_len, options, options_len, out, $create)
+        }
+
+        #[no_mangle]
+        pub unsafe extern "C" fn swc_plugin_free(buf: $crate::RawBuffer) {
+            drop(buf.into_vec())
+        }
+    };
+}
+
+#[doc(hidden)]
+pub unsafe fn __process<F, P>(
+    program: *const u8,
+    program_len: usize,
+    options: *const u8,
+    options_len: usize,
+    out: *mut RawBuffer,
+    create: F,
+) -> bool
+where
+    F: FnOnce(serde_json::Value) -> Result<P, Error>,
+    P: Pass,
+{
+    let program = slice::from_raw_parts(program, program_len);
+    let options = slice::from_raw_parts(options, options_len);
+
+    let res = catch_unwind(AssertUnwindSafe(|| process(program, options, create)))
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("plugin panicked")));
+
+    let (buf, ok) = match res {
+        Ok(v) => (v, true),
+        Err(err) => (format!("{:?}", err).into_bytes(), false),
+    };
+    *out = RawBuffer::from_vec(buf);
+    ok
+}
+
+fn process<F, P>(program: &[u8], options: &[u8], create: F) -> Result<Vec<u8>, Error>
+where
+    F: FnOnce(serde_json::Value) -> Result<P, Error>,
+    P: Pass,
+{
+    let options = serde_json::from_slice(options).context("failed to parse options")?;
+
+    GLOBALS.set(&Globals::new(), || {
+        let program: Program = codec::from_slice(program).context("failed to decode program")?;
+
+        let mut pass = create(options).context("failed to create pass")?;
+        let program = program.fold_with(&mut pass);
+
+        codec::to_vec(&program).context("failed to encode program")
+    })
+}
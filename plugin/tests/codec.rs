@@ -0,0 +1,103 @@
+use swc_common::FileName;
+use swc_ecma_ast::Program;
+use swc_ecma_parser::{EsConfig, Parser, Session, SourceFileInput, Syntax, TsConfig};
+use swc_plugin::codec;
+
+fn round_trip(syntax: Syntax, is_module: bool, src: &str) {
+    testing::run_test(false, |cm, handler| {
+        let fm = cm.new_source_file(FileName::Anon, src.into());
+        let mut p = Parser::new(
+            Session { handler },
+            syntax,
+            SourceFileInput::from(&*fm),
+            None,
+        );
+        let program = if is_module {
+            p.parse_module().map(Program::Module)
+        } else {
+            p.parse_script().map(Program::Script)
+        }
+        .map_err(|mut e| {
+            e.emit();
+        })?;
+
+        let buf = codec::to_vec(&program).expect("failed to encode");
+        let decoded: Program = codec::from_slice(&buf).expect("failed to decode");
+
+        // Some nodes, like `PatOrExpr`, are not restored as is, but the result
+        // should be same as json.
+        let json = serde_json::to_string(&program).unwrap();
+        let expected: Program = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, expected);
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+#[test]
+fn module() {
+    round_trip(
+        Syntax::Es(EsConfig {
+            jsx: true,
+            class_props: true,
+            class_private_props: true,
+            nullish_coalescing: true,
+            optional_chaining: true,
+            ..Default::default()
+        }),
+        true,
+        r#"
+import React, { useState as s } from "react";
+import * as ns from "./ns";
+export default class A extends B {
+    static x = 1;
+    #y = -2.5e-7;
+    async *gen(a = 1, ...rest) {
+        for await (const v of rest) yield `${v}é\n`;
+    }
+}
+export const re = /a(?<n>b)+/giu, big = 10n, nul = null, u = void 0;
+export function f({ a, b: [c, , d] = [] }) {
+    label: while (a?.b ?? c) { if (d) break label; else continue; }
+    return <div id="x">{a} text</div>;
+}
+export * from "./all";
+"#,
+    );
+}
+
+#[test]
+fn script() {
+    round_trip(
+        Syntax::Es(Default::default()),
+        false,
+        r#"
+"use strict";
+var a = 1, b = "str", c = [1, , 3], d = { e: true, ["f"]: false, g() {} };
+try { throw new Error(a >>> 2); } catch (e) { a ||= 1; } finally { b += "😀"; }
+switch (a) { case 1: case 2: break; default: a = typeof b === "string" ? 1 : 2; }
+"#,
+    );
+}
+
+#[test]
+fn typescript() {
+    round_trip(
+        Syntax::Typescript(TsConfig {
+            tsx: false,
+            decorators: true,
+            ..Default::default()
+        }),
+        true,
+        r#"
+enum E { A = 1, B = "b" }
+declare module "m" { export type T<U extends string = "u"> = [a: U, ...rest: U[]]; }
+namespace N { export const x: readonly number[] = []; }
+@dec() abstract class C<T> implements I { private constructor(public readonly p?: T) { super(); } }
+type M<T> = { -readonly [K in keyof T]+?: T[K] extends infer R ? R : never };
+let x = <any>y as unknown as Record<string, number>;
+function g(this: Window, a?: number): asserts a is number {}
+"#,
+    );
+}
@@ -1,4 +1,5 @@
 use crate::config::{GlobalPassOption, JscTarget, ModuleConfig};
+use anyhow::{Context, Error};
 use either::Either;
 use std::{collections::HashMap, sync::Arc};
 use swc_atoms::JsWord;
@@ -8,7 +9,8 @@ use swc_ecmascript::{
     preset_env,
     transforms::{
         compat, const_modules, fixer, helpers, hygiene, modules,
-        pass::{Optional, Pass},
+        optimization::minify::{self, minifier},
+        pass::{noop, Optional, Pass},
         typescript,
     },
 };
@@ -22,6 +24,7 @@ pub struct PassBuilder<'a, 'b, P: Pass> {
     global_mark: Mark,
     target: JscTarget,
     loose: bool,
+    minify: Option<minify::Config>,
 }
 
 impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
@@ -40,6 +43,7 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
             global_mark,
             loose,
             env: None,
+            minify: None,
         }
     }
 
@@ -53,6 +57,7 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
             loose: self.loose,
             env: self.env,
             global_mark: self.global_mark,
+            minify: self.minify,
         }
     }
 
@@ -82,6 +87,12 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
         self
     }
 
+    /// Enables the minifier, which runs after `hygiene`.
+    pub fn minify(mut self, minify: Option<minify::Config>) -> Self {
+        self.minify = minify;
+        self
+    }
+
     /// # Arguments
     /// ## module
     ///  - Use `None` if you want swc to emit import statements.
@@ -93,25 +104,33 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
     ///  - module handler
     ///  - helper injector
     ///  - identifier hygiene handler
+    ///  - minifier
     ///  - fixer
+    ///
+    /// Returns an error if targets of `preset_env` cannot be resolved.
     pub fn finalize(
         self,
         root_mark: Mark,
         syntax: Syntax,
         module: Option<ModuleConfig>,
-    ) -> impl Pass {
+    ) -> Result<impl Pass, Error> {
         let need_interop_analysis = match module {
             Some(ModuleConfig::CommonJs(ref c)) => !c.no_interop,
             Some(ModuleConfig::Amd(ref c)) => !c.config.no_interop,
             Some(ModuleConfig::Umd(ref c)) => !c.config.no_interop,
-            None => false,
+            Some(ModuleConfig::SystemJs(..)) | None => false,
         };
 
         // compat
         let compat_pass = if let Some(env) = self.env {
-            Either::Left(preset_env::preset_env(self.global_mark, env))
+            Either::Left(
+                preset_env::preset_env(self.global_mark, env)
+                    .context("failed to resolve targets of env")?,
+            )
         } else {
             Either::Right(chain!(
+                Optional::new(compat::es2021(), self.target <= JscTarget::Es2020),
+                Optional::new(compat::es2021::literals(), self.target <= JscTarget::Es2019),
                 Optional::new(compat::es2018(), self.target <= JscTarget::Es2018),
                 Optional::new(compat::es2017(), self.target <= JscTarget::Es2017),
                 Optional::new(compat::es2016(), self.target <= JscTarget::Es2016),
@@ -137,7 +156,12 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
             ))
         };
 
-        chain!(
+        let minify_pass = match self.minify {
+            Some(config) => Either::Left(minifier(config)),
+            None => Either::Right(noop()),
+        };
+
+        Ok(chain!(
             self.pass,
             compat_pass,
             compat::reserved_words::reserved_words(),
@@ -150,8 +174,9 @@ impl<'a, 'b, P: Pass> PassBuilder<'a, 'b, P> {
             ModuleConfig::build(self.cm.clone(), root_mark, module),
             // hygiene
             hygiene(),
+            minify_pass,
             // fixer
             fixer(),
-        )
+        ))
     }
 }
@@ -1,4 +1,7 @@
-use crate::builder::PassBuilder;
+use crate::{
+    builder::PassBuilder,
+    plugin::{PluginConfig, Plugins},
+};
 use anyhow::{bail, Context, Error};
 use dashmap::DashMap;
 use once_cell::sync::Lazy;
@@ -13,15 +16,16 @@ use std::{
 };
 use swc_atoms::JsWord;
 pub use swc_common::chain;
-use swc_common::{errors::Handler, FileName, Mark, SourceMap};
+use swc_common::{comments::Comment, errors::Handler, FileName, Mark, SourceMap, DUMMY_SP};
 pub use swc_ecmascript::parser::JscTarget;
 use swc_ecmascript::{
-    ast::{Expr, ExprStmt, ModuleItem, Stmt},
+    ast::{Expr, ExprStmt, Lit, ModuleItem, Number, Stmt, Str},
+    codegen,
     parser::{lexer::Lexer, Parser, Session as ParseSess, SourceFileInput, Syntax, TsConfig},
     preset_env,
     transforms::{
         const_modules, modules,
-        optimization::{simplifier, InlineGlobals, JsonParse},
+        optimization::{minify, simplifier, InlineGlobals, JsonParse},
         pass::{noop, Optional, Pass},
         proposals::{class_properties, decorators, export, nullish_coalescing, optional_chaining},
         react, resolver_with_mark, typescript,
@@ -46,7 +50,7 @@ pub struct ParseOptions {
     pub target: JscTarget,
 }
 
-#[derive(Default, Deserialize)]
+#[derive(Default, Clone, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Options {
     #[serde(flatten, default)]
@@ -93,6 +97,11 @@ pub struct Options {
     #[serde(default)]
     pub source_root: Option<String>,
 
+    /// Embeds input files in `sourcesContent` of source maps. Defaults to
+    /// `true`.
+    #[serde(default)]
+    pub inline_sources_content: Option<bool>,
+
     #[serde(default = "default_is_module")]
     pub is_module: bool,
 }
@@ -101,7 +110,7 @@ fn default_is_module() -> bool {
     true
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum SourceMapsConfig {
     Bool(bool),
@@ -126,7 +135,7 @@ impl Default for SourceMapsConfig {
     }
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum InputSourceMap {
     Bool(bool),
@@ -139,25 +148,69 @@ impl Default for InputSourceMap {
     }
 }
 
+/// Controls the content of generated source maps.
+#[derive(Debug, Clone)]
+pub struct SourceMapOptions {
+    /// Name of the input file in `sources`.
+    pub file_name: Option<String>,
+    pub source_root: Option<String>,
+    /// Embeds input files in `sourcesContent`.
+    pub inline_sources_content: bool,
+}
+
+impl Default for SourceMapOptions {
+    fn default() -> Self {
+        SourceMapOptions {
+            file_name: None,
+            source_root: None,
+            inline_sources_content: true,
+        }
+    }
+}
+
 impl Options {
+    pub fn source_map_options(&self) -> SourceMapOptions {
+        SourceMapOptions {
+            file_name: self.source_file_name.clone(),
+            source_root: self.source_root.clone(),
+            inline_sources_content: self.inline_sources_content.unwrap_or(true),
+        }
+    }
+
     pub fn build(
         &self,
         cm: &Arc<SourceMap>,
         handler: &Handler,
         is_module: bool,
         config: Option<Config>,
-    ) -> BuiltConfig<impl Pass> {
+    ) -> Result<BuiltConfig<impl Pass>, Error> {
         let mut config = config.unwrap_or_else(Default::default);
         if let Some(ref c) = self.config {
             config.merge(c)
         }
 
+        let codegen = config.codegen_config();
+        let comments = config.jsc.output.comments.clone().unwrap_or({
+            if codegen.minify {
+                CommentsConfig::Policy(CommentsPolicy::Some)
+            } else {
+                CommentsConfig::Policy(CommentsPolicy::All)
+            }
+        });
+        let extract_comments = config.jsc.output.extract_comments.unwrap_or(false);
+
         let JscConfig {
             transform,
             syntax,
             external_helpers,
             target,
             loose,
+            plugins,
+            minify,
+            base_url,
+            paths,
+            import_extension,
+            output: _,
         } = config.jsc;
 
         let syntax = syntax.unwrap_or_default();
@@ -201,12 +254,44 @@ impl Options {
             pass
         };
 
+        let import_rewriter = self.import_rewriter(base_url, paths, import_extension);
+
         let root_mark = Mark::fresh(Mark::root());
 
         let pass = chain!(
+            // Plugins receive modules before marks are applied.
+            Plugins::new(plugins),
             // handle jsx
             Optional::new(react::react(cm.clone(), transform.react), syntax.jsx()),
-            Optional::new(typescript::strip(), syntax.typescript()),
+            Optional::new(
+                typescript::strip_with_config(typescript::Config {
+                    preserve_import_export_assign: match config.module {
+                        Some(ModuleConfig::CommonJs(..))
+                        | Some(ModuleConfig::Amd(..))
+                        | Some(ModuleConfig::Umd(..))
+                        | Some(ModuleConfig::SystemJs(..)) => true,
+                        _ => false,
+                    },
+                    imported_enums: transform
+                        .imported_enums
+                        .into_iter()
+                        .map(|(src, enums)| {
+                            let enums = enums
+                                .into_iter()
+                                .map(|(name, members)| {
+                                    let values = members
+                                        .into_iter()
+                                        .map(|(member, v)| (member, v.into()))
+                                        .collect();
+                                    (name, values)
+                                })
+                                .collect();
+                            (src, enums)
+                        })
+                        .collect(),
+                }),
+                syntax.typescript()
+            ),
             Optional::new(nullish_coalescing(), syntax.nullish_coalescing()),
             Optional::new(optional_chaining(), syntax.optional_chaining()),
             resolver_with_mark(root_mark),
@@ -218,22 +303,39 @@ impl Options {
                 }),
                 syntax.decorators()
             ),
-            Optional::new(class_properties(), syntax.class_props()),
+            Optional::new(
+                class_properties(),
+                syntax.class_props() || syntax.static_blocks() || syntax.private_in_object()
+            ),
             Optional::new(
                 export(),
                 syntax.export_default_from() || syntax.export_namespace_from()
             ),
             Optional::new(simplifier(Default::default()), enable_optimizer),
-            json_parse_pass
+            json_parse_pass,
+            import_rewriter
         );
 
+        let env = match config.env {
+            Some(mut env) => {
+                if env.config_path.is_none() && !self.filename.is_empty() {
+                    env.config_path = Some(self.filename.clone().into());
+                }
+                Some(env)
+            }
+            None => None,
+        };
+
         let pass = PassBuilder::new(&cm, &handler, loose, root_mark, pass)
             .target(target)
-            .preset_env(config.env)
-            .finalize(root_mark, syntax, config.module);
-
-        BuiltConfig {
-            minify: config.minify.unwrap_or(false),
+            .preset_env(env)
+            .minify(minify)
+            .finalize(root_mark, syntax, config.module)?;
+
+        Ok(BuiltConfig {
+            codegen,
+            comments,
+            extract_comments,
             pass,
             external_helpers,
             syntax,
@@ -244,6 +346,43 @@ impl Options {
                 .clone()
                 .unwrap_or(SourceMapsConfig::Bool(false)),
             input_source_map: self.input_source_map.clone(),
+            source_map_options: self.source_map_options(),
+        })
+    }
+
+    /// Creates a pass which rewrites import paths, if `baseUrl`, `paths` or
+    /// `importExtension` is configured.
+    fn import_rewriter(
+        &self,
+        base_url: Option<PathBuf>,
+        paths: HashMap<String, Vec<String>>,
+        extension: Option<String>,
+    ) -> Box<dyn Pass> {
+        if self.filename.is_empty()
+            || (base_url.is_none() && paths.is_empty() && extension.is_none())
+        {
+            return box noop();
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let resolver = modules::path::TsConfigResolver::new(
+                modules::path::NodeResolver,
+                self.cwd.join(base_url.unwrap_or_default()),
+                paths.into_iter().collect(),
+            );
+
+            box modules::path::import_rewriter(
+                self.cwd.join(&self.filename),
+                resolver,
+                modules::path::Config { extension },
+            )
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let _ = (base_url, paths, extension);
+            box noop()
         }
     }
 }
@@ -312,6 +451,12 @@ impl Default for Rc {
                     external_helpers: false,
                     target: Default::default(),
                     loose: false,
+                    plugins: vec![],
+                    minify: None,
+                    base_url: None,
+                    paths: Default::default(),
+                    import_extension: None,
+                    output: Default::default(),
                 },
                 module: None,
                 minify: None,
@@ -329,6 +474,12 @@ impl Default for Rc {
                     external_helpers: false,
                     target: Default::default(),
                     loose: false,
+                    plugins: vec![],
+                    minify: None,
+                    base_url: None,
+                    paths: Default::default(),
+                    import_extension: None,
+                    output: Default::default(),
                 },
                 module: None,
                 minify: None,
@@ -346,6 +497,12 @@ impl Default for Rc {
                     external_helpers: false,
                     target: Default::default(),
                     loose: false,
+                    plugins: vec![],
+                    minify: None,
+                    base_url: None,
+                    paths: Default::default(),
+                    import_extension: None,
+                    output: Default::default(),
                 },
                 module: None,
                 minify: None,
@@ -385,10 +542,27 @@ impl Rc {
 
         bail!("not matched")
     }
+
+    /// Makes paths of plugins relative to `dir`, which is the directory of
+    /// the config file.
+    pub(crate) fn resolve_plugin_paths(&mut self, dir: &Path) {
+        let cs = match self {
+            Rc::Single(c) => std::slice::from_mut(c),
+            Rc::Multi(cs) => &mut cs[..],
+        };
+
+        for c in cs {
+            for plugin in &mut c.jsc.plugins {
+                if plugin.path.is_relative() {
+                    plugin.path = dir.join(&plugin.path);
+                }
+            }
+        }
+    }
 }
 
 /// A single object in the `.swcrc` file
-#[derive(Debug, Default, Clone, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields, rename_all = "camelCase")]
 pub struct Config {
     #[serde(default)]
@@ -473,6 +647,26 @@ impl Config {
 
         Ok(true)
     }
+
+    /// Options for the code generator.
+    pub fn codegen_config(&self) -> codegen::Config {
+        let output = &self.jsc.output;
+
+        codegen::Config {
+            minify: self.minify.unwrap_or_else(|| self.jsc.minify.is_some()),
+            quote: match output.quote {
+                Some(QuoteStyle::Double) => codegen::Quote::Double,
+                Some(QuoteStyle::Single) | None => codegen::Quote::Single,
+            },
+            indent: match (output.use_tabs, output.indent_width) {
+                (Some(true), _) => codegen::Indent::Tab,
+                (_, Some(width)) => codegen::Indent::Spaces(width),
+                _ => Default::default(),
+            },
+            ascii_only: output.ascii_only.unwrap_or(false),
+            target: self.jsc.target,
+        }
+    }
 }
 
 /// One `BuiltConfig` per a directory with swcrc
@@ -480,10 +674,13 @@ pub struct BuiltConfig<P: Pass> {
     pub pass: P,
     pub syntax: Syntax,
     pub target: JscTarget,
-    pub minify: bool,
+    pub codegen: codegen::Config,
+    pub comments: CommentsConfig,
+    pub extract_comments: bool,
     pub external_helpers: bool,
     pub source_maps: SourceMapsConfig,
     pub input_source_map: InputSourceMap,
+    pub source_map_options: SourceMapOptions,
     pub is_module: bool,
 }
 
@@ -504,6 +701,112 @@ pub struct JscConfig {
 
     #[serde(default)]
     pub loose: bool,
+
+    /// Native plugins, which are invoked before other passes.
+    #[serde(default)]
+    pub plugins: Vec<PluginConfig>,
+
+    /// Mangles and compresses the output. Minified code is emitted if this is
+    /// set, unless the top-level `minify` is `false`.
+    #[serde(default)]
+    pub minify: Option<minify::Config>,
+
+    /// Directory used to resolve non-relative specifiers and `paths`, relative
+    /// to `cwd`.
+    #[serde(default)]
+    pub base_url: Option<PathBuf>,
+
+    /// Aliases for module specifiers, like `paths` of `tsconfig.json`.
+    #[serde(default)]
+    pub paths: HashMap<String, Vec<String>>,
+
+    /// Extension of emitted files. If set, specifiers of script files are
+    /// rewritten to use it.
+    #[serde(default)]
+    pub import_extension: Option<String>,
+
+    #[serde(default)]
+    pub output: OutputConfig,
+}
+
+/// Options for the code generator.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct OutputConfig {
+    /// Preferred quote of string literals. Defaults to `single`.
+    #[serde(default)]
+    pub quote: Option<QuoteStyle>,
+
+    /// Number of spaces used for each level of indentation. Defaults to 4.
+    #[serde(default)]
+    pub indent_width: Option<usize>,
+
+    /// Indents with tabs instead of spaces.
+    #[serde(default)]
+    pub use_tabs: Option<bool>,
+
+    /// Escapes non-ascii characters in strings, template literals and
+    /// identifiers.
+    #[serde(default)]
+    pub ascii_only: Option<bool>,
+
+    /// Comments kept in the output. Defaults to `some` if minified, and
+    /// `all` otherwise.
+    #[serde(default)]
+    pub comments: Option<CommentsConfig>,
+
+    /// Moves license comments to [TransformOutput::license], instead of
+    /// printing them.
+    ///
+    /// [TransformOutput::license]: crate::TransformOutput::license
+    #[serde(default)]
+    pub extract_comments: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CommentsConfig {
+    Policy(CommentsPolicy),
+    /// Keeps comments matching the regex.
+    Regex(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommentsPolicy {
+    All,
+    None,
+    /// Keeps license comments, which start with `!` or contain `@license` or
+    /// `@preserve`.
+    Some,
+}
+
+impl CommentsConfig {
+    /// Creates a predicate which returns `true` for comments to keep.
+    pub fn filter(&self) -> Result<Box<dyn Fn(&Comment) -> bool>, Error> {
+        let filter: Box<dyn Fn(&Comment) -> bool> = match *self {
+            CommentsConfig::Policy(CommentsPolicy::All) => box |_: &Comment| true,
+            CommentsConfig::Policy(CommentsPolicy::None) => box |_: &Comment| false,
+            CommentsConfig::Policy(CommentsPolicy::Some) => box is_license_comment,
+            CommentsConfig::Regex(ref s) => {
+                let re = Regex::new(s).with_context(|| format!("invalid regex: {}", s))?;
+                box move |c: &Comment| re.is_match(&c.text)
+            }
+        };
+
+        Ok(filter)
+    }
+}
+
+pub(crate) fn is_license_comment(c: &Comment) -> bool {
+    c.text.starts_with('!') || c.text.contains("@license") || c.text.contains("@preserve")
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteStyle {
+    Single,
+    Double,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -516,6 +819,8 @@ pub enum ModuleConfig {
     Umd(modules::umd::Config),
     #[serde(rename = "amd")]
     Amd(modules::amd::Config),
+    #[serde(rename = "systemjs")]
+    SystemJs(modules::system_js::Config),
 }
 
 impl ModuleConfig {
@@ -531,6 +836,7 @@ impl ModuleConfig {
             }
             Some(ModuleConfig::Umd(config)) => box modules::umd::umd(cm, root_mark, config),
             Some(ModuleConfig::Amd(config)) => box modules::amd::amd(config),
+            Some(ModuleConfig::SystemJs(config)) => box modules::system_js::system_js(config),
         }
     }
 }
@@ -549,6 +855,35 @@ pub struct TransformConfig {
 
     #[serde(default)]
     pub legacy_decorator: bool,
+
+    /// Values of `const enum`s exported by other modules, keyed by the import
+    /// source, the enum name and then the member name. Members of these enums
+    /// are inlined.
+    #[serde(default)]
+    pub imported_enums: HashMap<JsWord, HashMap<JsWord, HashMap<JsWord, EnumMemberValue>>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EnumMemberValue {
+    Num(f64),
+    Str(JsWord),
+}
+
+impl From<EnumMemberValue> for Lit {
+    fn from(v: EnumMemberValue) -> Self {
+        match v {
+            EnumMemberValue::Num(value) => Lit::Num(Number {
+                span: DUMMY_SP,
+                value,
+            }),
+            EnumMemberValue::Str(value) => Lit::Str(Str {
+                span: DUMMY_SP,
+                value,
+                has_escape: false,
+            }),
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -717,6 +1052,35 @@ impl Merge for JscConfig {
         self.transform.merge(&from.transform);
         self.target.merge(&from.target);
         self.external_helpers.merge(&from.external_helpers);
+        self.plugins.merge(&from.plugins);
+        self.minify.merge(&from.minify);
+        self.base_url.merge(&from.base_url);
+        self.paths.merge(&from.paths);
+        self.import_extension.merge(&from.import_extension);
+        self.output.merge(&from.output);
+    }
+}
+
+impl Merge for OutputConfig {
+    fn merge(&mut self, from: &Self) {
+        if from.quote.is_some() {
+            self.quote = from.quote;
+        }
+        if from.indent_width.is_some() {
+            self.indent_width = from.indent_width;
+        }
+        if from.use_tabs.is_some() {
+            self.use_tabs = from.use_tabs;
+        }
+        if from.ascii_only.is_some() {
+            self.ascii_only = from.ascii_only;
+        }
+        if from.comments.is_some() {
+            self.comments = from.comments.clone();
+        }
+        if from.extract_comments.is_some() {
+            self.extract_comments = from.extract_comments;
+        }
     }
 }
 
@@ -737,6 +1101,34 @@ impl Merge for Option<ModuleConfig> {
     }
 }
 
+impl Merge for Vec<PluginConfig> {
+    fn merge(&mut self, from: &Self) {
+        if !from.is_empty() {
+            *self = from.clone();
+        }
+    }
+}
+
+impl Merge for HashMap<String, Vec<String>> {
+    fn merge(&mut self, from: &Self) {
+        if !from.is_empty() {
+            *self = from.clone();
+        }
+    }
+}
+
+impl Merge for PathBuf {
+    fn merge(&mut self, from: &Self) {
+        *self = from.clone();
+    }
+}
+
+impl Merge for String {
+    fn merge(&mut self, from: &Self) {
+        *self = from.clone();
+    }
+}
+
 impl Merge for bool {
     fn merge(&mut self, from: &Self) {
         *self |= *from
@@ -754,6 +1146,9 @@ impl Merge for TransformConfig {
         self.optimizer.merge(&from.optimizer);
         self.const_modules.merge(&from.const_modules);
         self.react.merge(&from.react);
+        if !from.imported_enums.is_empty() {
+            self.imported_enums = from.imported_enums.clone();
+        }
     }
 }
 
@@ -763,6 +1158,12 @@ impl Merge for OptimizerConfig {
     }
 }
 
+impl Merge for minify::Config {
+    fn merge(&mut self, from: &Self) {
+        *self = from.clone();
+    }
+}
+
 impl Merge for GlobalPassOption {
     fn merge(&mut self, from: &Self) {
         *self = from.clone();
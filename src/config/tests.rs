@@ -10,3 +10,13 @@ fn object() {
 fn array() {
     let _: Rc = serde_json::from_str(include_str!("array.json")).expect("failed to parse");
 }
+
+#[test]
+fn plugins() {
+    let rc: Rc = serde_json::from_str(include_str!("plugins.json")).expect("failed to parse");
+    let config = rc.into_config(None).unwrap();
+
+    assert_eq!(config.jsc.plugins.len(), 2);
+    assert_eq!(config.jsc.plugins[0].options["foo"], true);
+    assert!(config.jsc.plugins[1].options.is_null());
+}
@@ -1,4 +1,5 @@
 #![feature(box_syntax, box_patterns)]
+#![feature(specialization)]
 
 pub use sourcemap;
 pub use swc_atoms as atoms;
@@ -7,20 +8,24 @@ pub use swc_ecmascript as ecmascript;
 
 mod builder;
 pub mod config;
+pub mod plugin;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod project;
 
 pub use crate::builder::PassBuilder;
 use crate::config::{
     BuiltConfig, Config, ConfigFile, InputSourceMap, JscTarget, Merge, Options, Rc, RootMode,
-    SourceMapsConfig,
+    SourceMapOptions, SourceMapsConfig,
 };
 use anyhow::{Context, Error};
 use common::{
-    comments::{Comment, Comments},
-    errors::Handler,
-    BytePos, FileName, FoldWith, Globals, SourceFile, SourceMap, Spanned, GLOBALS,
+    comments::{Comment, CommentKind, Comments},
+    errors::{DiagnosticBuffer, Handler, JsonDiagnostic},
+    BytePos, FileName, FoldWith, Globals, SourceFile, SourceMap, SourceMapGenConfig, Span, Spanned,
+    Visit, VisitWith, GLOBALS,
 };
 use ecmascript::{
-    ast::Program,
+    ast::{Ident, Program},
     codegen::{self, Emitter},
     parser::{lexer::Lexer, Parser, Session as ParseSess, Syntax},
     transforms::{
@@ -36,6 +41,7 @@ pub use ecmascript::{
 use serde::Serialize;
 use serde_json::error::Category;
 use std::{
+    collections::HashMap,
     fs::{read_to_string, File},
     path::{Path, PathBuf},
     sync::Arc,
@@ -50,6 +56,8 @@ pub struct Compiler {
     pub cm: Arc<SourceMap>,
     pub handler: Handler,
     comments: Comments,
+    /// Set if `handler` stores diagnostics as json.
+    diagnostics: Option<DiagnosticBuffer>,
 }
 
 #[derive(Debug, Serialize)]
@@ -57,6 +65,40 @@ pub struct TransformOutput {
     pub code: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub map: Option<String>,
+    /// License comments, if `extractComments` is enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Warnings reported while processing the input.
+    ///
+    /// Filled by [Compiler::capture_diagnostics].
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub diagnostics: Vec<JsonDiagnostic>,
+}
+
+/// An error, with diagnostics reported while processing the input.
+#[derive(Debug, Serialize)]
+pub struct ErrorOutput {
+    pub message: String,
+    pub diagnostics: Vec<JsonDiagnostic>,
+}
+
+impl ErrorOutput {
+    /// Converts `err` into a serializable form, with `diagnostics` reported
+    /// while processing the input.
+    pub fn new(err: &Error, diagnostics: Vec<JsonDiagnostic>) -> Self {
+        let mut message = format!("{:?}", err);
+        for d in &diagnostics {
+            if let Some(ref rendered) = d.rendered {
+                message.push('\n');
+                message.push_str(rendered);
+            }
+        }
+
+        ErrorOutput {
+            message,
+            diagnostics,
+        }
+    }
 }
 
 /// These are **low-level** apis.
@@ -93,10 +135,32 @@ impl Compiler {
     ) -> Result<(Program, Option<sourcemap::SourceMap>), Error> {
         self.run(|| {
             let orig = (|| {
+                // Load source map inlined as a data url, by simple string operations
+                let read_inline = || {
+                    const PREFIX: &str = "sourceMappingURL=data:application/json;base64,";
+                    let idx = fm.src.rfind(PREFIX)?;
+                    let encoded = fm.src[idx + PREFIX.len()..].trim_end();
+
+                    let res = base64::decode(encoded.as_bytes())
+                        .context("failed to decode base64-encoded source map");
+                    let res = match res {
+                        Ok(v) => v,
+                        Err(err) => return Some(Err(err)),
+                    };
+
+                    Some(sourcemap::SourceMap::from_slice(&res).context(
+                        "failed to read input source map from inlined base64 encoded string",
+                    ))
+                };
+
                 // Load original source map
                 match input_source_map {
                     InputSourceMap::Bool(false) => None,
                     InputSourceMap::Bool(true) => {
+                        if let Some(map) = read_inline() {
+                            return Some(map);
+                        }
+
                         // Load original source map if possible
                         match &fm.name {
                             FileName::Real(filename) => {
@@ -114,23 +178,7 @@ impl Compiler {
                     }
                     InputSourceMap::Str(ref s) => {
                         if s == "inline" {
-                            // Load inline source map by simple string
-                            // operations
-                            let s = "sourceMappingURL=data:application/json;base64,";
-                            let idx = s.rfind(s)?;
-                            let encoded = &s[idx + s.len()..];
-
-                            let res = base64::decode(encoded.as_bytes())
-                                .context("failed to decode base64-encoded source map");
-                            let res = match res {
-                                Ok(v) => v,
-                                Err(err) => return Some(Err(err)),
-                            };
-
-                            Some(sourcemap::SourceMap::from_slice(&res).context(
-                                "failed to read input source map from inlined base64 encoded \
-                                 string",
-                            ))
+                            read_inline()
                         } else {
                             // Load source map passed by user
                             Some(sourcemap::SourceMap::from_slice(s.as_bytes()).context(
@@ -189,17 +237,45 @@ impl Compiler {
         comments: &Comments,
         source_map: SourceMapsConfig,
         orig: Option<&sourcemap::SourceMap>,
-        minify: bool,
+        source_map_options: &SourceMapOptions,
+        cfg: codegen::Config,
     ) -> Result<TransformOutput, Error> {
         self.run(|| {
             let mut src_map_buf = vec![];
 
+            let src_map_config = if source_map.enabled() {
+                let file = if program.span().is_dummy() {
+                    None
+                } else {
+                    Some(
+                        self.cm
+                            .lookup_char_pos(program.span().lo())
+                            .file
+                            .name
+                            .clone(),
+                    )
+                };
+                let mut names = RenamedIdents {
+                    cm: &self.cm,
+                    names: Default::default(),
+                };
+                program.visit_with(&mut names);
+
+                Some(SourceMapConfig {
+                    file,
+                    options: source_map_options,
+                    names: names.names,
+                })
+            } else {
+                None
+            };
+
             let src = {
                 let mut buf = vec![];
                 {
                     let handlers = box MyHandlers;
                     let mut emitter = Emitter {
-                        cfg: codegen::Config { minify },
+                        cfg,
                         comments: Some(&comments),
                         cm: self.cm.clone(),
                         wr: box codegen::text_writer::JsWriter::new(
@@ -211,7 +287,8 @@ impl Compiler {
                             } else {
                                 None
                             },
-                        ),
+                        )
+                        .with_indent(cfg.indent),
                         handlers,
                     };
 
@@ -228,7 +305,11 @@ impl Compiler {
                         let mut buf = vec![];
 
                         self.cm
-                            .build_source_map_from(&mut src_map_buf, orig)
+                            .build_source_map_with_config(
+                                &mut src_map_buf,
+                                orig,
+                                src_map_config.as_ref().unwrap(),
+                            )
                             .to_writer(&mut buf)
                             .context("failed to write source map")?;
                         let map = String::from_utf8(buf).context("source map is not utf-8")?;
@@ -243,22 +324,27 @@ impl Compiler {
                     let mut buf = vec![];
 
                     self.cm
-                        .build_source_map(&mut src_map_buf)
+                        .build_source_map_with_config(
+                            &mut src_map_buf,
+                            orig,
+                            src_map_config.as_ref().unwrap(),
+                        )
                         .to_writer(&mut buf)
                         .context("failed to write source map file")?;
                     let map = String::from_utf8(buf).context("source map is not utf-8")?;
 
                     src.push_str("\n//# sourceMappingURL=data:application/json;base64,");
-                    base64::encode_config_buf(
-                        map.as_bytes(),
-                        base64::Config::new(base64::CharacterSet::UrlSafe, true),
-                        &mut src,
-                    );
+                    base64::encode_config_buf(map.as_bytes(), base64::STANDARD, &mut src);
                     (src, None)
                 }
             };
 
-            Ok(TransformOutput { code, map })
+            Ok(TransformOutput {
+                code,
+                map,
+                license: None,
+                diagnostics: vec![],
+            })
         })
     }
 }
@@ -271,6 +357,51 @@ impl Compiler {
             handler,
             globals: Globals::new(),
             comments: Default::default(),
+            diagnostics: None,
+        }
+    }
+
+    /// Creates a compiler which reports diagnostics as [JsonDiagnostic]
+    /// instead of printing them.
+    ///
+    /// Reported diagnostics can be collected using
+    /// [Compiler::capture_diagnostics].
+    pub fn with_json_diagnostics(cm: Arc<SourceMap>) -> Self {
+        let (handler, diagnostics) = Handler::with_json_emitter(true, false, Some(cm.clone()));
+
+        Compiler {
+            diagnostics: Some(diagnostics),
+            ..Compiler::new(cm, handler)
+        }
+    }
+
+    /// Runs `op`, and returns diagnostics reported while running it.
+    ///
+    /// Diagnostics are collected per call, so ones reported by calls running
+    /// concurrently on other threads are not mixed. This is always empty if
+    /// the compiler is not created using [Compiler::with_json_diagnostics].
+    pub fn capture_diagnostics<F, Ret>(&self, op: F) -> (Ret, Vec<JsonDiagnostic>)
+    where
+        F: FnOnce() -> Ret,
+    {
+        match self.diagnostics {
+            Some(ref d) => d.capture(op),
+            None => (op(), vec![]),
+        }
+    }
+
+    /// Runs `op`, and attaches diagnostics reported while running it to the
+    /// output or the error.
+    pub fn with_diagnostics<F>(&self, op: F) -> Result<TransformOutput, ErrorOutput>
+    where
+        F: FnOnce() -> Result<TransformOutput, Error>,
+    {
+        match self.capture_diagnostics(op) {
+            (Ok(output), diagnostics) => Ok(TransformOutput {
+                diagnostics,
+                ..output
+            }),
+            (Err(err), diagnostics) => Err(ErrorOutput::new(&err, diagnostics)),
         }
     }
 
@@ -280,13 +411,21 @@ impl Compiler {
         opts: &Options,
         name: &FileName,
     ) -> Result<BuiltConfig<impl Pass>, Error> {
+        let config = self.resolve_config(opts, name)?;
+
+        self.run(|| opts.build(&self.cm, &self.handler, opts.is_module, Some(config)))
+    }
+
+    /// Loads `.swcrc` (and `configFile`) applicable to `name`.
+    ///
+    /// Note: `opts.config` is not merged into the returned config.
+    pub fn resolve_config(&self, opts: &Options, name: &FileName) -> Result<Config, Error> {
         self.run(|| -> Result<_, Error> {
             let Options {
                 ref root,
                 root_mode,
                 swcrc,
                 config_file,
-                is_module,
                 ..
             } = opts;
             let root = root.clone().unwrap_or_else(|| {
@@ -319,9 +458,7 @@ impl Compiler {
                                 if let Some(config_file) = config_file {
                                     config.merge(&config_file.into_config(Some(path))?)
                                 }
-                                let built =
-                                    opts.build(&self.cm, &self.handler, *is_module, Some(config));
-                                return Ok(built);
+                                return Ok(config);
                             }
 
                             if dir == root && *root_mode == RootMode::Root {
@@ -332,27 +469,15 @@ impl Compiler {
                     }
 
                     let config_file = config_file.unwrap_or_else(|| Rc::default());
-                    let built = opts.build(
-                        &self.cm,
-                        &self.handler,
-                        *is_module,
-                        Some(config_file.into_config(Some(path))?),
-                    );
-                    return Ok(built);
+                    return Ok(config_file.into_config(Some(path))?);
                 }
                 _ => {}
             }
 
-            let built = opts.build(
-                &self.cm,
-                &self.handler,
-                *is_module,
-                match config_file {
-                    Some(config_file) => Some(config_file.into_config(None)?),
-                    None => Some(Rc::default().into_config(None)?),
-                },
-            );
-            Ok(built)
+            match config_file {
+                Some(config_file) => config_file.into_config(None),
+                None => Rc::default().into_config(None),
+            }
         })
         .with_context(|| format!("failed to load config for file '{:?}'", name))
     }
@@ -406,14 +531,20 @@ impl Compiler {
         config: BuiltConfig<impl Pass>,
     ) -> Result<TransformOutput, Error> {
         self.run(|| {
-            if config.minify {
-                let preserve_excl = |_: &BytePos, vc: &mut Vec<Comment>| -> bool {
-                    vc.retain(|c: &Comment| c.text.starts_with("!"));
-                    !vc.is_empty()
-                };
-                self.comments.retain_leading(preserve_excl);
-                self.comments.retain_trailing(preserve_excl);
-            }
+            let license = if config.extract_comments {
+                Some(self.extract_license_comments(program.span()))
+            } else {
+                None
+            };
+
+            let keep = config.comments.filter()?;
+            let retain = |_: &BytePos, vc: &mut Vec<Comment>| -> bool {
+                vc.retain(|c| keep(c));
+                !vc.is_empty()
+            };
+            self.comments.retain_leading(retain);
+            self.comments.retain_trailing(retain);
+
             let mut pass = config.pass;
             let program = helpers::HELPERS.set(&Helpers::new(config.external_helpers), || {
                 util::HANDLER.set(&self.handler, || {
@@ -427,14 +558,100 @@ impl Compiler {
                 &self.comments,
                 config.source_maps,
                 src_map.as_ref(),
-                config.minify,
+                &config.source_map_options,
+                config.codegen,
             )
+            .map(|output| TransformOutput { license, ..output })
         })
     }
+
+    /// Removes license comments in `span`, and returns them in source order.
+    fn extract_license_comments(&self, span: Span) -> String {
+        let mut extracted = vec![];
+        let mut extract = |pos: &BytePos, vc: &mut Vec<Comment>| -> bool {
+            if span.lo() <= *pos && *pos <= span.hi() {
+                vc.retain(|c| {
+                    if config::is_license_comment(c) {
+                        extracted.push(c.clone());
+                        return false;
+                    }
+                    true
+                });
+            }
+            !vc.is_empty()
+        };
+        self.comments.retain_leading(&mut extract);
+        self.comments.retain_trailing(&mut extract);
+
+        extracted.sort_by_key(|c| c.span.lo());
+        extracted.dedup();
+
+        extracted
+            .into_iter()
+            .map(|c| match c.kind {
+                CommentKind::Block => format!("/*{}*/", c.text),
+                CommentKind::Line => format!("//{}", c.text),
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
 }
 
 struct MyHandlers;
 
+/// Collects original names of identifiers renamed by transforms, so they can
+/// be added to `names` of source maps.
+struct RenamedIdents<'a> {
+    cm: &'a SourceMap,
+    names: HashMap<BytePos, String>,
+}
+
+impl Visit<Ident> for RenamedIdents<'_> {
+    fn visit(&mut self, i: &Ident) {
+        if i.span.is_dummy() {
+            return;
+        }
+
+        if let Ok(orig) = self.cm.span_to_snippet(i.span) {
+            let is_ident = !orig.is_empty()
+                && orig
+                    .chars()
+                    .all(|c| c == '$' || c == '_' || c.is_alphanumeric());
+            if is_ident && orig != *i.sym {
+                self.names.insert(i.span.lo(), orig);
+            }
+        }
+    }
+}
+
+struct SourceMapConfig<'a> {
+    /// The file being printed.
+    file: Option<FileName>,
+    options: &'a SourceMapOptions,
+    names: HashMap<BytePos, String>,
+}
+
+impl SourceMapGenConfig for SourceMapConfig<'_> {
+    fn file_name_to_source(&self, f: &FileName) -> String {
+        match self.options.file_name {
+            Some(ref name) if self.file.as_ref() == Some(f) => name.clone(),
+            _ => f.to_string(),
+        }
+    }
+
+    fn name_for_bytepos(&self, pos: BytePos) -> Option<&str> {
+        self.names.get(&pos).map(|s| &**s)
+    }
+
+    fn inline_sources_content(&self, _: &FileName) -> bool {
+        self.options.inline_sources_content
+    }
+
+    fn source_root(&self) -> Option<&str> {
+        self.options.source_root.as_deref()
+    }
+}
+
 impl ecmascript::codegen::Handlers for MyHandlers {}
 
 fn load_swcrc(path: &Path) -> Result<Rc, Error> {
@@ -456,12 +673,16 @@ fn load_swcrc(path: &Path) -> Result<Rc, Error> {
 
     let content = read_to_string(path).context("failed to read config (.swcrc) file")?;
 
-    match serde_json::from_str(&content) {
-        Ok(v) => return Ok(v),
-        Err(..) => {}
+    let mut rc = match serde_json::from_str(&content) {
+        Ok(v) => v,
+        Err(..) => serde_json::from_str::<Config>(&content)
+            .map(Rc::Single)
+            .map_err(convert_json_err)?,
+    };
+
+    if let Some(dir) = path.parent() {
+        rc.resolve_plugin_paths(dir);
     }
 
-    serde_json::from_str::<Config>(&content)
-        .map(Rc::Single)
-        .map_err(convert_json_err)
+    Ok(rc)
 }
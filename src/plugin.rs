@@ -0,0 +1,215 @@
+//! Loader for native plugins built with `swc_plugin`.
+
+use anyhow::{bail, Context, Error};
+use dashmap::DashMap;
+use libloading::Library;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, mem,
+    path::{Path, PathBuf},
+    slice,
+    sync::Arc,
+    time::SystemTime,
+};
+use swc_common::Fold;
+use swc_ecmascript::{
+    ast::{Module, Program, Script},
+    transforms::util::HANDLER,
+};
+use swc_plugin::{
+    codec, AbiVersionFn, FreeFn, ProcessFn, RawBuffer, ABI_VERSION, ABI_VERSION_SYMBOL,
+    FREE_SYMBOL, PROCESS_SYMBOL,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+pub struct PluginConfig {
+    /// Path to the dynamic library.
+    ///
+    /// If this is relative, it's resolved from the directory of the config
+    /// file (`.swcrc`) which contains it.
+    pub path: PathBuf,
+
+    /// Passed to the plugin.
+    #[serde(default)]
+    pub options: serde_json::Value,
+}
+
+/// A loaded native plugin.
+#[derive(Debug)]
+pub struct Plugin {
+    lib: Arc<Library>,
+    path: PathBuf,
+    options: Vec<u8>,
+}
+
+impl Plugin {
+    pub fn load(config: &PluginConfig) -> Result<Self, Error> {
+        /// Loaded libraries, keyed by the modification time as well so a
+        /// rebuilt plugin is loaded again.
+        static CACHE: Lazy<DashMap<(PathBuf, SystemTime), Arc<Library>>> =
+            Lazy::new(Default::default);
+
+        let modified = fs::metadata(&config.path)
+            .and_then(|m| m.modified())
+            .with_context(|| format!("failed to load plugin {}", config.path.display()))?;
+        let key = (config.path.clone(), modified);
+
+        let lib = match CACHE.get(&key) {
+            Some(lib) => lib.clone(),
+            None => {
+                let lib = Arc::new(load_library(&config.path)?);
+                CACHE.retain(|(path, _), _| *path != config.path);
+                CACHE.insert(key, lib.clone());
+                lib
+            }
+        };
+
+        Ok(Plugin {
+            lib,
+            path: config.path.clone(),
+            options: serde_json::to_vec(&config.options)?,
+        })
+    }
+
+    /// Runs the plugin on a program encoded using [codec].
+    fn process(&self, program: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut out = RawBuffer::empty();
+        let res = unsafe {
+            let process = self.lib.get::<ProcessFn>(PROCESS_SYMBOL)?;
+            let free = self.lib.get::<FreeFn>(FREE_SYMBOL)?;
+
+            let ok = process(
+                program.as_ptr(),
+                program.len(),
+                self.options.as_ptr(),
+                self.options.len(),
+                &mut out,
+            );
+            let res = if ok {
+                Ok(out.as_slice().to_vec())
+            } else {
+                Err(Error::msg(
+                    String::from_utf8_lossy(out.as_slice()).into_owned(),
+                ))
+            };
+            free(out);
+
+            res
+        };
+
+        res.with_context(|| format!("plugin {} failed", self.path.display()))
+    }
+}
+
+fn load_library(path: &Path) -> Result<Library, Error> {
+    let lib =
+        Library::new(path).with_context(|| format!("failed to load plugin {}", path.display()))?;
+
+    let version = unsafe {
+        let f = lib
+            .get::<AbiVersionFn>(ABI_VERSION_SYMBOL)
+            .with_context(|| {
+                format!(
+                    "{} is not a plugin (`define_plugin!` is not used)",
+                    path.display()
+                )
+            })?;
+        let mut len = 0;
+        let ptr = f(&mut len);
+        slice::from_raw_parts(ptr, len)
+    };
+    if version != ABI_VERSION.as_bytes() {
+        bail!(
+            "plugin {} is built with swc_plugin {}, but swc requires {}",
+            path.display(),
+            String::from_utf8_lossy(version),
+            ABI_VERSION
+        )
+    }
+
+    Ok(lib)
+}
+
+/// Runs plugins in order.
+///
+/// Programs are sent to plugins using [codec], so plugins should be invoked
+/// before `resolver` to avoid passing marks. A program is encoded once and the
+/// output of a plugin is passed to the next plugin as is, so it's decoded only
+/// once after all plugins.
+///
+/// Plugins are loaded when a program is processed for the first time, and
+/// errors are reported using `HANDLER`.
+#[derive(Debug, Default)]
+pub struct Plugins {
+    configs: Vec<PluginConfig>,
+    loaded: Option<Vec<Plugin>>,
+}
+
+impl Plugins {
+    pub fn new(configs: Vec<PluginConfig>) -> Self {
+        Plugins {
+            configs,
+            loaded: None,
+        }
+    }
+}
+
+impl Plugins {
+    fn process(&mut self, program: &Program) -> Result<Program, Error> {
+        if self.loaded.is_none() {
+            self.loaded = Some(
+                self.configs
+                    .iter()
+                    .map(Plugin::load)
+                    .collect::<Result<_, _>>()?,
+            );
+        }
+
+        let mut buf = codec::to_vec(program).context("failed to encode program")?;
+        for plugin in self.loaded.as_ref().unwrap() {
+            buf = plugin.process(&buf)?;
+        }
+
+        let output: Program =
+            codec::from_slice(&buf).context("failed to decode program returned from plugins")?;
+        if mem::discriminant(&output) != mem::discriminant(program) {
+            bail!("plugins should not change the type of a program")
+        }
+
+        Ok(output)
+    }
+
+    fn apply(&mut self, program: Program) -> Program {
+        if self.configs.is_empty() {
+            return program;
+        }
+
+        match self.process(&program) {
+            Ok(program) => program,
+            Err(err) => {
+                HANDLER.with(|handler| handler.err(&format!("{:?}", err)));
+                program
+            }
+        }
+    }
+}
+
+impl Fold<Module> for Plugins {
+    fn fold(&mut self, module: Module) -> Module {
+        match self.apply(Program::Module(module)) {
+            Program::Module(module) => module,
+            Program::Script(..) => unreachable!(),
+        }
+    }
+}
+
+impl Fold<Script> for Plugins {
+    fn fold(&mut self, script: Script) -> Script {
+        match self.apply(Program::Script(script)) {
+            Program::Script(script) => script,
+            Program::Module(..) => unreachable!(),
+        }
+    }
+}
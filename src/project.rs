@@ -0,0 +1,445 @@
+//! Driver for compiling a directory, with a persistent cache and watch mode.
+
+use crate::{
+    config::{Config, Merge, Options},
+    Compiler,
+};
+use anyhow::{bail, Context, Error};
+use notify::{DebouncedEvent, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use siphasher::sip::SipHasher;
+use std::{
+    collections::HashMap,
+    fs,
+    hash::{Hash, Hasher},
+    io,
+    iter::once,
+    path::{Path, PathBuf},
+    sync::{mpsc::channel, Arc},
+    time::{Duration, UNIX_EPOCH},
+};
+use swc_common::FileName;
+use swc_ecmascript::preset_env;
+
+/// Extensions of files compiled by [Project].
+const EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "ts", "tsx"];
+
+/// Files which may affect the output of all files.
+const CONFIG_FILES: &[&str] = &[".swcrc", ".browserslistrc", "browserslist", "package.json"];
+
+/// Name of the cache file, stored in the output directory.
+const CACHE_FILE: &str = ".swc-cache.json";
+
+/// Compiles all files in a source directory to an output directory.
+///
+/// Outputs are cached by the content of the input file and the config
+/// resolved for it, including the browsers selected by browserslist and the
+/// modification time of plugins, so unchanged files are not compiled again.
+/// The cache is stored in the output directory and survives across runs.
+pub struct Project {
+    c: Arc<Compiler>,
+    opts: Options,
+    src_dir: PathBuf,
+    out_dir: PathBuf,
+    cache: Cache,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Cache {
+    /// Version of swc which created the cache.
+    version: String,
+    /// Keyed by the path of the input file, relative to the source directory.
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    hash: String,
+    /// Paths relative to the output directory.
+    outputs: Vec<PathBuf>,
+}
+
+/// Result of a (re)build.
+#[derive(Debug, Default)]
+pub struct BuildReport {
+    /// Input files which are compiled.
+    pub compiled: Vec<PathBuf>,
+    /// Input files whose outputs are up to date.
+    pub cached: Vec<PathBuf>,
+    /// Input files which are removed, along with their outputs.
+    pub removed: Vec<PathBuf>,
+    pub errors: Vec<(PathBuf, Error)>,
+}
+
+impl BuildReport {
+    pub fn is_empty(&self) -> bool {
+        self.compiled.is_empty()
+            && self.cached.is_empty()
+            && self.removed.is_empty()
+            && self.errors.is_empty()
+    }
+}
+
+impl Project {
+    /// `opts.filename` is ignored.
+    pub fn new(
+        c: Arc<Compiler>,
+        opts: Options,
+        src_dir: impl AsRef<Path>,
+        out_dir: impl AsRef<Path>,
+    ) -> Result<Self, Error> {
+        let src_dir = fs::canonicalize(src_dir.as_ref()).with_context(|| {
+            format!(
+                "failed to find source directory at {}",
+                src_dir.as_ref().display()
+            )
+        })?;
+        fs::create_dir_all(out_dir.as_ref()).context("failed to create output directory")?;
+        let out_dir =
+            fs::canonicalize(out_dir.as_ref()).context("failed to find output directory")?;
+
+        let cache = fs::read(out_dir.join(CACHE_FILE))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Cache>(&bytes).ok())
+            .filter(|cache| cache.version == env!("CARGO_PKG_VERSION"))
+            .unwrap_or_else(|| Cache {
+                version: env!("CARGO_PKG_VERSION").into(),
+                entries: Default::default(),
+            });
+
+        Ok(Project {
+            c,
+            opts,
+            src_dir,
+            out_dir,
+            cache,
+        })
+    }
+
+    /// Compiles all files in the source directory, and removes outputs of
+    /// files deleted since the last build.
+    pub fn build(&mut self) -> Result<BuildReport, Error> {
+        let mut report = BuildReport::default();
+
+        let mut files = vec![];
+        self.collect_files(&self.src_dir, &mut files)
+            .context("failed to read source directory")?;
+
+        let deleted = self
+            .cache
+            .entries
+            .keys()
+            .map(|rel| self.src_dir.join(rel))
+            .filter(|path| !files.contains(path))
+            .collect::<Vec<_>>();
+        for path in deleted {
+            self.remove(&path, &mut report)?;
+        }
+
+        for path in files {
+            self.compile(&path, &mut report);
+        }
+
+        self.save_cache()?;
+        Ok(report)
+    }
+
+    /// Builds the project, and recompiles files whenever they change.
+    ///
+    /// `on_build` is called after each build. A change of `.swcrc` or a
+    /// browserslist config in the source directory invalidates all files. This
+    /// method returns only if watching fails.
+    pub fn watch<F>(&mut self, mut on_build: F) -> Result<(), Error>
+    where
+        F: FnMut(&BuildReport),
+    {
+        let (tx, rx) = channel();
+        let mut watcher = notify::watcher(tx, Duration::from_millis(100))
+            .context("failed to create file watcher")?;
+        watcher
+            .watch(&self.src_dir, RecursiveMode::Recursive)
+            .context("failed to watch source directory")?;
+
+        let report = self.build()?;
+        on_build(&report);
+
+        loop {
+            let event = rx.recv().context("file watcher is disconnected")?;
+
+            let mut full = false;
+            let mut changed = vec![];
+            for event in once(event).chain(rx.try_iter()) {
+                match event {
+                    DebouncedEvent::Create(path)
+                    | DebouncedEvent::Write(path)
+                    | DebouncedEvent::Remove(path) => changed.push(path),
+                    DebouncedEvent::Rename(from, to) => {
+                        changed.push(from);
+                        changed.push(to);
+                    }
+                    DebouncedEvent::Rescan => full = true,
+                    DebouncedEvent::Error(err, path) => {
+                        log::error!("failed to watch {:?}: {}", path, err)
+                    }
+                    _ => {}
+                }
+            }
+
+            if changed.iter().any(|path| {
+                path.file_name()
+                    .map_or(false, |name| CONFIG_FILES.iter().any(|f| name == *f))
+            }) {
+                full = true;
+            }
+
+            let report = if full {
+                self.build()?
+            } else {
+                let mut report = BuildReport::default();
+                for path in changed {
+                    if !self.is_input(&path) {
+                        continue;
+                    }
+
+                    if path.is_file() {
+                        self.compile(&path, &mut report);
+                    } else {
+                        self.remove(&path, &mut report)?;
+                    }
+                }
+                self.save_cache()?;
+                report
+            };
+
+            if !report.is_empty() {
+                on_build(&report);
+            }
+        }
+    }
+
+    fn is_input(&self, path: &Path) -> bool {
+        if path.starts_with(&self.out_dir) {
+            return false;
+        }
+        let rel = match path.strip_prefix(&self.src_dir) {
+            Ok(rel) => rel,
+            Err(..) => return false,
+        };
+        let ignored = rel.iter().any(|c| {
+            let c = c.to_string_lossy();
+            c.starts_with('.') || c == "node_modules"
+        });
+
+        !ignored && is_source_file(path)
+    }
+
+    fn collect_files(&self, dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+
+            if path.is_dir() {
+                if path != self.out_dir {
+                    self.collect_files(&path, files)?;
+                }
+            } else if self.is_input(&path) {
+                files.push(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn compile(&mut self, path: &Path, report: &mut BuildReport) {
+        match self.compile_file(path) {
+            Ok(true) => report.compiled.push(path.to_path_buf()),
+            Ok(false) => report.cached.push(path.to_path_buf()),
+            Err(err) => report.errors.push((path.to_path_buf(), err)),
+        }
+    }
+
+    /// Returns `false` if outputs are up to date.
+    fn compile_file(&mut self, path: &Path) -> Result<bool, Error> {
+        let rel = path.strip_prefix(&self.src_dir).unwrap().to_path_buf();
+        let js = rel.with_extension("js");
+
+        // `a.ts` and `a.js` cannot be compiled to the same file.
+        for ext in EXTENSIONS {
+            let other = self.src_dir.join(rel.with_extension(ext));
+            if other != path && other.is_file() && self.is_input(&other) {
+                bail!(
+                    "both {} and {} are compiled to {}",
+                    path.display(),
+                    other.display(),
+                    self.out_dir.join(&js).display()
+                )
+            }
+        }
+
+        let src = fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+
+        let mut opts = self.opts.clone();
+        opts.filename = path.to_string_lossy().into_owned();
+        let name = FileName::Real(path.to_path_buf());
+
+        let config = self.c.resolve_config(&opts, &name)?;
+        let hash = hash_of(&src, &config, &opts, path)?;
+
+        if let Some(entry) = self.cache.entries.get(&rel) {
+            if entry.hash == hash
+                && entry
+                    .outputs
+                    .iter()
+                    .all(|output| self.out_dir.join(output).exists())
+            {
+                return Ok(false);
+            }
+        }
+
+        let c = &*self.c;
+        let output = c
+            .run(|| -> Result<_, Error> {
+                let fm = c.cm.new_source_file(name, src);
+                let config = opts.build(&c.cm, &c.handler, opts.is_module, Some(config))?;
+                let (program, src_map) = c.parse_js(
+                    fm,
+                    config.target,
+                    config.syntax,
+                    config.is_module,
+                    true,
+                    &config.input_source_map,
+                )?;
+
+                c.process_js_inner(program, src_map, config)
+            })
+            .with_context(|| format!("failed to compile {}", path.display()))?;
+
+        let dst = self.out_dir.join(&js);
+        if let Some(dir) = dst.parent() {
+            fs::create_dir_all(dir).context("failed to create output directory")?;
+        }
+
+        let mut outputs = vec![js.clone()];
+        let mut code = output.code;
+        if let Some(map) = output.map {
+            let map_path = js.with_extension("js.map");
+            add_source_map_url(&mut code, &map_path);
+            fs::write(self.out_dir.join(&map_path), map).context("failed to write source map")?;
+            outputs.push(map_path);
+        }
+        fs::write(&dst, code).with_context(|| format!("failed to write {}", dst.display()))?;
+
+        self.cache.entries.insert(rel, CacheEntry { hash, outputs });
+        Ok(true)
+    }
+
+    /// Removes outputs of a deleted input file.
+    fn remove(&mut self, path: &Path, report: &mut BuildReport) -> Result<(), Error> {
+        let rel = match path.strip_prefix(&self.src_dir) {
+            Ok(rel) => rel,
+            Err(..) => return Ok(()),
+        };
+
+        if let Some(entry) = self.cache.entries.remove(rel) {
+            for output in entry.outputs {
+                let output = self.out_dir.join(output);
+                match fs::remove_file(&output) {
+                    Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                        return Err(Error::new(err)
+                            .context(format!("failed to remove {}", output.display())))
+                    }
+                    _ => {}
+                }
+            }
+            report.removed.push(path.to_path_buf());
+        }
+
+        Ok(())
+    }
+
+    fn save_cache(&self) -> Result<(), Error> {
+        let bytes = serde_json::to_vec(&self.cache).context("failed to serialize cache")?;
+        fs::write(self.out_dir.join(CACHE_FILE), bytes).context("failed to write cache")
+    }
+}
+
+/// Returns `true` if `path` has an extension compiled by swc. Type
+/// declarations (`.d.ts`) are not compiled.
+pub fn is_source_file(path: &Path) -> bool {
+    if path.to_string_lossy().ends_with(".d.ts") {
+        return false;
+    }
+
+    path.extension()
+        .map_or(false, |ext| EXTENSIONS.iter().any(|e| ext == *e))
+}
+
+/// Appends a `sourceMappingURL` comment for the source map at `map_path`,
+/// which is stored next to the output, unless `code` already has one.
+pub fn add_source_map_url(code: &mut String, map_path: &Path) {
+    if code.contains("sourceMappingURL=") {
+        return;
+    }
+
+    code.push_str(&format!(
+        "\n//# sourceMappingURL={}\n",
+        map_path.file_name().unwrap().to_string_lossy()
+    ));
+}
+
+/// Hashes everything which affects the output of a file.
+///
+/// Options are hashed as json, which has keys sorted, so the hash does not
+/// depend on the iteration order of maps. Targets of `env` are resolved, as
+/// browserslist configs may change without changing options.
+fn hash_of(src: &str, config: &Config, opts: &Options, path: &Path) -> Result<String, Error> {
+    let mut merged = config.clone();
+    if let Some(ref c) = opts.config {
+        merged.merge(c);
+    }
+
+    let targets = match merged.env {
+        Some(env) => {
+            let config_path = env.config_path.unwrap_or_else(|| path.to_path_buf());
+            let versions = preset_env::targets_to_versions(env.targets, Some(&config_path))
+                .context("failed to resolve targets of env")?;
+            Some(versions)
+        }
+        None => None,
+    };
+
+    // Plugins are reloaded if they are rebuilt.
+    let plugins: Vec<_> = merged
+        .jsc
+        .plugins
+        .iter()
+        .map(|plugin| {
+            fs::metadata(&plugin.path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_nanos().to_string())
+        })
+        .collect();
+
+    let options = serde_json::to_string(&json!({
+        "resolved": config,
+        "config": opts.config,
+        "targets": targets,
+        "plugins": plugins,
+        "sourceMaps": opts.source_maps,
+        "inputSourceMap": opts.input_source_map,
+        "sourceFileName": opts.source_file_name,
+        "sourceRoot": opts.source_root,
+        "inlineSourcesContent": opts.inline_sources_content,
+        "isModule": opts.is_module,
+    }))
+    .context("failed to serialize options")?;
+
+    let mut h = SipHasher::new();
+    src.hash(&mut h);
+    options.hash(&mut h);
+
+    Ok(format!("{:016x}", h.finish()))
+}
@@ -30,3 +30,11 @@ fn swcrc_simple() {
     let f = file("tests/swcrc_errors/simple/foo.js");
     println!("{}", f);
 }
+
+#[test]
+fn swcrc_env_query() {
+    let f = file("tests/swcrc_errors/env_query/foo.js");
+    println!("{}", f);
+
+    assert!(f.contains("unknown browser `netscape`"));
+}
@@ -0,0 +1,124 @@
+use std::{env, fs, path::Path, sync::Arc};
+use swc::{config::Options, project::Project, Compiler};
+use testing::Tester;
+
+#[test]
+fn build_uses_cache() {
+    let out_dir = env::temp_dir().join("swc-incremental-build_uses_cache");
+    let _ = fs::remove_dir_all(&out_dir);
+
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Arc::new(Compiler::new(cm.clone(), handler));
+            let opts = || Options {
+                swcrc: true,
+                is_module: true,
+                ..Default::default()
+            };
+
+            let mut project =
+                Project::new(c.clone(), opts(), "tests/incremental/src", &out_dir).unwrap();
+            let report = project.build().unwrap();
+            assert!(report.errors.is_empty(), "{:?}", report.errors);
+            assert_eq!(report.compiled.len(), 2);
+            assert!(report.cached.is_empty());
+
+            assert!(out_dir.join("a.js").exists());
+            assert!(out_dir.join("nested").join("b.js").exists());
+            assert!(!out_dir.join("c.d.js").exists());
+
+            let code = fs::read_to_string(out_dir.join("a.js")).unwrap();
+            assert!(!code.contains(": number"), "{}", code);
+
+            // The cache is loaded from the output directory.
+            let mut project =
+                Project::new(c.clone(), opts(), "tests/incremental/src", &out_dir).unwrap();
+            let report = project.build().unwrap();
+            assert!(report.compiled.is_empty());
+            assert_eq!(report.cached.len(), 2);
+
+            // Outputs removed by a user are regenerated.
+            fs::remove_file(out_dir.join("a.js")).unwrap();
+            let report = project.build().unwrap();
+            assert_eq!(
+                report.compiled,
+                vec![fs::canonicalize(Path::new("tests/incremental/src/a.ts")).unwrap()]
+            );
+
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn output_collision() {
+    let src_dir = env::temp_dir().join("swc-incremental-output_collision-src");
+    let out_dir = env::temp_dir().join("swc-incremental-output_collision-out");
+    let _ = fs::remove_dir_all(&src_dir);
+    let _ = fs::remove_dir_all(&out_dir);
+    fs::create_dir_all(&src_dir).unwrap();
+    fs::write(src_dir.join("a.ts"), "export const a: number = 1;").unwrap();
+    fs::write(src_dir.join("a.js"), "export const a = 2;").unwrap();
+
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Arc::new(Compiler::new(cm.clone(), handler));
+            let opts = Options {
+                is_module: true,
+                ..Default::default()
+            };
+
+            let mut project = Project::new(c, opts, &src_dir, &out_dir).unwrap();
+            let report = project.build().unwrap();
+            assert!(report.compiled.is_empty());
+            assert_eq!(report.errors.len(), 2);
+            for (_, err) in &report.errors {
+                assert!(err.to_string().contains("are compiled to"), "{}", err);
+            }
+            assert!(!out_dir.join("a.js").exists());
+
+            Ok(())
+        })
+        .unwrap();
+}
+
+#[test]
+fn browserslist_invalidates_cache() {
+    let src_dir = env::temp_dir().join("swc-incremental-browserslist-src");
+    let out_dir = env::temp_dir().join("swc-incremental-browserslist-out");
+    let _ = fs::remove_dir_all(&src_dir);
+    let _ = fs::remove_dir_all(&out_dir);
+    fs::create_dir_all(&src_dir).unwrap();
+    fs::write(src_dir.join(".swcrc"), r#"{ "env": { "targets": "" } }"#).unwrap();
+    fs::write(src_dir.join(".browserslistrc"), "chrome 80").unwrap();
+    fs::write(src_dir.join("a.js"), "export const f = () => 1;").unwrap();
+
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Arc::new(Compiler::new(cm.clone(), handler));
+            let opts = || Options {
+                swcrc: true,
+                is_module: true,
+                ..Default::default()
+            };
+
+            let mut project = Project::new(c.clone(), opts(), &src_dir, &out_dir).unwrap();
+            let report = project.build().unwrap();
+            assert!(report.errors.is_empty(), "{:?}", report.errors);
+            assert_eq!(report.compiled.len(), 1);
+            let code = fs::read_to_string(out_dir.join("a.js")).unwrap();
+            assert!(code.contains("=>"), "{}", code);
+
+            fs::write(src_dir.join(".browserslistrc"), "ie 11").unwrap();
+
+            let mut project = Project::new(c.clone(), opts(), &src_dir, &out_dir).unwrap();
+            let report = project.build().unwrap();
+            assert!(report.errors.is_empty(), "{:?}", report.errors);
+            assert_eq!(report.compiled.len(), 1);
+            let code = fs::read_to_string(out_dir.join("a.js")).unwrap();
+            assert!(!code.contains("=>"), "{}", code);
+
+            Ok(())
+        })
+        .unwrap();
+}
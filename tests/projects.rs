@@ -1,8 +1,20 @@
 use rayon::prelude::*;
-use std::path::Path;
+use serde_json::json;
+use std::{
+    env::{
+        self,
+        consts::{DLL_PREFIX, DLL_SUFFIX},
+    },
+    fs,
+    path::Path,
+    sync::Arc,
+};
 use swc::{
-    config::{Config, Options, SourceMapsConfig},
-    Compiler,
+    common::{FileName, FilePathMapping, SourceMap},
+    config::{
+        CommentsConfig, CommentsPolicy, Config, JscConfig, Options, OutputConfig, SourceMapsConfig,
+    },
+    Compiler, TransformOutput,
 };
 use swc_ecmascript::preset_env;
 use testing::{NormalizedOutput, StdErr, Tester};
@@ -502,3 +514,190 @@ fn issue_801() {
 
     assert!(!f.contains("function delete"));
 }
+
+#[test]
+fn plugin_load_error() {
+    let res = file("tests/projects/plugin-missing/input.js");
+
+    assert!(res.is_err(), "missing plugin should be reported");
+}
+
+#[test]
+fn plugin() {
+    // Built by cargo from `examples/rename_plugin.rs`.
+    let name = format!("{}rename_plugin{}", DLL_PREFIX, DLL_SUFFIX);
+    let built = env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .with_file_name("examples")
+        .join(&name);
+
+    // The path of the plugin is relative to `.swcrc`.
+    let dir = env::temp_dir().join("swc-plugin-test");
+    fs::create_dir_all(&dir).unwrap();
+    fs::copy(&built, dir.join(&name)).expect("failed to copy the plugin");
+    fs::write(
+        dir.join(".swcrc"),
+        json!({
+            "jsc": {
+                "plugins": [{ "path": name, "options": { "foo": "bar" } }]
+            }
+        })
+        .to_string(),
+    )
+    .unwrap();
+    fs::write(dir.join("input.js"), "foo(baz);").unwrap();
+
+    let f = file(dir.join("input.js").to_str().unwrap()).unwrap();
+    println!("{}", f);
+
+    assert!(f.contains("bar(baz)"));
+
+    // Plugins should be invoked for scripts too.
+    let f = Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+
+            let fm = cm.load_file(&dir.join("input.js")).unwrap();
+            let output = c
+                .process_js_file(
+                    fm,
+                    &Options {
+                        swcrc: true,
+                        is_module: false,
+                        ..Default::default()
+                    },
+                )
+                .expect("failed to process file");
+            if c.handler.has_errors() {
+                return Err(());
+            }
+
+            Ok(output.code)
+        })
+        .unwrap();
+    println!("{}", f);
+
+    assert!(f.contains("bar(baz)"));
+}
+
+#[test]
+fn minify() {
+    let f = file("tests/projects/minify/input.js").unwrap();
+    println!("{}", f);
+
+    assert!(!f.contains("longName"), "parameters should be mangled");
+    assert!(f.contains("foo"), "exported function should not be mangled");
+    assert!(f.contains("!0"));
+}
+
+#[test]
+fn imported_enums() {
+    let f = file("tests/projects/imported-enums/input.ts").unwrap();
+    println!("{}", f);
+
+    assert!(!f.contains("Kind"), "imported enum should be inlined");
+    assert!(f.contains("'name'"));
+}
+
+fn file_output(f: &str, config: Config) -> TransformOutput {
+    Tester::new()
+        .print_errors(|cm, handler| {
+            let c = Compiler::new(cm.clone(), handler);
+
+            let fm = cm.load_file(Path::new(f)).expect("failed to load file");
+            let output = c
+                .process_js_file(
+                    fm,
+                    &Options {
+                        is_module: true,
+                        swcrc: false,
+                        config: Some(config),
+                        ..Default::default()
+                    },
+                )
+                .expect("failed to process file");
+
+            Ok(output)
+        })
+        .unwrap()
+}
+
+#[test]
+fn comments_some() {
+    let output = file_output(
+        "tests/projects/comments/input.js",
+        Config {
+            jsc: JscConfig {
+                output: OutputConfig {
+                    comments: Some(CommentsConfig::Policy(CommentsPolicy::Some)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    println!("{}", output.code);
+
+    assert!(output.code.contains("banner v1.0.0"));
+    assert!(output.code.contains("@license MIT"));
+    assert!(!output.code.contains("Not a license"));
+    assert!(!output.code.contains("internal"));
+    assert!(output.license.is_none());
+}
+
+#[test]
+fn comments_extract() {
+    let output = file_output(
+        "tests/projects/comments/input.js",
+        Config {
+            jsc: JscConfig {
+                output: OutputConfig {
+                    extract_comments: Some(true),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    );
+    println!("{}", output.code);
+
+    assert!(!output.code.contains("banner v1.0.0"));
+    assert!(!output.code.contains("@license MIT"));
+    assert!(output.code.contains("Not a license"));
+    assert_eq!(
+        output.license.as_deref(),
+        Some("/*! banner v1.0.0 */\n\n/**\n * @license MIT\n */")
+    );
+}
+
+#[test]
+fn json_diagnostics_per_call() {
+    let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let c = Compiler::with_json_diagnostics(cm.clone());
+    let opts = Options {
+        is_module: true,
+        swcrc: false,
+        ..Default::default()
+    };
+
+    let err = c
+        .with_diagnostics(|| {
+            let fm = cm.new_source_file(FileName::Anon, "let = ;".into());
+            c.process_js_file(fm, &opts)
+        })
+        .unwrap_err();
+    assert!(!err.diagnostics.is_empty());
+
+    // Diagnostics of the previous call are not reported again.
+    let output = c
+        .with_diagnostics(|| {
+            let fm = cm.new_source_file(FileName::Anon, "let a = 1;".into());
+            c.process_js_file(fm, &opts)
+        })
+        .unwrap();
+    assert!(output.diagnostics.is_empty(), "{:?}", output.diagnostics);
+}
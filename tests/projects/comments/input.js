@@ -0,0 +1,10 @@
+/*! banner v1.0.0 */
+/**
+ * @license MIT
+ */
+
+// Not a license
+export function foo() {
+    /* internal */
+    return 1;
+}
@@ -0,0 +1,3 @@
+import { Kind, other } from './consts';
+
+other(Kind.Big, Kind.Name);
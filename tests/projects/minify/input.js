@@ -0,0 +1,6 @@
+export function foo(longName) {
+    if (longName) {
+        console.log(longName);
+    }
+    return true;
+}
@@ -366,6 +366,10 @@ impl Validate<ClassMember> for Analyzer<'_, '_> {
             swc_ecma_ast::ClassMember::TsIndexSignature(v) => {
                 Some(ty::ClassMember::IndexSignature(v.validate_with(self)?))
             }
+            swc_ecma_ast::ClassMember::StaticBlock(v) => {
+                v.body.validate_with(self)?;
+                None
+            }
         })
     }
 }
@@ -828,6 +832,7 @@ impl Validate<Class> for Analyzer<'_, '_> {
                             ClassMember::ClassProp(_) => true,
                             ClassMember::PrivateProp(_) => true,
                             ClassMember::TsIndexSignature(_) => true,
+                            ClassMember::StaticBlock(_) => true,
                             ClassMember::Method(m) => match &mut m.key {
                                 PropName::Computed(c) => match c.expr.validate_with(child) {
                                     Ok(ty) => {
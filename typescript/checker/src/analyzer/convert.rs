@@ -325,6 +325,15 @@ impl Validate<TsTupleType> for Analyzer<'_, '_> {
     }
 }
 
+#[validator]
+impl Validate<TsTupleElement> for Analyzer<'_, '_> {
+    type Output = ValidationResult;
+
+    fn validate(&mut self, e: &mut TsTupleElement) -> Self::Output {
+        e.ty.validate_with(self)
+    }
+}
+
 #[validator]
 impl Validate<TsConditionalType> for Analyzer<'_, '_> {
     type Output = ValidationResult<Conditional>;
@@ -675,7 +684,7 @@ pub(crate) fn default_any_array_pat(arr: &mut ArrayPat) {
                 .iter_mut()
                 .map(|elem| {
                     // any
-                    match elem {
+                    let ty = match elem {
                         Some(Pat::Array(ref mut arr)) => {
                             default_any_array_pat(arr);
                             arr.type_ann.take().unwrap().type_ann
@@ -689,6 +698,12 @@ pub(crate) fn default_any_array_pat(arr: &mut ArrayPat) {
                             span: DUMMY_SP,
                             kind: TsKeywordTypeKind::TsAnyKeyword,
                         }),
+                    };
+
+                    TsTupleElement {
+                        span: DUMMY_SP,
+                        label: None,
+                        ty: *ty,
                     }
                 })
                 .collect(),
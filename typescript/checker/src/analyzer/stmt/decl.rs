@@ -53,7 +53,7 @@ impl Validate<VarDecl> for Analyzer<'_, '_> {
                             Some(mut pat) => {
                                 //
                                 if i < tuple.elem_types.len() {
-                                    pat.set_ty(tuple.elem_types[i].clone().into())
+                                    pat.set_ty(Some(box tuple.elem_types[i].ty.clone()))
                                 }
 
                                 var.decls.push(VarDeclarator {
@@ -28,7 +28,7 @@ use fxhash::FxHashMap;
 use std::{path::PathBuf, sync::Arc};
 use swc_atoms::JsWord;
 use swc_common::{errors::Handler, FoldWith, Globals, SourceMap, Span, VisitMutWith};
-use swc_ecma_ast::Module;
+use swc_ecma_ast::{Expr, Lit, Module, TsEnumMemberId};
 use swc_ecma_parser::{
     lexer::Lexer, JscTarget, Parser, Session, SourceFileInput, Syntax, TsConfig,
 };
@@ -88,6 +88,39 @@ impl ModuleTypeInfo {
         self.types.extend(other.types);
         self.vars.extend(other.vars);
     }
+
+    /// Returns values of members of exported `const enum`s, keyed by the name
+    /// of the enum and then the name of the member.
+    ///
+    /// Members which cannot be computed at compile time are not included.
+    pub fn const_enums(&self) -> FxHashMap<JsWord, FxHashMap<JsWord, Lit>> {
+        self.types
+            .iter()
+            .flat_map(|(id, types)| types.iter().map(move |ty| (id, ty)))
+            .filter_map(|(id, ty)| match ty.normalize() {
+                Type::Enum(e) if e.is_const => {
+                    let values = e
+                        .members
+                        .iter()
+                        .filter_map(|m| match m.val {
+                            Expr::Lit(ref lit @ Lit::Num(..))
+                            | Expr::Lit(ref lit @ Lit::Str(..)) => {
+                                let name = match m.id {
+                                    TsEnumMemberId::Ident(ref i) => i.sym.clone(),
+                                    TsEnumMemberId::Str(ref s) => s.value.clone(),
+                                };
+                                Some((name, lit.clone()))
+                            }
+                            _ => None,
+                        })
+                        .collect();
+
+                    Some((JsWord::from(id.as_str()), values))
+                }
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -195,21 +228,11 @@ impl Checker {
             );
             let mut parser = Parser::new_from(session, lexer);
 
-            let module = parser
-                .parse_typescript_module()
-                .map_err(|mut e| {
-                    e.emit();
-                    ()
-                })
-                .ok()
-                .unwrap_or_else(|| {
-                    println!("Parser.parse_module returned Err()");
-                    Module {
-                        span: Default::default(),
-                        body: Default::default(),
-                        shebang: None,
-                    }
-                });
+            // Keep checking files with syntax errors.
+            let (module, errors) = parser.parse_typescript_module_with_recovery();
+            for e in errors {
+                e.into_diagnostic(&self.handler).emit();
+            }
             module
         });
 
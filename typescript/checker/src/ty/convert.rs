@@ -154,7 +154,15 @@ impl From<ty::Tuple> for TsType {
     fn from(t: ty::Tuple) -> Self {
         TsType::TsTupleType(TsTupleType {
             span: t.span,
-            elem_types: t.types.into_iter().map(|v| box v.into()).collect(),
+            elem_types: t
+                .types
+                .into_iter()
+                .map(|v| TsTupleElement {
+                    span: v.span(),
+                    label: None,
+                    ty: v.into(),
+                })
+                .collect(),
         })
     }
 }
@@ -207,6 +215,7 @@ impl From<ty::Constructor> for TsType {
                 params: t.params.into_iter().map(From::from).collect(),
                 type_params: t.type_params.map(From::from),
                 type_ann: t.type_ann.into(),
+                is_abstract: false,
             },
         ))
     }
@@ -363,7 +363,9 @@ fn do_test(treat_error_as_bug: bool, file_name: &Path, mode: Mode) -> Result<(),
                                     JscTarget::Es2016 => Lib::load("es2016"),
                                     JscTarget::Es2017 => Lib::load("es2017"),
                                     JscTarget::Es2018 => Lib::load("es2018"),
-                                    JscTarget::Es2019 => Lib::load("es2019"),
+                                    JscTarget::Es2019 | JscTarget::Es2020 | JscTarget::Es2021 => {
+                                        Lib::load("es2019")
+                                    }
                                 };
                             } else if s.starts_with("strict:") {
                                 let strict = s["strict:".len()..].trim().parse().unwrap();
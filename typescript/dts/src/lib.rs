@@ -506,6 +506,8 @@ impl Fold<Vec<ClassMember>> for TypeResolver {
                         _ => {}
                     }
                 }
+                // Declarations do not have static blocks.
+                ClassMember::StaticBlock(..) => continue,
                 _ => {}
             }
 
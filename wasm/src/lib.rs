@@ -1,19 +1,12 @@
 #![feature(box_syntax)]
 
 use once_cell::sync::Lazy;
-use std::{
-    fmt::{self, Display, Formatter},
-    io::{self, Write},
-    sync::{Arc, RwLock},
-};
+use std::sync::Arc;
 use swc::{
-    common::{
-        errors::{EmitterWriter, Handler, HandlerFlags, SourceMapperDyn},
-        FileName, FilePathMapping, SourceMap,
-    },
+    common::{FileName, FilePathMapping, SourceMap},
     config::{InputSourceMap, Options, ParseOptions, SourceMapsConfig},
     ecmascript::ast::Program,
-    Compiler,
+    Compiler, ErrorOutput,
 };
 use wasm_bindgen::prelude::*;
 
@@ -25,11 +18,11 @@ pub fn parse_sync(s: &str, opts: JsValue) -> Result<JsValue, JsValue> {
         .into_serde()
         .map_err(|err| format!("failed to parse options: {}", err))?;
 
-    let (c, errors) = compiler();
+    let c = compiler();
 
     let fm = c.cm.new_source_file(FileName::Anon, s.into());
-    let (prog, src_map) = c
-        .parse_js(
+    let (res, diagnostics) = c.capture_diagnostics(|| {
+        c.parse_js(
             fm,
             opts.target,
             opts.syntax,
@@ -37,7 +30,8 @@ pub fn parse_sync(s: &str, opts: JsValue) -> Result<JsValue, JsValue> {
             opts.comments,
             &InputSourceMap::Bool(false),
         )
-        .map_err(|err| format!("failed to parse: {}\n{}", err, errors))?;
+    });
+    let (prog, src_map) = res.map_err(|err| to_js_error(ErrorOutput::new(&err, diagnostics)))?;
 
     let mut source_map = vec![];
     if let Some(src_map) = src_map {
@@ -64,21 +58,24 @@ pub fn print_sync(s: JsValue, opts: JsValue) -> Result<JsValue, JsValue> {
         .into_serde()
         .map_err(|err| format!("failed to parse options: {}", err))?;
 
-    let (c, errors) = compiler();
+    let c = compiler();
 
     let s = c
-        .print(
-            &program,
-            c.comments(),
-            opts.source_maps
-                .clone()
-                .unwrap_or(SourceMapsConfig::Bool(false)),
-            None,
-            opts.config.unwrap_or_default().minify.unwrap_or_default(),
-        )
-        .map_err(|err| format!("failed to print: {}\n{}", err, errors))?;
-
-    Ok(JsValue::from_serde(&s).map_err(|err| format!("failed to print: {}\n{}", err, errors))?)
+        .with_diagnostics(|| {
+            c.print(
+                &program,
+                c.comments(),
+                opts.source_maps
+                    .clone()
+                    .unwrap_or(SourceMapsConfig::Bool(false)),
+                None,
+                &opts.source_map_options(),
+                opts.config.unwrap_or_default().codegen_config(),
+            )
+        })
+        .map_err(to_js_error)?;
+
+    Ok(JsValue::from_serde(&s).map_err(|err| format!("failed to print: {}", err))?)
 }
 
 #[wasm_bindgen(js_name = "transformSync")]
@@ -89,24 +86,18 @@ pub fn transform_sync(s: &str, opts: JsValue) -> Result<JsValue, JsValue> {
         .into_serde()
         .map_err(|err| format!("failed to parse options: {}", err))?;
 
-    let (c, errors) = compiler();
+    let c = compiler();
 
     let fm = c.cm.new_source_file(FileName::Anon, s.into());
     let out = c
-        .process_js_file(fm, &opts)
-        .map_err(|err| format!("failed to process code: {}\n{}", err, errors))?;
+        .with_diagnostics(|| c.process_js_file(fm, &opts))
+        .map_err(to_js_error)?;
 
     Ok(JsValue::from_serde(&out).unwrap())
 }
 
-fn compiler() -> (Compiler, BufferedError) {
-    let cm = codemap();
-
-    let (handler, errors) = new_handler(cm.clone());
-
-    let c = Compiler::new(cm.clone(), handler);
-
-    (c, errors)
+fn compiler() -> Compiler {
+    Compiler::with_json_diagnostics(codemap())
 }
 
 /// Get global sourcemap
@@ -117,46 +108,7 @@ fn codemap() -> Arc<SourceMap> {
     CM.clone()
 }
 
-/// Creates a new handler which emits to returned buffer.
-fn new_handler(cm: Arc<SourceMapperDyn>) -> (Handler, BufferedError) {
-    let e = BufferedError::default();
-
-    let handler = Handler::with_emitter_and_flags(
-        Box::new(EmitterWriter::new(
-            Box::new(e.clone()),
-            Some(cm.clone()),
-            false,
-            false,
-        )),
-        HandlerFlags {
-            treat_err_as_bug: false,
-            can_emit_warnings: true,
-            ..Default::default()
-        },
-    );
-
-    (handler, e)
-}
-
-#[derive(Clone, Default)]
-pub(crate) struct BufferedError(Arc<RwLock<String>>);
-
-impl Write for BufferedError {
-    fn write(&mut self, d: &[u8]) -> io::Result<usize> {
-        self.0
-            .write()
-            .unwrap()
-            .push_str(&String::from_utf8_lossy(d));
-
-        Ok(d.len())
-    }
-    fn flush(&mut self) -> io::Result<()> {
-        Ok(())
-    }
-}
-
-impl Display for BufferedError {
-    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        Display::fmt(&self.0.read().unwrap(), f)
-    }
+/// Converts `output` into an object with `message` and `diagnostics`.
+fn to_js_error(output: ErrorOutput) -> JsValue {
+    JsValue::from_serde(&output).unwrap_or_else(|_| JsValue::from_str(&output.message))
 }